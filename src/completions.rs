@@ -0,0 +1,405 @@
+use std::fmt::Write;
+
+/// Shells `motdyn completions` can write a script for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl Shell {
+    pub const NAMES: &'static [&'static str] = &["bash", "zsh", "fish", "powershell"];
+
+    pub fn parse_name(value: &str) -> Option<Self> {
+        match value {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            "powershell" | "pwsh" => Some(Self::Powershell),
+            _ => None,
+        }
+    }
+}
+
+/// What to offer after an option that takes a value.
+#[derive(Clone, Copy)]
+enum ValueHint {
+    Path,
+    Free,
+    Choices(&'static [&'static str]),
+}
+
+/// The root command (`name` empty) or a subcommand: its options and the bare
+/// words it accepts, such as further subcommands.
+struct Command {
+    name: &'static str,
+    options: Vec<&'static str>,
+    words: Vec<&'static str>,
+}
+
+/// Every option that takes a value, whichever command it appears under.
+const VALUE_OPTIONS: &[(&str, ValueHint)] = &[
+    ("--profile", ValueHint::Choices(&["auto", "full", "basic"])),
+    ("--color", ValueHint::Choices(&["auto", "always", "never"])),
+    (
+        "--format",
+        ValueHint::Choices(&["default", "ansi-safe", "toml", "json"]),
+    ),
+    ("--json-style", ValueHint::Choices(&["raw", "human"])),
+    ("--config", ValueHint::Path),
+    ("--file", ValueHint::Path),
+    ("--output", ValueHint::Path),
+    ("--text", ValueHint::Free),
+    ("--env-prefix", ValueHint::Free),
+    ("--width", ValueHint::Free),
+    (
+        "--target",
+        ValueHint::Choices(&["profile", "bash_profile", "bash_login", "zprofile"]),
+    ),
+    (
+        "--method",
+        ValueHint::Choices(&["profile", "sshrc", "motd"]),
+    ),
+];
+
+/// Mirrors what `parse_cli` accepts in this build.
+fn commands() -> Vec<Command> {
+    let mut root_options = vec!["-h", "--help", "-V", "--version"];
+    #[cfg(feature = "full")]
+    root_options.extend([
+        "-v",
+        "--verbose",
+        "--profile",
+        "--plain",
+        "--color",
+        "--no-color",
+        "--compact",
+        "--section-headers",
+        "--format",
+        "--json",
+        "--json-style",
+        "--debug",
+        "--welcome-only",
+        "-q",
+        "--quiet",
+        "--minimal",
+        "--check",
+        "--force",
+        "--config",
+        "--width",
+    ]);
+    root_options.extend(["--text", "--file", "--env-prefix"]);
+
+    let mut root_words = Vec::new();
+    let mut commands = Vec::new();
+    #[cfg(feature = "install-hooks")]
+    for name in ["install", "uninstall", "status"] {
+        root_words.push(name);
+        commands.push(Command {
+            name,
+            options: vec!["-h", "--help", "--user", "--target", "--method"],
+            words: Vec::new(),
+        });
+    }
+    #[cfg(feature = "full")]
+    {
+        for name in ["check-update", "preview"] {
+            root_words.push(name);
+            commands.push(Command {
+                name,
+                options: root_options.clone(),
+                words: Vec::new(),
+            });
+        }
+        root_words.extend(["config", "generate"]);
+        commands.push(Command {
+            name: "config",
+            options: vec!["-h", "--help", "--config"],
+            words: vec!["validate"],
+        });
+        commands.push(Command {
+            name: "generate",
+            options: vec!["-h", "--help", "--output", "--config", "--profile"],
+            words: Vec::new(),
+        });
+    }
+    root_words.push("completions");
+    commands.push(Command {
+        name: "completions",
+        options: vec!["-h", "--help"],
+        words: Shell::NAMES.to_vec(),
+    });
+
+    commands.insert(
+        0,
+        Command {
+            name: "",
+            options: root_options,
+            words: root_words,
+        },
+    );
+    commands
+}
+
+/// The completion script for `shell`, meant to be redirected into the
+/// shell's completion directory.
+pub fn script(shell: Shell) -> String {
+    let commands = commands();
+    match shell {
+        Shell::Bash => bash_script(&commands),
+        Shell::Zsh => zsh_script(&commands),
+        Shell::Fish => fish_script(&commands),
+        Shell::Powershell => powershell_script(&commands),
+    }
+}
+
+fn subcommand_pattern(commands: &[Command]) -> String {
+    commands
+        .iter()
+        .filter(|command| !command.name.is_empty())
+        .map(|command| command.name)
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn candidates(command: &Command) -> String {
+    command
+        .words
+        .iter()
+        .chain(&command.options)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bash_script(commands: &[Command]) -> String {
+    let mut out = String::from(
+        "_motdyn() {\n    local cur prev cmd word opts\n    cur=\"${COMP_WORDS[COMP_CWORD]}\"\n    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n    cmd=\"\"\n    for word in \"${COMP_WORDS[@]:1:COMP_CWORD-1}\"; do\n        case \"$word\" in\n",
+    );
+    let _ = writeln!(
+        out,
+        "            {}) cmd=\"$word\"; break ;;",
+        subcommand_pattern(commands)
+    );
+    out.push_str("        esac\n    done\n\n    case \"$prev\" in\n");
+    for (option, hint) in VALUE_OPTIONS {
+        let reply = match hint {
+            ValueHint::Path => "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string(),
+            ValueHint::Free => "COMPREPLY=()".to_string(),
+            ValueHint::Choices(values) => format!(
+                "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                values.join(" ")
+            ),
+        };
+        let _ = writeln!(out, "        {}) {}; return ;;", option, reply);
+    }
+    out.push_str("    esac\n\n    case \"$cmd\" in\n");
+    for command in commands {
+        let _ = writeln!(
+            out,
+            "        {}) opts=\"{}\" ;;",
+            if command.name.is_empty() {
+                "\"\""
+            } else {
+                command.name
+            },
+            candidates(command)
+        );
+    }
+    out.push_str(
+        "    esac\n    COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))\n}\n\ncomplete -F _motdyn motdyn\n",
+    );
+    out
+}
+
+fn zsh_script(commands: &[Command]) -> String {
+    let mut out = String::from("#compdef motdyn\n\n_motdyn() {\n    local cmd\n");
+    let _ = writeln!(
+        out,
+        "    cmd=${{words[2,CURRENT-1][(r)({})]}}\n",
+        subcommand_pattern(commands)
+    );
+    out.push_str("    case $words[CURRENT-1] in\n");
+    for (option, hint) in VALUE_OPTIONS {
+        let action = match hint {
+            ValueHint::Path => "_files".to_string(),
+            ValueHint::Free => "_message value".to_string(),
+            ValueHint::Choices(values) => format!("compadd -- {}", values.join(" ")),
+        };
+        let _ = writeln!(out, "        {}) {}; return ;;", option, action);
+    }
+    out.push_str("    esac\n\n    case $cmd in\n");
+    for command in commands {
+        let _ = writeln!(
+            out,
+            "        {}) compadd -- {} ;;",
+            if command.name.is_empty() {
+                "''"
+            } else {
+                command.name
+            },
+            candidates(command)
+        );
+    }
+    out.push_str("    esac\n}\n\n_motdyn \"$@\"\n");
+    out
+}
+
+fn fish_script(commands: &[Command]) -> String {
+    let mut out = String::from("complete -c motdyn -f\n");
+    for command in commands {
+        let condition = if command.name.is_empty() {
+            "__fish_use_subcommand".to_string()
+        } else {
+            format!("__fish_seen_subcommand_from {}", command.name)
+        };
+        if !command.words.is_empty() {
+            let _ = writeln!(
+                out,
+                "complete -c motdyn -n '{}' -a '{}'",
+                condition,
+                command.words.join(" ")
+            );
+        }
+        for option in &command.options {
+            let flag = match option.strip_prefix("--") {
+                Some(long) => format!("-l {}", long),
+                None => format!("-s {}", option.trim_start_matches('-')),
+            };
+            let value = match VALUE_OPTIONS.iter().find(|(name, _)| name == option) {
+                Some((_, ValueHint::Path)) => " -r -F".to_string(),
+                Some((_, ValueHint::Free)) => " -x".to_string(),
+                Some((_, ValueHint::Choices(values))) => {
+                    format!(" -x -a '{}'", values.join(" "))
+                }
+                None => String::new(),
+            };
+            let _ = writeln!(
+                out,
+                "complete -c motdyn -n '{}' {}{}",
+                condition, flag, value
+            );
+        }
+    }
+    out
+}
+
+fn powershell_script(commands: &[Command]) -> String {
+    let mut out = String::from(
+        "Register-ArgumentCompleter -Native -CommandName motdyn -ScriptBlock {\n    param($wordToComplete, $commandAst, $cursorPosition)\n    $words = @($commandAst.CommandElements | Select-Object -Skip 1 | ForEach-Object { $_.ToString() })\n    if ($wordToComplete) { $words = @($words | Select-Object -SkipLast 1) }\n    $previous = if ($words.Count -gt 0) { $words[-1] } else { '' }\n",
+    );
+    let _ = writeln!(
+        out,
+        "    $command = @($words | Where-Object {{ $_ -match '^({})$' }} | Select-Object -First 1)[0]",
+        subcommand_pattern(commands)
+    );
+    out.push_str("    $candidates = switch ($previous) {\n");
+    for (option, hint) in VALUE_OPTIONS {
+        match hint {
+            ValueHint::Choices(values) => {
+                let _ = writeln!(out, "        '{}' {{ @({}) }}", option, quoted_list(values));
+            }
+            ValueHint::Path | ValueHint::Free => {
+                let _ = writeln!(out, "        '{}' {{ return }}", option);
+            }
+        }
+    }
+    out.push_str("        default {\n            switch ($command) {\n");
+    for command in commands {
+        let words = command
+            .words
+            .iter()
+            .chain(&command.options)
+            .copied()
+            .collect::<Vec<_>>();
+        let _ = writeln!(
+            out,
+            "                {} {{ @({}) }}",
+            if command.name.is_empty() {
+                "$null".to_string()
+            } else {
+                format!("'{}'", command.name)
+            },
+            quoted_list(&words)
+        );
+    }
+    out.push_str(
+        "            }\n        }\n    }\n    $candidates | Where-Object { $_ -like \"$wordToComplete*\" } | ForEach-Object {\n        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)\n    }\n}\n",
+    );
+    out
+}
+
+fn quoted_list(values: &[&str]) -> String {
+    values
+        .iter()
+        .map(|value| format!("'{}'", value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shell_names() {
+        assert_eq!(Shell::parse_name("bash"), Some(Shell::Bash));
+        assert_eq!(Shell::parse_name("pwsh"), Some(Shell::Powershell));
+        assert_eq!(Shell::parse_name("tcsh"), None);
+    }
+
+    #[test]
+    fn bash_script_completes_subcommands_and_option_values() {
+        let script = script(Shell::Bash);
+        assert!(script.ends_with("complete -F _motdyn motdyn\n"));
+        assert!(
+            script
+                .contains("        completions) opts=\"bash zsh fish powershell -h --help\" ;;\n")
+        );
+        assert!(script.contains("--config) COMPREPLY=($(compgen -f -- \"$cur\")); return ;;"));
+        #[cfg(feature = "full")]
+        assert!(script.contains(
+            "--format) COMPREPLY=($(compgen -W \"default ansi-safe toml json\" -- \"$cur\")); return ;;"
+        ));
+        #[cfg(feature = "install-hooks")]
+        assert!(script.contains("            install|uninstall|status|"));
+    }
+
+    #[test]
+    fn every_option_with_a_value_has_a_hint() {
+        let value_taking = [
+            "--profile",
+            "--color",
+            "--format",
+            "--json-style",
+            "--config",
+            "--file",
+            "--output",
+            "--text",
+            "--env-prefix",
+            "--width",
+            "--target",
+            "--method",
+        ];
+        for command in commands() {
+            for option in command.options {
+                let hinted = VALUE_OPTIONS.iter().any(|(name, _)| *name == option);
+                assert_eq!(hinted, value_taking.contains(&option), "{}", option);
+            }
+        }
+    }
+
+    #[test]
+    fn fish_and_zsh_scripts_name_every_subcommand() {
+        let fish = script(Shell::Fish);
+        let zsh = script(Shell::Zsh);
+        assert!(zsh.starts_with("#compdef motdyn\n"));
+        for command in commands().iter().filter(|command| !command.name.is_empty()) {
+            assert!(fish.contains(&format!("__fish_seen_subcommand_from {}'", command.name)));
+            assert!(zsh.contains(&format!("        {}) compadd -- ", command.name)));
+        }
+        assert!(fish.contains("complete -c motdyn -n '__fish_seen_subcommand_from completions' -a 'bash zsh fish powershell'\n"));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::ffi::OsStr;
 use std::fmt;
@@ -10,15 +11,60 @@ use serde::de::{self, Deserializer};
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct RawConfig {
+    include: Option<String>,
     welcome: Option<String>,
     #[serde(default)]
     welcome_sources: Option<Vec<String>>,
+    ascii_art_file: Option<String>,
     farewell: Option<String>,
+    farewells: Option<Vec<String>>,
+    farewell_on_alert: Option<bool>,
+    header: Option<String>,
+    show_header: Option<bool>,
+    separator: Option<String>,
+    display_hostname: Option<String>,
+    template: Option<String>,
+    time_format: Option<String>,
+    time_24h: Option<bool>,
+    timezone: Option<String>,
+    disk_no_alert: Option<Vec<String>>,
+    disk_labels: Option<BTreeMap<String, String>>,
+    colors: Option<BTreeMap<String, String>>,
+    disk_timeout_ms: Option<u64>,
+    disk_mounts: Option<Vec<String>>,
+    disk_all_local: Option<bool>,
     #[serde(default, deserialize_with = "deserialize_module_list")]
     modules: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_section_list")]
+    sections: Option<Vec<String>>,
     remote_welcome: Option<RemoteWelcomeConfig>,
     service_status: Option<ServiceStatusConfig>,
     output: Option<OutputConfig>,
+    spacing: Option<SpacingConfig>,
+    thresholds: Option<ThresholdsConfig>,
+    cert_checks: Option<Vec<CertCheckConfig>>,
+    service_fd_checks: Option<Vec<ServiceFdCheckConfig>>,
+    scheduled_banners: Option<Vec<ScheduledBannerConfig>>,
+    recent_logins: Option<RecentLoginsConfig>,
+    log_to_syslog: Option<bool>,
+    show_cpu_topology: Option<bool>,
+    show_swap_activity: Option<bool>,
+    show_cpu_usage: Option<bool>,
+    cpu_usage_sample_ms: Option<u64>,
+    resolve_hostname: Option<bool>,
+    cache_ttl_secs: Option<u64>,
+    show_gpu: Option<bool>,
+    interfaces: Option<Vec<String>>,
+    show_containers: Option<bool>,
+    show_net_throughput: Option<bool>,
+    net_throughput_sample_ms: Option<u64>,
+    uptime_format: Option<String>,
+    show_pressure: Option<bool>,
+    environment: Option<String>,
+    environments: Option<BTreeMap<String, EnvironmentConfig>>,
+    ssh: Option<Box<RawConfig>>,
+    local: Option<Box<RawConfig>>,
+    hosts: Option<BTreeMap<String, RawConfig>>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize)]
@@ -44,19 +90,164 @@ pub struct OutputConfig {
     pub compact: Option<bool>,
     pub plain: Option<bool>,
     pub section_headers: Option<bool>,
+    pub ansi_safe: Option<bool>,
+    pub clip_ascii_art: Option<bool>,
+    pub max_ascii_lines: Option<usize>,
+    pub max_disk_lines: Option<usize>,
+    pub disk_display: Option<String>,
+    pub progress_bars: Option<bool>,
+    pub progress_bar_width: Option<usize>,
+    pub width: Option<usize>,
+    pub temp_unit: Option<String>,
     #[serde(default, deserialize_with = "deserialize_hidden_field_list")]
     pub hidden_fields: Option<Vec<String>>,
 }
 
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpacingConfig {
+    pub before_welcome: Option<usize>,
+    pub after_welcome: Option<usize>,
+    pub before_farewell: Option<usize>,
+    pub gutter: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdsConfig {
+    pub mem_warn: Option<f64>,
+    pub mem_crit: Option<f64>,
+    pub swap_warn: Option<f64>,
+    pub swap_crit: Option<f64>,
+    pub disk_warn: Option<f64>,
+    pub disk_crit: Option<f64>,
+    pub temp_warn: Option<f64>,
+    pub temp_crit: Option<f64>,
+    pub load_warn: Option<f64>,
+    pub load_crit: Option<f64>,
+    pub pressure_warn: Option<f64>,
+    pub pressure_crit: Option<f64>,
+    pub battery_low: Option<f64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CertCheckConfig {
+    pub label: String,
+    pub path: String,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceFdCheckConfig {
+    pub label: String,
+    pub process_name: Option<String>,
+    pub pid_file: Option<String>,
+}
+
+/// Dates are kept as written; malformed entries are skipped at render time.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledBannerConfig {
+    pub start: String,
+    pub end: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RecentLoginsConfig {
+    pub limit: Option<usize>,
+    pub state_path: Option<String>,
+}
+
+/// One `[environments.<name>]` table: how the host name is highlighted and
+/// the banner printed above the welcome text while `environment` names it.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnvironmentConfig {
+    pub color: Option<String>,
+    pub banner: Option<String>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct MotdConfig {
     pub welcome: Option<String>,
     pub welcome_sources: Option<Vec<String>>,
+    pub ascii_art_file: Option<String>,
     pub farewell: Option<String>,
+    pub farewells: Option<Vec<String>>,
+    pub farewell_on_alert: Option<bool>,
+    pub header: Option<String>,
+    pub show_header: Option<bool>,
+    pub separator: Option<String>,
+    pub display_hostname: Option<String>,
+    pub template: Option<String>,
+    pub time_format: Option<String>,
+    pub time_24h: Option<bool>,
+    pub timezone: Option<String>,
+    pub disk_no_alert: Option<Vec<String>>,
+    pub disk_labels: Option<BTreeMap<String, String>>,
+    pub colors: Option<BTreeMap<String, String>>,
+    pub disk_timeout_ms: Option<u64>,
+    pub disk_mounts: Option<Vec<String>>,
+    pub disk_all_local: Option<bool>,
     pub modules: Option<Vec<String>>,
     pub remote_welcome: RemoteWelcomeConfig,
     pub service_status: ServiceStatusConfig,
     pub output: OutputConfig,
+    pub spacing: SpacingConfig,
+    pub thresholds: ThresholdsConfig,
+    pub cert_checks: Option<Vec<CertCheckConfig>>,
+    pub service_fd_checks: Option<Vec<ServiceFdCheckConfig>>,
+    pub scheduled_banners: Option<Vec<ScheduledBannerConfig>>,
+    pub recent_logins: RecentLoginsConfig,
+    pub log_to_syslog: Option<bool>,
+    pub show_cpu_topology: Option<bool>,
+    pub show_swap_activity: Option<bool>,
+    pub show_cpu_usage: Option<bool>,
+    pub cpu_usage_sample_ms: Option<u64>,
+    pub resolve_hostname: Option<bool>,
+    pub cache_ttl_secs: Option<u64>,
+    pub show_gpu: Option<bool>,
+    pub interfaces: Option<Vec<String>>,
+    pub show_containers: Option<bool>,
+    pub show_net_throughput: Option<bool>,
+    pub net_throughput_sample_ms: Option<u64>,
+    /// `clock` (default) or `compact`, lowercased.
+    pub uptime_format: Option<String>,
+    pub show_pressure: Option<bool>,
+    /// Lowercased deployment tag such as `prod`, looked up in `environments`.
+    pub environment: Option<String>,
+    /// Keyed by lowercased environment name.
+    pub environments: Option<BTreeMap<String, EnvironmentConfig>>,
+    pub ssh: Option<Box<MotdConfig>>,
+    pub local: Option<Box<MotdConfig>>,
+    /// Overlays keyed by host name or `*`/`?` glob, see [`apply_host_overlay`].
+    pub hosts: Option<BTreeMap<String, MotdConfig>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionKind {
+    Ssh,
+    Local,
+}
+
+impl SessionKind {
+    pub fn detect() -> Self {
+        if env::var_os("SSH_CONNECTION").is_some_and(|value| !value.is_empty()) {
+            Self::Ssh
+        } else {
+            Self::Local
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Ssh => "ssh",
+            Self::Local => "local",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -78,6 +269,27 @@ enum ConfigModuleName {
     FailedLogin,
     Services,
     Updates,
+    NeedRestart,
+    Certificates,
+    FdUsage,
+    RecentLogins,
+    MemoryAvailable,
+    Oom,
+    TopCpu,
+    TopUsers,
+    Mac,
+    ContainerStorage,
+    Link,
+    AutoUpdates,
+    RebootRequired,
+    UpdateCheck,
+    AuthorizedKeys,
+    Temperature,
+    Processes,
+    Addresses,
+    FailedUnits,
+    Battery,
+    Pools,
 }
 
 impl ConfigModuleName {
@@ -100,6 +312,29 @@ impl ConfigModuleName {
             "failed_login" | "failedlogin" | "failed" | "security" => Some(Self::FailedLogin),
             "services" | "service" | "systemd" => Some(Self::Services),
             "updates" | "update" | "packages" | "package_updates" => Some(Self::Updates),
+            "needrestart" | "need_restart" | "restarts" => Some(Self::NeedRestart),
+            "certificates" | "certs" | "cert_checks" => Some(Self::Certificates),
+            "fd_usage" | "fds" | "service_fds" => Some(Self::FdUsage),
+            "recent_logins" | "recent" | "login_history" => Some(Self::RecentLogins),
+            "memory_available" | "mem_available" | "available_memory" => {
+                Some(Self::MemoryAvailable)
+            }
+            "oom" | "oom_events" | "oom_kills" => Some(Self::Oom),
+            "top_cpu" | "cpu_top" | "top_process" => Some(Self::TopCpu),
+            "top_users" | "users_top" | "process_owners" => Some(Self::TopUsers),
+            "mac" | "selinux" | "apparmor" | "lsm" => Some(Self::Mac),
+            "container_storage" | "docker_df" | "podman_df" => Some(Self::ContainerStorage),
+            "link" | "links" | "link_speed" => Some(Self::Link),
+            "auto_updates" | "unattended_upgrades" | "dnf_automatic" => Some(Self::AutoUpdates),
+            "reboot_required" | "reboot" | "restart_required" => Some(Self::RebootRequired),
+            "update_check" | "self_update" | "motdyn_update" => Some(Self::UpdateCheck),
+            "authorized_keys" | "ssh_keys" | "authorized_ssh_keys" => Some(Self::AuthorizedKeys),
+            "temperature" | "temp" | "thermal" | "sensors" => Some(Self::Temperature),
+            "processes" | "procs" | "ssh_sessions" | "sessions" => Some(Self::Processes),
+            "addresses" | "ip_addresses" | "ips" | "interfaces" => Some(Self::Addresses),
+            "failed_units" | "failed_services" | "systemd_failed" => Some(Self::FailedUnits),
+            "battery" | "batteries" | "power" => Some(Self::Battery),
+            "pools" | "storage_pools" | "zfs" | "zpool" | "btrfs" => Some(Self::Pools),
             _ => None,
         }
     }
@@ -123,6 +358,27 @@ impl ConfigModuleName {
             Self::FailedLogin => "failed_login",
             Self::Services => "services",
             Self::Updates => "updates",
+            Self::NeedRestart => "needrestart",
+            Self::Certificates => "certificates",
+            Self::FdUsage => "fd_usage",
+            Self::RecentLogins => "recent_logins",
+            Self::MemoryAvailable => "memory_available",
+            Self::Oom => "oom",
+            Self::TopCpu => "top_cpu",
+            Self::TopUsers => "top_users",
+            Self::Mac => "mac",
+            Self::ContainerStorage => "container_storage",
+            Self::Link => "link",
+            Self::AutoUpdates => "auto_updates",
+            Self::RebootRequired => "reboot_required",
+            Self::UpdateCheck => "update_check",
+            Self::AuthorizedKeys => "authorized_keys",
+            Self::Temperature => "temperature",
+            Self::Processes => "processes",
+            Self::Addresses => "addresses",
+            Self::FailedUnits => "failed_units",
+            Self::Battery => "battery",
+            Self::Pools => "pools",
         }
     }
 }
@@ -175,6 +431,25 @@ impl ConfigHiddenFieldName {
 pub enum ConfigValidationError {
     RemoteWelcomeTimeoutZero,
     RemoteWelcomeCachePathEmpty,
+    NestedSessionOverlay(&'static str),
+    IncludeInOverlay(&'static str),
+    CertCheckFieldEmpty { index: usize, field: &'static str },
+    ServiceFdCheckLabelEmpty(usize),
+    ServiceFdCheckTarget(usize),
+    RecentLoginsLimitZero,
+    RecentLoginsStatePathEmpty,
+    DiskTimeoutZero,
+    CpuUsageSampleZero,
+    MaxDiskLinesZero,
+    ProgressBarWidthZero,
+    WidthZero,
+    MaxAsciiLinesZero,
+    SpacingTooLarge { key: &'static str, max: usize },
+    InvalidDiskDisplay(String),
+    InvalidTempUnit(String),
+    InvalidUptimeFormat(String),
+    ThresholdOutOfRange(String),
+    ThresholdOrder(&'static str),
 }
 
 impl fmt::Display for ConfigValidationError {
@@ -186,6 +461,77 @@ impl fmt::Display for ConfigValidationError {
             Self::RemoteWelcomeCachePathEmpty => {
                 write!(f, "`remote_welcome.cache_path` must not be empty")
             }
+            Self::IncludeInOverlay(table) => {
+                write!(f, "`[{}]` cannot set `include`", table)
+            }
+            Self::NestedSessionOverlay(table) => {
+                write!(
+                    f,
+                    "`[{}]` cannot contain `[ssh]`, `[local]` or `[hosts]` tables",
+                    table
+                )
+            }
+            Self::CertCheckFieldEmpty { index, field } => {
+                write!(f, "`cert_checks[{}].{}` must not be empty", index, field)
+            }
+            Self::ServiceFdCheckLabelEmpty(index) => {
+                write!(f, "`service_fd_checks[{}].label` must not be empty", index)
+            }
+            Self::ServiceFdCheckTarget(index) => {
+                write!(
+                    f,
+                    "`service_fd_checks[{}]` must set exactly one of `process_name` or `pid_file`",
+                    index
+                )
+            }
+            Self::RecentLoginsLimitZero => {
+                write!(f, "`recent_logins.limit` must be greater than 0")
+            }
+            Self::DiskTimeoutZero => {
+                write!(f, "`disk_timeout_ms` must be greater than 0")
+            }
+            Self::CpuUsageSampleZero => {
+                write!(f, "`cpu_usage_sample_ms` must be greater than 0")
+            }
+            Self::MaxDiskLinesZero => {
+                write!(f, "`output.max_disk_lines` must be greater than 0")
+            }
+            Self::ProgressBarWidthZero => {
+                write!(f, "`output.progress_bar_width` must be greater than 0")
+            }
+            Self::WidthZero => {
+                write!(f, "`output.width` must be greater than 0")
+            }
+            Self::MaxAsciiLinesZero => {
+                write!(f, "`output.max_ascii_lines` must be greater than 0")
+            }
+            Self::SpacingTooLarge { key, max } => {
+                write!(f, "`spacing.{}` must be at most {}", key, max)
+            }
+            Self::InvalidDiskDisplay(value) => write!(
+                f,
+                "`output.disk_display` must be `used_total`, `used_free` or `free_total`, got '{}'",
+                value
+            ),
+            Self::InvalidTempUnit(value) => {
+                write!(f, "`output.temp_unit` must be `C` or `F`, got '{}'", value)
+            }
+            Self::InvalidUptimeFormat(value) => write!(
+                f,
+                "`uptime_format` must be `clock` or `compact`, got '{}'",
+                value
+            ),
+            Self::RecentLoginsStatePathEmpty => {
+                write!(f, "`recent_logins.state_path` must not be empty")
+            }
+            Self::ThresholdOutOfRange(key) => {
+                write!(f, "`thresholds.{}` must be between 0 and 100", key)
+            }
+            Self::ThresholdOrder(name) => write!(
+                f,
+                "`thresholds.{0}_warn` must not exceed `thresholds.{0}_crit`",
+                name
+            ),
         }
     }
 }
@@ -243,6 +589,90 @@ pub enum ConfigLoadStatus {
 pub struct LoadedConfig {
     pub config: Option<MotdConfig>,
     pub status: ConfigLoadStatus,
+    /// Problems that did not stop the file from loading, such as a missing
+    /// `include` target.
+    pub warnings: Vec<String>,
+    /// The keys this layer sets, for `--verbose` to name where each
+    /// effective value came from.
+    pub keys: ConfigKeys,
+}
+
+/// The top-level keys a config layer sets. The `[ssh]`, `[local]` and
+/// `[hosts."..."]` tables are kept apart, since only the ones matching the
+/// session and host apply.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigKeys {
+    base: BTreeSet<String>,
+    ssh: BTreeSet<String>,
+    local: BTreeSet<String>,
+    hosts: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ConfigKeys {
+    pub fn from_table(table: &toml::Table) -> Self {
+        let overlay = |name: &str| match table.get(name) {
+            Some(toml::Value::Table(overlay)) => table_keys(overlay),
+            _ => BTreeSet::new(),
+        };
+        let hosts = match table.get("hosts") {
+            Some(toml::Value::Table(hosts)) => hosts
+                .iter()
+                .filter_map(|(pattern, value)| {
+                    value
+                        .as_table()
+                        .map(|host| (pattern.clone(), table_keys(host)))
+                })
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+        Self {
+            base: table_keys(table),
+            ssh: overlay("ssh"),
+            local: overlay("local"),
+            hosts,
+        }
+    }
+
+    /// The keys set outside the session overlays, with the `[hosts."..."]`
+    /// tables matching `host_name`.
+    pub fn for_host(&self, host_name: &str) -> BTreeSet<String> {
+        let mut keys = self.base.clone();
+        for (pattern, host) in &self.hosts {
+            if host_pattern_matches(pattern, host_name) {
+                keys.extend(host.iter().cloned());
+            }
+        }
+        keys
+    }
+
+    /// The keys the `[ssh]` or `[local]` table for `session` sets.
+    pub fn for_session(&self, session: SessionKind) -> &BTreeSet<String> {
+        match session {
+            SessionKind::Ssh => &self.ssh,
+            SessionKind::Local => &self.local,
+        }
+    }
+
+    fn extend(&mut self, other: Self) {
+        self.base.extend(other.base);
+        self.ssh.extend(other.ssh);
+        self.local.extend(other.local);
+        for (pattern, keys) in other.hosts {
+            self.hosts.entry(pattern).or_default().extend(keys);
+        }
+    }
+}
+
+/// `sections` is reported as the `modules` it sets.
+fn table_keys(table: &toml::Table) -> BTreeSet<String> {
+    table
+        .keys()
+        .filter(|key| !matches!(key.as_str(), "include" | "ssh" | "local" | "hosts"))
+        .map(|key| match key.as_str() {
+            "sections" => "modules".to_string(),
+            _ => key.clone(),
+        })
+        .collect()
 }
 
 impl LoadedConfig {
@@ -250,13 +680,17 @@ impl LoadedConfig {
         Self {
             config: None,
             status: ConfigLoadStatus::Missing,
+            warnings: Vec::new(),
+            keys: ConfigKeys::default(),
         }
     }
 
-    fn loaded(config: MotdConfig) -> Self {
+    fn loaded(config: MotdConfig, keys: ConfigKeys) -> Self {
         Self {
             config: Some(config),
             status: ConfigLoadStatus::Loaded,
+            warnings: Vec::new(),
+            keys,
         }
     }
 
@@ -264,6 +698,8 @@ impl LoadedConfig {
         Self {
             config: None,
             status: ConfigLoadStatus::Invalid(error),
+            warnings: Vec::new(),
+            keys: ConfigKeys::default(),
         }
     }
 
@@ -288,29 +724,271 @@ pub fn load_config(path: &Path) -> LoadedConfig {
         return LoadedConfig::missing();
     }
 
-    let content = match fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(err) => {
-            return LoadedConfig::invalid(ConfigLoadError::Read {
-                path: path.to_path_buf(),
-                message: err.to_string(),
-            });
-        }
+    let mut warnings = Vec::new();
+    let mut keys = ConfigKeys::default();
+    let chain = &mut vec![canonical_path(path)];
+    let mut loaded = match load_config_file(path, chain, &mut warnings, &mut keys) {
+        Ok(config) => LoadedConfig::loaded(config, keys),
+        Err(err) => LoadedConfig::invalid(err),
+    };
+    loaded.warnings = warnings;
+    loaded
+}
+
+/// Loads one file and merges it over the file its `include` names, which
+/// loads the same way. `chain` holds the files being loaded, outermost
+/// first: an include back into it, or one whose target is missing, is
+/// skipped with a warning. Errors in an included file fail the whole load.
+/// The keys of every file loaded are added to `keys`.
+fn load_config_file(
+    path: &Path,
+    chain: &mut Vec<PathBuf>,
+    warnings: &mut Vec<String>,
+    keys: &mut ConfigKeys,
+) -> Result<MotdConfig, ConfigLoadError> {
+    let content = fs::read_to_string(path).map_err(|err| ConfigLoadError::Read {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    })?;
+    let mut raw: RawConfig = toml::from_str(&content).map_err(|err| ConfigLoadError::Parse {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    })?;
+    let include = normalize_optional_text(raw.include.take());
+    let config = validate_and_normalize(raw, path)?;
+    if let Ok(table) = toml::from_str::<toml::Table>(&content) {
+        keys.extend(ConfigKeys::from_table(&table));
+    }
+    let Some(include) = include else {
+        return Ok(config);
+    };
+
+    let target = resolve_include_path(path, &include);
+    let canonical = canonical_path(&target);
+    let included = if chain.contains(&canonical) {
+        warnings.push(format!(
+            "config '{}': skipping include '{}', which is already being loaded",
+            path.display(),
+            target.display()
+        ));
+        None
+    } else if !target.exists() {
+        warnings.push(format!(
+            "config '{}': include '{}' does not exist",
+            path.display(),
+            target.display()
+        ));
+        None
+    } else {
+        chain.push(canonical);
+        let included = load_config_file(&target, chain, warnings, keys)?;
+        chain.pop();
+        Some(included)
     };
+    Ok(merge_config(included, Some(config)))
+}
+
+/// `~` is expanded, and a relative `include` is taken from the directory of
+/// the file that names it.
+fn resolve_include_path(including: &Path, include: &str) -> PathBuf {
+    let target = expand_tilde(include);
+    if target.is_absolute() {
+        return target;
+    }
+    including
+        .parent()
+        .map_or_else(|| target.clone(), |dir| dir.join(&target))
+}
+
+fn canonical_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Stands in for a file path in errors about `MOTDYN_*` overrides.
+const ENV_CONFIG_SOURCE: &str = "environment";
+
+/// How a `MOTDYN_*` value is read: lists are comma-separated and booleans
+/// accept `true`/`false`, `yes`/`no`, `on`/`off` or `1`/`0`.
+#[derive(Debug, Clone, Copy)]
+enum EnvValueKind {
+    Text,
+    List,
+    Bool,
+    Integer,
+}
+
+/// Environment variables that override top-level config keys. Aliases come
+/// first so the canonical name wins when both are set.
+const ENV_CONFIG_KEYS: &[(&str, &str, EnvValueKind)] = &[
+    ("MOTDYN_ASCII_ART", "welcome", EnvValueKind::Text),
+    ("MOTDYN_WELCOME", "welcome", EnvValueKind::Text),
+    (
+        "MOTDYN_WELCOME_SOURCES",
+        "welcome_sources",
+        EnvValueKind::List,
+    ),
+    (
+        "MOTDYN_ASCII_ART_FILE",
+        "ascii_art_file",
+        EnvValueKind::Text,
+    ),
+    ("MOTDYN_FAREWELL", "farewell", EnvValueKind::Text),
+    ("MOTDYN_FAREWELLS", "farewells", EnvValueKind::List),
+    (
+        "MOTDYN_FAREWELL_ON_ALERT",
+        "farewell_on_alert",
+        EnvValueKind::Bool,
+    ),
+    ("MOTDYN_HEADER", "header", EnvValueKind::Text),
+    ("MOTDYN_SHOW_HEADER", "show_header", EnvValueKind::Bool),
+    ("MOTDYN_SEPARATOR", "separator", EnvValueKind::Text),
+    (
+        "MOTDYN_DISPLAY_HOSTNAME",
+        "display_hostname",
+        EnvValueKind::Text,
+    ),
+    ("MOTDYN_TEMPLATE", "template", EnvValueKind::Text),
+    ("MOTDYN_TIME_FORMAT", "time_format", EnvValueKind::Text),
+    ("MOTDYN_TIME_24H", "time_24h", EnvValueKind::Bool),
+    ("MOTDYN_TIMEZONE", "timezone", EnvValueKind::Text),
+    ("MOTDYN_DISK_NO_ALERT", "disk_no_alert", EnvValueKind::List),
+    (
+        "MOTDYN_DISK_TIMEOUT_MS",
+        "disk_timeout_ms",
+        EnvValueKind::Integer,
+    ),
+    ("MOTDYN_DISK_MOUNTS", "disk_mounts", EnvValueKind::List),
+    (
+        "MOTDYN_DISK_ALL_LOCAL",
+        "disk_all_local",
+        EnvValueKind::Bool,
+    ),
+    ("MOTDYN_SECTIONS", "sections", EnvValueKind::List),
+    ("MOTDYN_MODULES", "modules", EnvValueKind::List),
+    ("MOTDYN_LOG_TO_SYSLOG", "log_to_syslog", EnvValueKind::Bool),
+    (
+        "MOTDYN_SHOW_CPU_TOPOLOGY",
+        "show_cpu_topology",
+        EnvValueKind::Bool,
+    ),
+    (
+        "MOTDYN_SHOW_SWAP_ACTIVITY",
+        "show_swap_activity",
+        EnvValueKind::Bool,
+    ),
+    (
+        "MOTDYN_SHOW_CPU_USAGE",
+        "show_cpu_usage",
+        EnvValueKind::Bool,
+    ),
+    (
+        "MOTDYN_CPU_USAGE_SAMPLE_MS",
+        "cpu_usage_sample_ms",
+        EnvValueKind::Integer,
+    ),
+    (
+        "MOTDYN_RESOLVE_HOSTNAME",
+        "resolve_hostname",
+        EnvValueKind::Bool,
+    ),
+    (
+        "MOTDYN_CACHE_TTL_SECS",
+        "cache_ttl_secs",
+        EnvValueKind::Integer,
+    ),
+    ("MOTDYN_SHOW_GPU", "show_gpu", EnvValueKind::Bool),
+    ("MOTDYN_INTERFACES", "interfaces", EnvValueKind::List),
+    (
+        "MOTDYN_SHOW_CONTAINERS",
+        "show_containers",
+        EnvValueKind::Bool,
+    ),
+    (
+        "MOTDYN_SHOW_NET_THROUGHPUT",
+        "show_net_throughput",
+        EnvValueKind::Bool,
+    ),
+    (
+        "MOTDYN_NET_THROUGHPUT_SAMPLE_MS",
+        "net_throughput_sample_ms",
+        EnvValueKind::Integer,
+    ),
+    ("MOTDYN_UPTIME_FORMAT", "uptime_format", EnvValueKind::Text),
+    ("MOTDYN_SHOW_PRESSURE", "show_pressure", EnvValueKind::Bool),
+    ("MOTDYN_ENV", "environment", EnvValueKind::Text),
+];
+
+/// Reads `MOTDYN_*` overrides into a config layer that wins over both files,
+/// with the names of the variables that were set. Empty variables are
+/// ignored; `Missing` means none was set.
+pub fn load_config_from_env() -> (LoadedConfig, Vec<&'static str>) {
+    load_config_from_env_with(|name| {
+        env::var_os(name).map(|value| value.to_string_lossy().into_owned())
+    })
+}
+
+fn load_config_from_env_with<F>(env_lookup: F) -> (LoadedConfig, Vec<&'static str>)
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut table = toml::Table::new();
+    let mut vars = Vec::new();
+    let mut bad_values = Vec::new();
+    for &(var, key, kind) in ENV_CONFIG_KEYS {
+        let Some(value) = env_lookup(var).filter(|value| !value.trim().is_empty()) else {
+            continue;
+        };
+        match env_value(&value, kind) {
+            Some(value) => {
+                table.insert(key.to_string(), value);
+                vars.push(var);
+            }
+            None => bad_values.push(format!("{}: invalid value '{}'", var, value)),
+        }
+    }
 
-    let raw: RawConfig = match toml::from_str(&content) {
-        Ok(raw) => raw,
-        Err(err) => {
-            return LoadedConfig::invalid(ConfigLoadError::Parse {
+    let path = Path::new(ENV_CONFIG_SOURCE);
+    let loaded = if !bad_values.is_empty() {
+        LoadedConfig::invalid(ConfigLoadError::Parse {
+            path: path.to_path_buf(),
+            message: bad_values.join("; "),
+        })
+    } else if vars.is_empty() {
+        LoadedConfig::missing()
+    } else {
+        let keys = ConfigKeys::from_table(&table);
+        match toml::Value::Table(table).try_into::<RawConfig>() {
+            Ok(raw) => match validate_and_normalize(raw, path) {
+                Ok(config) => LoadedConfig::loaded(config, keys),
+                Err(err) => LoadedConfig::invalid(err),
+            },
+            Err(err) => LoadedConfig::invalid(ConfigLoadError::Parse {
                 path: path.to_path_buf(),
                 message: err.to_string(),
-            });
+            }),
         }
     };
+    (loaded, vars)
+}
 
-    match validate_and_normalize(raw, path) {
-        Ok(config) => LoadedConfig::loaded(config),
-        Err(err) => LoadedConfig::invalid(err),
+fn env_value(value: &str, kind: EnvValueKind) -> Option<toml::Value> {
+    let value = value.trim();
+    match kind {
+        EnvValueKind::Text => Some(toml::Value::String(value.to_string())),
+        EnvValueKind::List => Some(toml::Value::Array(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| toml::Value::String(item.to_string()))
+                .collect(),
+        )),
+        EnvValueKind::Bool => match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Some(toml::Value::Boolean(true)),
+            "0" | "false" | "no" | "off" => Some(toml::Value::Boolean(false)),
+            _ => None,
+        },
+        EnvValueKind::Integer => value.parse::<i64>().ok().map(toml::Value::Integer),
     }
 }
 
@@ -323,34 +1001,202 @@ pub fn merge_config(sys_cfg: Option<MotdConfig>, usr_cfg: Option<MotdConfig>) ->
         if let Some(welcome_sources) = user_cfg.welcome_sources {
             final_cfg.welcome_sources = Some(welcome_sources);
         }
+        if let Some(ascii_art_file) = user_cfg.ascii_art_file {
+            final_cfg.ascii_art_file = Some(ascii_art_file);
+        }
         if let Some(farewell) = user_cfg.farewell {
             final_cfg.farewell = Some(farewell);
         }
+        if let Some(farewells) = user_cfg.farewells {
+            final_cfg.farewells = Some(farewells);
+        }
+        if let Some(farewell_on_alert) = user_cfg.farewell_on_alert {
+            final_cfg.farewell_on_alert = Some(farewell_on_alert);
+        }
+        if let Some(header) = user_cfg.header {
+            final_cfg.header = Some(header);
+        }
+        if let Some(show_header) = user_cfg.show_header {
+            final_cfg.show_header = Some(show_header);
+        }
+        if let Some(separator) = user_cfg.separator {
+            final_cfg.separator = Some(separator);
+        }
+        if let Some(display_hostname) = user_cfg.display_hostname {
+            final_cfg.display_hostname = Some(display_hostname);
+        }
+        if let Some(template) = user_cfg.template {
+            final_cfg.template = Some(template);
+        }
+        if let Some(time_format) = user_cfg.time_format {
+            final_cfg.time_format = Some(time_format);
+        }
+        if let Some(time_24h) = user_cfg.time_24h {
+            final_cfg.time_24h = Some(time_24h);
+        }
+        if let Some(timezone) = user_cfg.timezone {
+            final_cfg.timezone = Some(timezone);
+        }
+        if let Some(disk_no_alert) = user_cfg.disk_no_alert {
+            final_cfg.disk_no_alert = Some(disk_no_alert);
+        }
+        if let Some(disk_labels) = user_cfg.disk_labels {
+            final_cfg.disk_labels = Some(disk_labels);
+        }
+        if let Some(colors) = user_cfg.colors {
+            final_cfg.colors = Some(colors);
+        }
+        if let Some(disk_timeout_ms) = user_cfg.disk_timeout_ms {
+            final_cfg.disk_timeout_ms = Some(disk_timeout_ms);
+        }
+        if let Some(disk_mounts) = user_cfg.disk_mounts {
+            final_cfg.disk_mounts = Some(disk_mounts);
+        }
+        if let Some(disk_all_local) = user_cfg.disk_all_local {
+            final_cfg.disk_all_local = Some(disk_all_local);
+        }
         if let Some(modules) = user_cfg.modules {
             final_cfg.modules = Some(modules);
         }
         merge_remote_welcome(&mut final_cfg.remote_welcome, user_cfg.remote_welcome);
         merge_service_status(&mut final_cfg.service_status, user_cfg.service_status);
         merge_output(&mut final_cfg.output, user_cfg.output);
+        merge_spacing(&mut final_cfg.spacing, user_cfg.spacing);
+        merge_thresholds(&mut final_cfg.thresholds, user_cfg.thresholds);
+        if let Some(cert_checks) = user_cfg.cert_checks {
+            final_cfg.cert_checks = Some(cert_checks);
+        }
+        if let Some(service_fd_checks) = user_cfg.service_fd_checks {
+            final_cfg.service_fd_checks = Some(service_fd_checks);
+        }
+        if let Some(scheduled_banners) = user_cfg.scheduled_banners {
+            final_cfg.scheduled_banners = Some(scheduled_banners);
+        }
+        merge_recent_logins(&mut final_cfg.recent_logins, user_cfg.recent_logins);
+        if let Some(log_to_syslog) = user_cfg.log_to_syslog {
+            final_cfg.log_to_syslog = Some(log_to_syslog);
+        }
+        if let Some(show_cpu_topology) = user_cfg.show_cpu_topology {
+            final_cfg.show_cpu_topology = Some(show_cpu_topology);
+        }
+        if let Some(show_swap_activity) = user_cfg.show_swap_activity {
+            final_cfg.show_swap_activity = Some(show_swap_activity);
+        }
+        if let Some(show_cpu_usage) = user_cfg.show_cpu_usage {
+            final_cfg.show_cpu_usage = Some(show_cpu_usage);
+        }
+        if let Some(cpu_usage_sample_ms) = user_cfg.cpu_usage_sample_ms {
+            final_cfg.cpu_usage_sample_ms = Some(cpu_usage_sample_ms);
+        }
+        if let Some(resolve_hostname) = user_cfg.resolve_hostname {
+            final_cfg.resolve_hostname = Some(resolve_hostname);
+        }
+        if let Some(cache_ttl_secs) = user_cfg.cache_ttl_secs {
+            final_cfg.cache_ttl_secs = Some(cache_ttl_secs);
+        }
+        if let Some(show_gpu) = user_cfg.show_gpu {
+            final_cfg.show_gpu = Some(show_gpu);
+        }
+        if let Some(interfaces) = user_cfg.interfaces {
+            final_cfg.interfaces = Some(interfaces);
+        }
+        if let Some(show_containers) = user_cfg.show_containers {
+            final_cfg.show_containers = Some(show_containers);
+        }
+        if let Some(show_net_throughput) = user_cfg.show_net_throughput {
+            final_cfg.show_net_throughput = Some(show_net_throughput);
+        }
+        if let Some(net_throughput_sample_ms) = user_cfg.net_throughput_sample_ms {
+            final_cfg.net_throughput_sample_ms = Some(net_throughput_sample_ms);
+        }
+        if let Some(uptime_format) = user_cfg.uptime_format {
+            final_cfg.uptime_format = Some(uptime_format);
+        }
+        if let Some(show_pressure) = user_cfg.show_pressure {
+            final_cfg.show_pressure = Some(show_pressure);
+        }
+        if let Some(environment) = user_cfg.environment {
+            final_cfg.environment = Some(environment);
+        }
+        if let Some(user_environments) = user_cfg.environments {
+            final_cfg
+                .environments
+                .get_or_insert_with(BTreeMap::new)
+                .extend(user_environments);
+        }
+        final_cfg.ssh = merge_overlay(final_cfg.ssh.take(), user_cfg.ssh);
+        final_cfg.local = merge_overlay(final_cfg.local.take(), user_cfg.local);
+        if let Some(user_hosts) = user_cfg.hosts {
+            let hosts = final_cfg.hosts.get_or_insert_with(BTreeMap::new);
+            for (pattern, user_host) in user_hosts {
+                let merged = merge_config(hosts.remove(&pattern), Some(user_host));
+                hosts.insert(pattern, merged);
+            }
+        }
     }
     final_cfg
 }
 
+/// Merges every `[hosts."..."]` table whose pattern matches `host_name` on
+/// top of the rest of `cfg`: glob patterns first, in name order, then an
+/// exact match, so the most specific table wins. Matching ignores case.
+pub fn apply_host_overlay(mut cfg: MotdConfig, host_name: &str) -> MotdConfig {
+    let Some(hosts) = cfg.hosts.take() else {
+        return cfg;
+    };
+    let (exact, globs): (Vec<_>, Vec<_>) = hosts
+        .into_iter()
+        .filter(|(pattern, _)| host_pattern_matches(pattern, host_name))
+        .partition(|(pattern, _)| !pattern.contains(['*', '?']));
+    for (_, overlay) in globs.into_iter().chain(exact) {
+        cfg = merge_config(Some(cfg), Some(overlay));
+    }
+    cfg
+}
+
+/// `*` matches any run of characters and `?` exactly one.
+fn host_pattern_matches(pattern: &str, host_name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((ch, rest)) => name
+                .split_first()
+                .is_some_and(|(first, name)| first == ch && matches(rest, name)),
+        }
+    }
+    let pattern = pattern.to_ascii_lowercase().chars().collect::<Vec<_>>();
+    let name = host_name.to_ascii_lowercase().chars().collect::<Vec<_>>();
+    matches(&pattern, &name)
+}
+
+pub fn apply_session_overlay(mut cfg: MotdConfig, session: SessionKind) -> MotdConfig {
+    let (ssh, local) = (cfg.ssh.take(), cfg.local.take());
+    let overlay = match session {
+        SessionKind::Ssh => ssh,
+        SessionKind::Local => local,
+    };
+    match overlay {
+        Some(overlay) => merge_config(Some(cfg), Some(*overlay)),
+        None => cfg,
+    }
+}
+
+fn merge_overlay(
+    target: Option<Box<MotdConfig>>,
+    source: Option<Box<MotdConfig>>,
+) -> Option<Box<MotdConfig>> {
+    match (target, source) {
+        (Some(target), Some(source)) => Some(Box::new(merge_config(Some(*target), Some(*source)))),
+        (target, None) => target,
+        (None, source) => source,
+    }
+}
+
 fn validate_and_normalize(raw: RawConfig, path: &Path) -> Result<MotdConfig, ConfigLoadError> {
     let mut issues = Vec::new();
-    let remote_welcome =
-        normalize_remote_welcome(raw.remote_welcome.unwrap_or_default(), &mut issues);
-    let service_status = normalize_service_status(raw.service_status.unwrap_or_default());
-    let output = normalize_output(raw.output.unwrap_or_default());
-    let config = MotdConfig {
-        welcome: normalize_optional_text(raw.welcome),
-        welcome_sources: normalize_ordered_string_list(raw.welcome_sources),
-        farewell: normalize_optional_text(raw.farewell),
-        modules: raw.modules,
-        remote_welcome,
-        service_status,
-        output,
-    };
+    let config = normalize_config(raw, None, &mut issues);
 
     if issues.is_empty() {
         Ok(config)
@@ -362,6 +1208,118 @@ fn validate_and_normalize(raw: RawConfig, path: &Path) -> Result<MotdConfig, Con
     }
 }
 
+fn normalize_config(
+    raw: RawConfig,
+    overlay: Option<&'static str>,
+    issues: &mut Vec<ConfigValidationError>,
+) -> MotdConfig {
+    if let Some(table) = overlay
+        && (raw.ssh.is_some() || raw.local.is_some() || raw.hosts.is_some())
+    {
+        issues.push(ConfigValidationError::NestedSessionOverlay(table));
+    }
+    if let Some(table) = overlay
+        && raw.include.is_some()
+    {
+        issues.push(ConfigValidationError::IncludeInOverlay(table));
+    }
+    if matches!(raw.disk_timeout_ms, Some(0)) {
+        issues.push(ConfigValidationError::DiskTimeoutZero);
+    }
+    if matches!(raw.cpu_usage_sample_ms, Some(0)) {
+        issues.push(ConfigValidationError::CpuUsageSampleZero);
+    }
+    let ssh = raw
+        .ssh
+        .filter(|_| overlay.is_none())
+        .map(|raw| Box::new(normalize_config(*raw, Some("ssh"), issues)));
+    let local = raw
+        .local
+        .filter(|_| overlay.is_none())
+        .map(|raw| Box::new(normalize_config(*raw, Some("local"), issues)));
+    let hosts = raw.hosts.filter(|_| overlay.is_none()).map(|hosts| {
+        hosts
+            .into_iter()
+            .map(|(pattern, raw)| (pattern, normalize_config(raw, Some("hosts"), issues)))
+            .collect()
+    });
+    let remote_welcome = normalize_remote_welcome(raw.remote_welcome.unwrap_or_default(), issues);
+    let service_status = normalize_service_status(raw.service_status.unwrap_or_default());
+    let output = normalize_output(raw.output.unwrap_or_default(), issues);
+    let cert_checks = raw
+        .cert_checks
+        .map(|checks| normalize_cert_checks(checks, issues));
+    let service_fd_checks = raw
+        .service_fd_checks
+        .map(|checks| normalize_service_fd_checks(checks, issues));
+    let recent_logins = normalize_recent_logins(raw.recent_logins.unwrap_or_default(), issues);
+    MotdConfig {
+        welcome: normalize_optional_text(raw.welcome),
+        welcome_sources: normalize_ordered_string_list(raw.welcome_sources),
+        ascii_art_file: normalize_optional_text(raw.ascii_art_file),
+        farewell: normalize_optional_text(raw.farewell),
+        farewells: normalize_ordered_string_list(raw.farewells),
+        farewell_on_alert: raw.farewell_on_alert,
+        // An empty header is kept: it suppresses the default "Welcome!".
+        header: raw.header,
+        show_header: raw.show_header,
+        separator: raw.separator,
+        display_hostname: normalize_optional_text(raw.display_hostname),
+        template: raw
+            .template
+            .map(|template| template.trim_end_matches(['\r', '\n']).to_string())
+            .filter(|template| !template.trim().is_empty()),
+        time_format: normalize_optional_text(raw.time_format),
+        time_24h: raw.time_24h,
+        timezone: normalize_optional_text(raw.timezone),
+        disk_no_alert: normalize_ordered_string_list(raw.disk_no_alert.map(|paths| {
+            paths
+                .into_iter()
+                .map(|path| normalize_mount_path(&path))
+                .collect()
+        })),
+        disk_labels: raw.disk_labels.map(normalize_disk_labels),
+        colors: raw.colors.map(normalize_colors),
+        disk_timeout_ms: raw.disk_timeout_ms,
+        disk_mounts: normalize_ordered_string_list(raw.disk_mounts.map(|paths| {
+            paths
+                .into_iter()
+                .map(|path| normalize_mount_path(&path))
+                .collect()
+        })),
+        disk_all_local: raw.disk_all_local,
+        modules: raw.modules.or(raw.sections),
+        remote_welcome,
+        service_status,
+        output,
+        spacing: normalize_spacing(raw.spacing.unwrap_or_default(), issues),
+        thresholds: normalize_thresholds(raw.thresholds.unwrap_or_default(), issues),
+        cert_checks,
+        service_fd_checks,
+        scheduled_banners: raw.scheduled_banners.map(normalize_scheduled_banners),
+        recent_logins,
+        log_to_syslog: raw.log_to_syslog,
+        show_cpu_topology: raw.show_cpu_topology,
+        show_swap_activity: raw.show_swap_activity,
+        show_cpu_usage: raw.show_cpu_usage,
+        cpu_usage_sample_ms: raw.cpu_usage_sample_ms,
+        resolve_hostname: raw.resolve_hostname,
+        cache_ttl_secs: raw.cache_ttl_secs,
+        show_gpu: raw.show_gpu,
+        interfaces: normalize_ordered_string_list(raw.interfaces),
+        show_containers: raw.show_containers,
+        show_net_throughput: raw.show_net_throughput,
+        net_throughput_sample_ms: raw.net_throughput_sample_ms,
+        uptime_format: normalize_uptime_format(raw.uptime_format, issues),
+        show_pressure: raw.show_pressure,
+        environment: normalize_optional_text(raw.environment).map(|name| name.to_ascii_lowercase()),
+        environments: raw.environments.map(normalize_environments),
+        ssh,
+        local,
+        hosts,
+    }
+}
+
 fn deserialize_module_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
 where
     D: Deserializer<'de>,
@@ -371,6 +1329,33 @@ where
     })
 }
 
+/// `sections` is the forgiving spelling of `modules`: a name this version
+/// does not know is skipped with a warning instead of rejecting the file.
+fn deserialize_section_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(raw_values) = Option::<Vec<String>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let mut normalized = Vec::new();
+    for value in raw_values {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match ConfigModuleName::parse(trimmed).map(ConfigModuleName::key) {
+            Some(canonical) => {
+                if !normalized.iter().any(|entry: &String| entry == canonical) {
+                    normalized.push(canonical.to_string());
+                }
+            }
+            None => eprintln!("motdyn: ignoring unknown section '{}'", trimmed),
+        }
+    }
+    Ok(Some(normalized))
+}
+
 fn deserialize_hidden_field_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
 where
     D: Deserializer<'de>,
@@ -434,7 +1419,112 @@ fn normalize_remote_welcome(
     config
 }
 
-fn normalize_output(config: OutputConfig) -> OutputConfig {
+fn normalize_uptime_format(
+    value: Option<String>,
+    issues: &mut Vec<ConfigValidationError>,
+) -> Option<String> {
+    let value = value?;
+    let canonical = value.trim().to_ascii_lowercase();
+    if matches!(canonical.as_str(), "clock" | "compact") {
+        Some(canonical)
+    } else {
+        issues.push(ConfigValidationError::InvalidUptimeFormat(value));
+        None
+    }
+}
+
+fn normalize_thresholds(
+    config: ThresholdsConfig,
+    issues: &mut Vec<ConfigValidationError>,
+) -> ThresholdsConfig {
+    let pairs = [
+        ("mem", config.mem_warn, config.mem_crit),
+        ("swap", config.swap_warn, config.swap_crit),
+        ("disk", config.disk_warn, config.disk_crit),
+        ("pressure", config.pressure_warn, config.pressure_crit),
+    ];
+    for (name, warn, crit) in pairs {
+        for (suffix, value) in [("warn", warn), ("crit", crit)] {
+            if value.is_some_and(|value| !(0.0..=100.0).contains(&value)) {
+                issues.push(ConfigValidationError::ThresholdOutOfRange(format!(
+                    "{}_{}",
+                    name, suffix
+                )));
+            }
+        }
+        if let (Some(warn), Some(crit)) = (warn, crit)
+            && warn > crit
+        {
+            issues.push(ConfigValidationError::ThresholdOrder(name));
+        }
+    }
+    if config
+        .battery_low
+        .is_some_and(|value| !(0.0..=100.0).contains(&value))
+    {
+        issues.push(ConfigValidationError::ThresholdOutOfRange(
+            "battery_low".into(),
+        ));
+    }
+    // Temperatures are degrees Celsius and loads are per online core, not
+    // percentages, so only their order is checked.
+    let ordered = [
+        ("temp", config.temp_warn, config.temp_crit),
+        ("load", config.load_warn, config.load_crit),
+    ];
+    for (name, warn, crit) in ordered {
+        if let (Some(warn), Some(crit)) = (warn, crit)
+            && warn > crit
+        {
+            issues.push(ConfigValidationError::ThresholdOrder(name));
+        }
+    }
+    config
+}
+
+fn normalize_output(
+    mut config: OutputConfig,
+    issues: &mut Vec<ConfigValidationError>,
+) -> OutputConfig {
+    if matches!(config.max_disk_lines, Some(0)) {
+        issues.push(ConfigValidationError::MaxDiskLinesZero);
+    }
+    if matches!(config.max_ascii_lines, Some(0)) {
+        issues.push(ConfigValidationError::MaxAsciiLinesZero);
+    }
+    if matches!(config.progress_bar_width, Some(0)) {
+        issues.push(ConfigValidationError::ProgressBarWidthZero);
+    }
+    if matches!(config.width, Some(0)) {
+        issues.push(ConfigValidationError::WidthZero);
+    }
+    config.disk_display = match config.disk_display {
+        Some(value) => {
+            let canonical = value.trim().to_ascii_lowercase().replace('-', "_");
+            if !matches!(
+                canonical.as_str(),
+                "used_total" | "used_free" | "free_total"
+            ) {
+                issues.push(ConfigValidationError::InvalidDiskDisplay(value));
+                None
+            } else {
+                Some(canonical)
+            }
+        }
+        None => None,
+    };
+    config.temp_unit = match config.temp_unit {
+        Some(value) => {
+            let canonical = value.trim().to_ascii_uppercase();
+            if !matches!(canonical.as_str(), "C" | "F") {
+                issues.push(ConfigValidationError::InvalidTempUnit(value));
+                None
+            } else {
+                Some(canonical)
+            }
+        }
+        None => None,
+    };
     config
 }
 
@@ -480,15 +1570,199 @@ fn merge_output(target: &mut OutputConfig, source: OutputConfig) {
     if let Some(section_headers) = source.section_headers {
         target.section_headers = Some(section_headers);
     }
-    if let Some(hidden_fields) = source.hidden_fields {
-        target.hidden_fields = Some(hidden_fields);
+    if let Some(ansi_safe) = source.ansi_safe {
+        target.ansi_safe = Some(ansi_safe);
     }
-}
-
-fn normalize_optional_text(value: Option<String>) -> Option<String> {
-    value.and_then(|value| {
-        let trimmed = value.trim();
-        if trimmed.is_empty() {
+    if let Some(clip_ascii_art) = source.clip_ascii_art {
+        target.clip_ascii_art = Some(clip_ascii_art);
+    }
+    if let Some(max_ascii_lines) = source.max_ascii_lines {
+        target.max_ascii_lines = Some(max_ascii_lines);
+    }
+    if let Some(max_disk_lines) = source.max_disk_lines {
+        target.max_disk_lines = Some(max_disk_lines);
+    }
+    if let Some(disk_display) = source.disk_display {
+        target.disk_display = Some(disk_display);
+    }
+    if let Some(progress_bars) = source.progress_bars {
+        target.progress_bars = Some(progress_bars);
+    }
+    if let Some(progress_bar_width) = source.progress_bar_width {
+        target.progress_bar_width = Some(progress_bar_width);
+    }
+    if let Some(width) = source.width {
+        target.width = Some(width);
+    }
+    if let Some(temp_unit) = source.temp_unit {
+        target.temp_unit = Some(temp_unit);
+    }
+    if let Some(hidden_fields) = source.hidden_fields {
+        target.hidden_fields = Some(hidden_fields);
+    }
+}
+
+fn normalize_cert_checks(
+    checks: Vec<CertCheckConfig>,
+    issues: &mut Vec<ConfigValidationError>,
+) -> Vec<CertCheckConfig> {
+    checks
+        .into_iter()
+        .enumerate()
+        .map(|(index, check)| {
+            let label = check.label.trim().to_string();
+            let path = check.path.trim().to_string();
+            if label.is_empty() {
+                issues.push(ConfigValidationError::CertCheckFieldEmpty {
+                    index,
+                    field: "label",
+                });
+            }
+            if path.is_empty() {
+                issues.push(ConfigValidationError::CertCheckFieldEmpty {
+                    index,
+                    field: "path",
+                });
+            }
+            CertCheckConfig { label, path }
+        })
+        .collect()
+}
+
+fn normalize_service_fd_checks(
+    checks: Vec<ServiceFdCheckConfig>,
+    issues: &mut Vec<ConfigValidationError>,
+) -> Vec<ServiceFdCheckConfig> {
+    checks
+        .into_iter()
+        .enumerate()
+        .map(|(index, check)| {
+            let label = check.label.trim().to_string();
+            let process_name = normalize_optional_text(check.process_name);
+            let pid_file = normalize_optional_text(check.pid_file);
+            if label.is_empty() {
+                issues.push(ConfigValidationError::ServiceFdCheckLabelEmpty(index));
+            }
+            if process_name.is_some() == pid_file.is_some() {
+                issues.push(ConfigValidationError::ServiceFdCheckTarget(index));
+            }
+            ServiceFdCheckConfig {
+                label,
+                process_name,
+                pid_file,
+            }
+        })
+        .collect()
+}
+
+fn normalize_scheduled_banners(banners: Vec<ScheduledBannerConfig>) -> Vec<ScheduledBannerConfig> {
+    banners
+        .into_iter()
+        .map(|banner| ScheduledBannerConfig {
+            start: banner.start.trim().to_string(),
+            end: banner.end.trim().to_string(),
+            message: banner.message.trim().to_string(),
+        })
+        .collect()
+}
+
+fn normalize_recent_logins(
+    mut config: RecentLoginsConfig,
+    issues: &mut Vec<ConfigValidationError>,
+) -> RecentLoginsConfig {
+    if matches!(config.limit, Some(0)) {
+        issues.push(ConfigValidationError::RecentLoginsLimitZero);
+    }
+
+    config.state_path = match config.state_path {
+        Some(value) if value.trim().is_empty() => {
+            issues.push(ConfigValidationError::RecentLoginsStatePathEmpty);
+            Some(value)
+        }
+        Some(value) => Some(value.trim().to_string()),
+        None => None,
+    };
+
+    config
+}
+
+fn merge_recent_logins(target: &mut RecentLoginsConfig, source: RecentLoginsConfig) {
+    if let Some(limit) = source.limit {
+        target.limit = Some(limit);
+    }
+    if let Some(state_path) = source.state_path {
+        target.state_path = Some(state_path);
+    }
+}
+
+const MAX_SPACING_LINES: usize = 10;
+const MAX_GUTTER_COLUMNS: usize = 40;
+
+/// Blank-line counts and the gutter are printed as-is, so a typo such as
+/// `before_welcome = 1000000` would otherwise flood every login.
+fn normalize_spacing(
+    spacing: SpacingConfig,
+    issues: &mut Vec<ConfigValidationError>,
+) -> SpacingConfig {
+    for (key, value, max) in [
+        ("before_welcome", spacing.before_welcome, MAX_SPACING_LINES),
+        ("after_welcome", spacing.after_welcome, MAX_SPACING_LINES),
+        (
+            "before_farewell",
+            spacing.before_farewell,
+            MAX_SPACING_LINES,
+        ),
+        ("gutter", spacing.gutter, MAX_GUTTER_COLUMNS),
+    ] {
+        if value.is_some_and(|value| value > max) {
+            issues.push(ConfigValidationError::SpacingTooLarge { key, max });
+        }
+    }
+    spacing
+}
+
+fn merge_spacing(target: &mut SpacingConfig, source: SpacingConfig) {
+    if let Some(before_welcome) = source.before_welcome {
+        target.before_welcome = Some(before_welcome);
+    }
+    if let Some(after_welcome) = source.after_welcome {
+        target.after_welcome = Some(after_welcome);
+    }
+    if let Some(before_farewell) = source.before_farewell {
+        target.before_farewell = Some(before_farewell);
+    }
+    if let Some(gutter) = source.gutter {
+        target.gutter = Some(gutter);
+    }
+}
+
+fn merge_thresholds(target: &mut ThresholdsConfig, source: ThresholdsConfig) {
+    let pairs = [
+        (&mut target.mem_warn, source.mem_warn),
+        (&mut target.mem_crit, source.mem_crit),
+        (&mut target.swap_warn, source.swap_warn),
+        (&mut target.swap_crit, source.swap_crit),
+        (&mut target.disk_warn, source.disk_warn),
+        (&mut target.disk_crit, source.disk_crit),
+        (&mut target.temp_warn, source.temp_warn),
+        (&mut target.temp_crit, source.temp_crit),
+        (&mut target.load_warn, source.load_warn),
+        (&mut target.load_crit, source.load_crit),
+        (&mut target.pressure_warn, source.pressure_warn),
+        (&mut target.pressure_crit, source.pressure_crit),
+        (&mut target.battery_low, source.battery_low),
+    ];
+    for (target, source) in pairs {
+        if source.is_some() {
+            *target = source;
+        }
+    }
+}
+
+fn normalize_optional_text(value: Option<String>) -> Option<String> {
+    value.and_then(|value| {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
             None
         } else {
             Some(trimmed.to_string())
@@ -496,6 +1770,57 @@ fn normalize_optional_text(value: Option<String>) -> Option<String> {
     })
 }
 
+/// Keys are normalized like `disk_no_alert` paths; blank labels are dropped.
+fn normalize_disk_labels(labels: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    labels
+        .into_iter()
+        .filter_map(|(path, label)| {
+            let label = label.trim();
+            (!label.is_empty()).then(|| (normalize_mount_path(&path), label.to_string()))
+        })
+        .collect()
+}
+
+/// Module keys and color names are lowercased; blank entries are dropped.
+fn normalize_colors(colors: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    colors
+        .into_iter()
+        .filter_map(|(module, color)| {
+            let (module, color) = (module.trim(), color.trim());
+            (!module.is_empty() && !color.is_empty())
+                .then(|| (module.to_ascii_lowercase(), color.to_ascii_lowercase()))
+        })
+        .collect()
+}
+
+/// Names are lowercased to match `environment`; blank colors and banners are
+/// dropped.
+fn normalize_environments(
+    environments: BTreeMap<String, EnvironmentConfig>,
+) -> BTreeMap<String, EnvironmentConfig> {
+    environments
+        .into_iter()
+        .map(|(name, environment)| {
+            (
+                name.trim().to_ascii_lowercase(),
+                EnvironmentConfig {
+                    color: normalize_optional_text(environment.color),
+                    banner: normalize_optional_text(environment.banner),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Trims whitespace and trailing slashes so `/backup/` matches `/backup`.
+fn normalize_mount_path(path: &str) -> String {
+    let trimmed = path.trim();
+    match trimmed.trim_end_matches('/') {
+        "" if trimmed.starts_with('/') => "/".to_string(),
+        stripped => stripped.to_string(),
+    }
+}
+
 fn normalize_ordered_string_list(values: Option<Vec<String>>) -> Option<Vec<String>> {
     values.map(|values| {
         let mut normalized = Vec::new();
@@ -652,6 +1977,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn load_config_from_env_reads_typed_overrides() {
+        let vars = BTreeMap::from([
+            ("MOTDYN_FAREWELL", " bye "),
+            ("MOTDYN_SECTIONS", "uptime, hostname,,cpu"),
+            ("MOTDYN_TIME_24H", "yes"),
+            ("MOTDYN_DISK_TIMEOUT_MS", "250"),
+            ("MOTDYN_HEADER", ""),
+            ("MOTDYN_LOGIN_HOOK_RAN", "1"),
+        ]);
+        let (loaded, applied) =
+            load_config_from_env_with(|name| vars.get(name).map(ToString::to_string));
+
+        assert!(matches!(loaded.status, ConfigLoadStatus::Loaded));
+        assert_eq!(
+            applied,
+            vec![
+                "MOTDYN_FAREWELL",
+                "MOTDYN_TIME_24H",
+                "MOTDYN_DISK_TIMEOUT_MS",
+                "MOTDYN_SECTIONS"
+            ]
+        );
+        let cfg = loaded.config.unwrap();
+        assert_eq!(cfg.farewell.as_deref(), Some("bye"));
+        assert_eq!(
+            cfg.modules,
+            Some(vec![
+                "uptime".to_string(),
+                "host".to_string(),
+                "cpu".to_string()
+            ])
+        );
+        assert_eq!(cfg.time_24h, Some(true));
+        assert_eq!(cfg.disk_timeout_ms, Some(250));
+        assert_eq!(cfg.header, None);
+    }
+
+    #[test]
+    fn load_config_from_env_takes_ascii_art_inline() {
+        let vars = BTreeMap::from([
+            ("MOTDYN_ASCII_ART", " /\\_/\\\n( o.o )"),
+            ("MOTDYN_ASCII_ART_FILE", "/etc/motdyn/art.txt"),
+        ]);
+        let (loaded, _) = load_config_from_env_with(|name| vars.get(name).map(ToString::to_string));
+        assert_eq!(
+            loaded.keys.for_host("any"),
+            BTreeSet::from(["ascii_art_file".into(), "welcome".into()])
+        );
+        let cfg = loaded.config.unwrap();
+        assert_eq!(cfg.welcome.as_deref(), Some("/\\_/\\\n( o.o )"));
+        assert_eq!(cfg.ascii_art_file.as_deref(), Some("/etc/motdyn/art.txt"));
+    }
+
+    #[test]
+    fn load_config_from_env_reports_bad_values() {
+        let (loaded, applied) = load_config_from_env_with(|_| None);
+        assert!(matches!(loaded.status, ConfigLoadStatus::Missing));
+        assert!(applied.is_empty());
+
+        let vars = BTreeMap::from([("MOTDYN_SHOW_GPU", "maybe")]);
+        let (loaded, _) = load_config_from_env_with(|name| vars.get(name).map(ToString::to_string));
+        assert_eq!(
+            loaded.note().as_deref(),
+            Some("failed to parse config 'environment': MOTDYN_SHOW_GPU: invalid value 'maybe'")
+        );
+
+        let vars = BTreeMap::from([("MOTDYN_MODULES", "host,nope")]);
+        let (loaded, _) = load_config_from_env_with(|name| vars.get(name).map(ToString::to_string));
+        assert!(loaded.config.is_none());
+        assert!(loaded.note().unwrap().contains("environment"));
+    }
+
+    #[test]
+    fn load_config_accepts_sections_as_an_ordered_module_list() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "sections = [\"uptime\", \"cpu\", \"memory\", \"disk\"]\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        assert!(matches!(loaded.status, ConfigLoadStatus::Loaded));
+        assert_eq!(
+            loaded.config.and_then(|cfg| cfg.modules),
+            Some(vec![
+                "uptime".to_string(),
+                "cpu".to_string(),
+                "memory".to_string(),
+                "disk".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn load_config_skips_unknown_section_names() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "sections = [\"uptime\", \"bogus\"]\n").unwrap();
+
+        let loaded = load_config(&config_path);
+        assert!(matches!(loaded.status, ConfigLoadStatus::Loaded));
+        assert_eq!(
+            loaded.config.and_then(|cfg| cfg.modules),
+            Some(vec!["uptime".to_string()])
+        );
+
+        let vars = BTreeMap::from([("MOTDYN_SECTIONS", "uptime,nope")]);
+        let (loaded, _) = load_config_from_env_with(|name| vars.get(name).map(ToString::to_string));
+        assert_eq!(
+            loaded.config.and_then(|cfg| cfg.modules),
+            Some(vec!["uptime".to_string()])
+        );
+    }
+
     #[test]
     fn load_config_rejects_invalid_module_names() {
         let dir = tempdir().unwrap();
@@ -667,6 +2109,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn load_config_accepts_sensor_process_and_address_modules() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "modules = [\"temp\", \"procs\", \"ips\"]\n").unwrap();
+
+        let cfg = load_config(&config_path).config.unwrap();
+        assert_eq!(
+            cfg.modules,
+            Some(vec![
+                "temperature".to_string(),
+                "processes".to_string(),
+                "addresses".to_string(),
+            ])
+        );
+    }
+
     #[test]
     fn load_config_rejects_invalid_hidden_fields() {
         let dir = tempdir().unwrap();
@@ -690,7 +2149,27 @@ mod tests {
                 "https://system.example/motd.txt".into(),
                 "./system-banner.txt".into(),
             ]),
+            ascii_art_file: Some("/etc/motdyn/art.txt".into()),
             farewell: Some("sys bye".into()),
+            farewells: Some(vec!["sys later".into()]),
+            farewell_on_alert: None,
+            header: Some("Hello".to_string()),
+            show_header: None,
+            separator: None,
+            display_hostname: Some("build-farm".into()),
+            template: Some("{hostname}".into()),
+            time_format: None,
+            time_24h: Some(false),
+            timezone: Some("UTC".into()),
+            disk_no_alert: None,
+            disk_timeout_ms: Some(5000),
+            disk_mounts: Some(vec!["/".into(), "/home".into()]),
+            disk_all_local: Some(true),
+            disk_labels: Some(BTreeMap::from([(
+                "/srv/nfs/backup01".into(),
+                "Backups".into(),
+            )])),
+            colors: None,
             modules: Some(vec!["host".into(), "memory".into()]),
             remote_welcome: RemoteWelcomeConfig {
                 timeout_ms: Some(500),
@@ -701,6 +2180,55 @@ mod tests {
                 services: Some(vec!["sshd".into(), "chronyd".into()]),
             },
             output: OutputConfig::default(),
+            spacing: SpacingConfig {
+                before_welcome: Some(2),
+                after_welcome: Some(1),
+                gutter: Some(2),
+                ..SpacingConfig::default()
+            },
+            thresholds: ThresholdsConfig {
+                mem_warn: Some(70.0),
+                mem_crit: Some(85.0),
+                ..ThresholdsConfig::default()
+            },
+            cert_checks: None,
+            service_fd_checks: None,
+            scheduled_banners: None,
+            recent_logins: RecentLoginsConfig::default(),
+            log_to_syslog: Some(true),
+            show_cpu_topology: Some(true),
+            show_swap_activity: None,
+            show_cpu_usage: Some(true),
+            cpu_usage_sample_ms: Some(500),
+            resolve_hostname: Some(false),
+            cache_ttl_secs: Some(30),
+            show_gpu: None,
+            interfaces: Some(vec!["eth0".to_string()]),
+            show_containers: None,
+            show_net_throughput: Some(true),
+            net_throughput_sample_ms: Some(500),
+            uptime_format: Some("clock".into()),
+            show_pressure: Some(true),
+            environment: Some("staging".into()),
+            environments: Some(BTreeMap::from([
+                (
+                    "prod".into(),
+                    EnvironmentConfig {
+                        color: Some("red".into()),
+                        banner: Some("=== PRODUCTION ===".into()),
+                    },
+                ),
+                (
+                    "lab".into(),
+                    EnvironmentConfig {
+                        color: Some("blue".into()),
+                        banner: None,
+                    },
+                ),
+            ])),
+            ssh: None,
+            local: None,
+            hosts: None,
         };
         let usr = MotdConfig {
             welcome: Some("user".into()),
@@ -708,7 +2236,24 @@ mod tests {
                 "./user-banner.txt".into(),
                 "https://user.example/motd.txt".into(),
             ]),
+            ascii_art_file: Some("~/.config/motdyn/art.txt".into()),
             farewell: None,
+            farewells: Some(vec!["later".into(), "ciao".into()]),
+            farewell_on_alert: Some(true),
+            header: None,
+            show_header: Some(false),
+            separator: Some("---".to_string()),
+            display_hostname: None,
+            template: None,
+            time_format: Some("%H:%M".into()),
+            time_24h: None,
+            timezone: Some("Asia/Tokyo".into()),
+            disk_no_alert: Some(vec!["/backup".into()]),
+            disk_labels: None,
+            colors: Some(BTreeMap::from([("cpu".into(), "green".into())])),
+            disk_timeout_ms: Some(500),
+            disk_mounts: None,
+            disk_all_local: Some(false),
             modules: Some(vec!["time".into(), "disk".into()]),
             remote_welcome: RemoteWelcomeConfig {
                 cache_ttl_secs: Some(60),
@@ -722,6 +2267,50 @@ mod tests {
                 compact: Some(true),
                 ..OutputConfig::default()
             },
+            spacing: SpacingConfig {
+                after_welcome: Some(0),
+                ..SpacingConfig::default()
+            },
+            thresholds: ThresholdsConfig {
+                mem_crit: Some(95.0),
+                ..ThresholdsConfig::default()
+            },
+            cert_checks: Some(vec![CertCheckConfig {
+                label: "web".into(),
+                path: "/etc/ssl/web.pem".into(),
+            }]),
+            service_fd_checks: None,
+            scheduled_banners: Some(vec![ScheduledBannerConfig {
+                start: "2026-05-01".into(),
+                end: "2026-05-02".into(),
+                message: "Maintenance on Saturday".into(),
+            }]),
+            recent_logins: RecentLoginsConfig::default(),
+            log_to_syslog: None,
+            show_cpu_topology: None,
+            show_swap_activity: Some(true),
+            show_cpu_usage: None,
+            cpu_usage_sample_ms: Some(100),
+            resolve_hostname: Some(true),
+            cache_ttl_secs: None,
+            show_gpu: Some(true),
+            interfaces: None,
+            show_containers: Some(true),
+            show_net_throughput: None,
+            net_throughput_sample_ms: Some(250),
+            uptime_format: Some("compact".into()),
+            show_pressure: None,
+            environment: Some("prod".into()),
+            environments: Some(BTreeMap::from([(
+                "prod".into(),
+                EnvironmentConfig {
+                    color: Some("magenta".into()),
+                    banner: None,
+                },
+            )])),
+            ssh: None,
+            local: None,
+            hosts: None,
         };
 
         let merged = merge_config(Some(sys), Some(usr));
@@ -736,6 +2325,65 @@ mod tests {
             )
         );
         assert_eq!(merged.farewell.as_deref(), Some("sys bye"));
+        assert_eq!(
+            merged.farewells,
+            Some(vec!["later".to_string(), "ciao".to_string()])
+        );
+        assert_eq!(
+            merged.ascii_art_file.as_deref(),
+            Some("~/.config/motdyn/art.txt")
+        );
+        assert_eq!(merged.log_to_syslog, Some(true));
+        assert_eq!(merged.display_hostname.as_deref(), Some("build-farm"));
+        assert_eq!(merged.template.as_deref(), Some("{hostname}"));
+        assert_eq!(merged.time_format.as_deref(), Some("%H:%M"));
+        assert_eq!(merged.time_24h, Some(false));
+        assert_eq!(merged.timezone.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(merged.farewell_on_alert, Some(true));
+        assert_eq!(merged.header.as_deref(), Some("Hello"));
+        assert_eq!(merged.show_header, Some(false));
+        assert_eq!(merged.separator.as_deref(), Some("---"));
+        assert_eq!(merged.disk_no_alert, Some(vec!["/backup".to_string()]));
+        assert_eq!(merged.disk_timeout_ms, Some(500));
+        assert_eq!(
+            merged.disk_mounts,
+            Some(vec!["/".to_string(), "/home".to_string()])
+        );
+        assert_eq!(merged.disk_all_local, Some(false));
+        assert_eq!(
+            merged.colors,
+            Some(BTreeMap::from([("cpu".to_string(), "green".to_string())]))
+        );
+        assert_eq!(
+            merged
+                .disk_labels
+                .as_ref()
+                .and_then(|labels| labels.get("/srv/nfs/backup01"))
+                .map(String::as_str),
+            Some("Backups")
+        );
+        assert_eq!(merged.show_cpu_topology, Some(true));
+        assert_eq!(merged.show_swap_activity, Some(true));
+        assert_eq!(merged.show_cpu_usage, Some(true));
+        assert_eq!(merged.cpu_usage_sample_ms, Some(100));
+        assert_eq!(merged.resolve_hostname, Some(true));
+        assert_eq!(merged.cache_ttl_secs, Some(30));
+        assert_eq!(merged.show_gpu, Some(true));
+        assert_eq!(merged.interfaces, Some(vec!["eth0".to_string()]));
+        assert_eq!(merged.show_containers, Some(true));
+        assert_eq!(merged.show_net_throughput, Some(true));
+        assert_eq!(merged.net_throughput_sample_ms, Some(250));
+        assert_eq!(merged.uptime_format.as_deref(), Some("compact"));
+        assert_eq!(merged.show_pressure, Some(true));
+        assert_eq!(merged.environment.as_deref(), Some("prod"));
+        let environments = merged.environments.expect("environments should merge");
+        assert_eq!(environments["prod"].color.as_deref(), Some("magenta"));
+        assert_eq!(environments["prod"].banner, None);
+        assert_eq!(environments["lab"].color.as_deref(), Some("blue"));
+        assert_eq!(
+            merged.scheduled_banners.map(|banners| banners.len()),
+            Some(1)
+        );
         assert_eq!(
             merged.modules.as_deref(),
             Some(&["time".to_string(), "disk".to_string()][..])
@@ -748,6 +2396,486 @@ mod tests {
             Some(&["docker".to_string()][..])
         );
         assert_eq!(merged.output.compact, Some(true));
+        assert_eq!(merged.spacing.before_welcome, Some(2));
+        assert_eq!(merged.spacing.after_welcome, Some(0));
+        assert_eq!(merged.spacing.before_farewell, None);
+        assert_eq!(merged.spacing.gutter, Some(2));
+        assert_eq!(merged.thresholds.mem_warn, Some(70.0));
+        assert_eq!(merged.thresholds.mem_crit, Some(95.0));
+        assert_eq!(merged.cert_checks.map(|checks| checks.len()), Some(1));
+    }
+
+    #[test]
+    fn apply_session_overlay_merges_matching_table() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "farewell = \"bye\"\n[output]\ncompact = false\n[ssh]\nmodules = [\"host\", \"disk\"]\n[local]\nfarewell = \"see you\"\n[local.output]\ncompact = true\n",
+        )
+        .unwrap();
+        let cfg = load_config(&config_path)
+            .config
+            .expect("config should load");
+
+        let ssh = apply_session_overlay(cfg.clone(), SessionKind::Ssh);
+        assert_eq!(ssh.farewell.as_deref(), Some("bye"));
+        assert_eq!(
+            ssh.modules.as_deref(),
+            Some(&["host".to_string(), "disk".to_string()][..])
+        );
+        assert_eq!(ssh.output.compact, Some(false));
+        assert!(ssh.ssh.is_none() && ssh.local.is_none());
+
+        let local = apply_session_overlay(cfg, SessionKind::Local);
+        assert_eq!(local.farewell.as_deref(), Some("see you"));
+        assert_eq!(local.modules, None);
+        assert_eq!(local.output.compact, Some(true));
+    }
+
+    #[test]
+    fn apply_host_overlay_lets_exact_names_win_over_globs() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "farewell = \"bye\"\nheader = \"base\"\n\
+             [hosts.\"web-01\"]\nheader = \"web-01\"\n\
+             [hosts.\"web-*\"]\nheader = \"web\"\nfarewell = \"web bye\"\n\
+             [hosts.\"db-??\"]\nheader = \"db\"\n",
+        )
+        .unwrap();
+        let cfg = load_config(&config_path).config.unwrap();
+        assert_eq!(cfg.hosts.as_ref().map(BTreeMap::len), Some(3));
+
+        let web01 = apply_host_overlay(cfg.clone(), "WEB-01");
+        assert_eq!(web01.header.as_deref(), Some("web-01"));
+        assert_eq!(web01.farewell.as_deref(), Some("web bye"));
+        assert!(web01.hosts.is_none());
+
+        let web02 = apply_host_overlay(cfg.clone(), "web-02");
+        assert_eq!(web02.header.as_deref(), Some("web"));
+
+        assert_eq!(
+            apply_host_overlay(cfg.clone(), "db-01").header.as_deref(),
+            Some("db")
+        );
+        let other = apply_host_overlay(cfg, "db-001");
+        assert_eq!(other.header.as_deref(), Some("base"));
+        assert_eq!(other.farewell.as_deref(), Some("bye"));
+    }
+
+    #[test]
+    fn load_config_rejects_overlays_inside_host_tables() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "[hosts.\"web-*\".ssh]\nfarewell = \"bye\"\n[local.hosts.\"db\"]\nfarewell = \"bye\"\n",
+        )
+        .unwrap();
+
+        match load_config(&config_path).status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![
+                        ConfigValidationError::NestedSessionOverlay("local"),
+                        ConfigValidationError::NestedSessionOverlay("hosts"),
+                    ]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_nested_session_overlays() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "[ssh.local]\nfarewell = \"bye\"\n").unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![ConfigValidationError::NestedSessionOverlay("ssh")]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_zero_disk_timeout() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "disk_timeout_ms = 0\n").unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(issues, vec![ConfigValidationError::DiskTimeoutZero]);
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_oversized_spacing() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "[spacing]\nbefore_welcome = 1000000\nafter_welcome = 10\ngutter = 41\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![
+                        ConfigValidationError::SpacingTooLarge {
+                            key: "before_welcome",
+                            max: 10
+                        },
+                        ConfigValidationError::SpacingTooLarge {
+                            key: "gutter",
+                            max: 40
+                        },
+                    ]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_zero_cpu_usage_sample() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "show_cpu_usage = true\ncpu_usage_sample_ms = 0\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(issues, vec![ConfigValidationError::CpuUsageSampleZero]);
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_zero_progress_bar_width() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "[output]\nprogress_bars = true\nprogress_bar_width = 0\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(issues, vec![ConfigValidationError::ProgressBarWidthZero]);
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_zero_max_disk_lines() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "[output]\nmax_disk_lines = 0\n").unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(issues, vec![ConfigValidationError::MaxDiskLinesZero]);
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_zero_max_ascii_lines() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "[output]\nmax_ascii_lines = 0\n").unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(issues, vec![ConfigValidationError::MaxAsciiLinesZero]);
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_validates_thresholds() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "[thresholds]\nmem_warn = 95\nmem_crit = 90\ndisk_crit = 120\ntemp_warn = 95\ntemp_crit = 85\nload_warn = 3\nload_crit = 2\npressure_warn = 30\npressure_crit = 20\nbattery_low = 120\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![
+                        ConfigValidationError::ThresholdOrder("mem"),
+                        ConfigValidationError::ThresholdOutOfRange("disk_crit".into()),
+                        ConfigValidationError::ThresholdOrder("pressure"),
+                        ConfigValidationError::ThresholdOutOfRange("battery_low".into()),
+                        ConfigValidationError::ThresholdOrder("temp"),
+                        ConfigValidationError::ThresholdOrder("load"),
+                    ]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_validates_disk_display() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "[output]\ndisk_display = \"Free-Total\"\n").unwrap();
+        let loaded = load_config(&config_path);
+        assert_eq!(loaded.status, ConfigLoadStatus::Loaded);
+        let cfg = loaded.config.expect("config should parse");
+        assert_eq!(cfg.output.disk_display.as_deref(), Some("free_total"));
+
+        fs::write(&config_path, "[output]\ndisk_display = \"percent\"\n").unwrap();
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![ConfigValidationError::InvalidDiskDisplay("percent".into())]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_validates_temp_unit() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "[output]\ntemp_unit = \"f\"\n").unwrap();
+        let loaded = load_config(&config_path);
+        assert_eq!(loaded.status, ConfigLoadStatus::Loaded);
+        let cfg = loaded.config.expect("config should parse");
+        assert_eq!(cfg.output.temp_unit.as_deref(), Some("F"));
+
+        fs::write(&config_path, "[output]\ntemp_unit = \"kelvin\"\n").unwrap();
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![ConfigValidationError::InvalidTempUnit("kelvin".into())]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_merges_own_fields_over_include() {
+        let dir = tempdir().unwrap();
+        let base_path = dir.path().join("base.toml");
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &base_path,
+            "farewell = \"base\"\nheader = \"base\"\n[output]\ncompact = true\n",
+        )
+        .unwrap();
+        fs::write(
+            &config_path,
+            "include = \"base.toml\"\nheader = \"own\"\n[output]\nplain = true\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        assert_eq!(loaded.status, ConfigLoadStatus::Loaded);
+        assert!(loaded.warnings.is_empty());
+        assert_eq!(
+            loaded.keys.for_host("any"),
+            BTreeSet::from(["farewell".into(), "header".into(), "output".into()])
+        );
+        let cfg = loaded.config.expect("config should load");
+        assert_eq!(cfg.farewell.as_deref(), Some("base"));
+        assert_eq!(cfg.header.as_deref(), Some("own"));
+        assert_eq!(cfg.output.compact, Some(true));
+        assert_eq!(cfg.output.plain, Some(true));
+    }
+
+    #[test]
+    fn config_keys_separate_session_and_host_overlays() {
+        let table = "sections = [\"host\"]\n[ssh]\nfarewell = \"ssh\"\n[hosts.\"web-*\"]\nheader = \"web\"\n"
+            .parse::<toml::Table>()
+            .unwrap();
+        let keys = ConfigKeys::from_table(&table);
+        assert_eq!(keys.for_host("db-01"), BTreeSet::from(["modules".into()]));
+        assert_eq!(
+            keys.for_host("WEB-01"),
+            BTreeSet::from(["header".into(), "modules".into()])
+        );
+        assert_eq!(
+            keys.for_session(SessionKind::Ssh),
+            &BTreeSet::from(["farewell".into()])
+        );
+        assert!(keys.for_session(SessionKind::Local).is_empty());
+    }
+
+    #[test]
+    fn load_config_warns_about_missing_and_cyclic_includes() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "include = \"gone.toml\"\nheader = \"own\"\n").unwrap();
+        let loaded = load_config(&config_path);
+        assert_eq!(loaded.status, ConfigLoadStatus::Loaded);
+        assert_eq!(loaded.config.unwrap().header.as_deref(), Some("own"));
+        assert_eq!(loaded.warnings.len(), 1);
+        assert!(loaded.warnings[0].contains("gone.toml' does not exist"));
+
+        let other_path = dir.path().join("other.toml");
+        fs::write(&config_path, "include = \"other.toml\"\nheader = \"own\"\n").unwrap();
+        fs::write(
+            &other_path,
+            "include = \"config.toml\"\nheader = \"other\"\nfarewell = \"other\"\n",
+        )
+        .unwrap();
+        let loaded = load_config(&config_path);
+        assert_eq!(loaded.status, ConfigLoadStatus::Loaded);
+        let cfg = loaded.config.expect("config should load");
+        assert_eq!(cfg.header.as_deref(), Some("own"));
+        assert_eq!(cfg.farewell.as_deref(), Some("other"));
+        assert_eq!(loaded.warnings.len(), 1);
+        assert!(loaded.warnings[0].contains("already being loaded"));
+    }
+
+    #[test]
+    fn load_config_fails_on_invalid_include_or_overlay_include() {
+        let dir = tempdir().unwrap();
+        let base_path = dir.path().join("base.toml");
+        let config_path = dir.path().join("config.toml");
+        fs::write(&base_path, "bogus = 1\n").unwrap();
+        fs::write(
+            &config_path,
+            format!("include = \"{}\"\n", base_path.display()),
+        )
+        .unwrap();
+        match load_config(&config_path).status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Parse { path, .. }) => {
+                assert_eq!(path, base_path);
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+
+        fs::write(&config_path, "[ssh]\ninclude = \"base.toml\"\n").unwrap();
+        match load_config(&config_path).status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(issues, vec![ConfigValidationError::IncludeInOverlay("ssh")]);
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_validates_uptime_format() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "uptime_format = \" Compact \"\n").unwrap();
+        let loaded = load_config(&config_path);
+        assert_eq!(loaded.status, ConfigLoadStatus::Loaded);
+        let cfg = loaded.config.expect("config should parse");
+        assert_eq!(cfg.uptime_format.as_deref(), Some("compact"));
+
+        fs::write(&config_path, "uptime_format = \"short\"\n").unwrap();
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![ConfigValidationError::InvalidUptimeFormat("short".into())]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_rejects_empty_cert_check_fields() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "[[cert_checks]]\nlabel = \"web\"\npath = \"/etc/ssl/web.pem\"\n[[cert_checks]]\nlabel = \" \"\npath = \"\"\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![
+                        ConfigValidationError::CertCheckFieldEmpty {
+                            index: 1,
+                            field: "label"
+                        },
+                        ConfigValidationError::CertCheckFieldEmpty {
+                            index: 1,
+                            field: "path"
+                        },
+                    ]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_config_requires_one_service_fd_check_target() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "[[service_fd_checks]]\nlabel = \"nginx\"\nprocess_name = \"nginx\"\n[[service_fd_checks]]\nlabel = \"db\"\nprocess_name = \"postgres\"\npid_file = \"/run/postgres.pid\"\n[[service_fd_checks]]\nlabel = \"\"\n",
+        )
+        .unwrap();
+
+        let loaded = load_config(&config_path);
+        match loaded.status {
+            ConfigLoadStatus::Invalid(ConfigLoadError::Validation { issues, .. }) => {
+                assert_eq!(
+                    issues,
+                    vec![
+                        ConfigValidationError::ServiceFdCheckTarget(1),
+                        ConfigValidationError::ServiceFdCheckLabelEmpty(2),
+                        ConfigValidationError::ServiceFdCheckTarget(2),
+                    ]
+                );
+            }
+            other => panic!("unexpected status: {other:?}"),
+        }
     }
 
     #[test]
@@ -9,6 +9,8 @@ use std::path::{Path, PathBuf};
 use std::os::unix::fs::PermissionsExt;
 
 const SYSTEM_SCRIPT_PATH: &str = "/etc/profile.d/motdyn.sh";
+const SSHRC_PATH: &str = "/etc/ssh/sshrc";
+const UPDATE_MOTD_SCRIPT_PATH: &str = "/etc/update-motd.d/99-motdyn";
 const DEFAULT_USER_PROFILE_PATH: &str = ".profile";
 const USER_PROFILE_FILES: &[&str] = &[
     DEFAULT_USER_PROFILE_PATH,
@@ -19,6 +21,9 @@ const USER_PROFILE_FILES: &[&str] = &[
 const USER_GUARD_ENV: &str = "MOTDYN_LOGIN_HOOK_RAN";
 const USER_BLOCK_START: &str = "# >>> motdyn >>>";
 const USER_BLOCK_END: &str = "# <<< motdyn <<<";
+/// Every script `install` writes carries this, so replacing one of ours needs
+/// no backup.
+const GENERATED_MARKER: &str = "auto-generated by 'motdyn install";
 
 type Result<T> = std::result::Result<T, InstallerError>;
 
@@ -51,12 +56,60 @@ impl UserProfileTarget {
     }
 }
 
+/// Where a system-wide install hooks motdyn into the login path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemInstallMethod {
+    /// `/etc/profile.d/motdyn.sh`, run by every interactive login shell.
+    Profile,
+    /// A managed block in `/etc/ssh/sshrc`, run only for SSH sessions.
+    Sshrc,
+    /// `/etc/update-motd.d/99-motdyn`, run by pam_motd's update-motd framework.
+    Motd,
+}
+
+impl SystemInstallMethod {
+    const ALL: [Self; 3] = [Self::Profile, Self::Sshrc, Self::Motd];
+
+    pub fn parse_name(value: &str) -> Option<Self> {
+        match value {
+            "profile" => Some(Self::Profile),
+            "sshrc" => Some(Self::Sshrc),
+            "motd" => Some(Self::Motd),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Profile => "profile",
+            Self::Sshrc => "sshrc",
+            Self::Motd => "motd",
+        }
+    }
+
+    fn path(self) -> &'static Path {
+        Path::new(match self {
+            Self::Profile => SYSTEM_SCRIPT_PATH,
+            Self::Sshrc => SSHRC_PATH,
+            Self::Motd => UPDATE_MOTD_SCRIPT_PATH,
+        })
+    }
+
+    fn methods(method: Option<Self>) -> Vec<Self> {
+        method.map_or_else(|| Self::ALL.to_vec(), |method| vec![method])
+    }
+}
+
 #[derive(Debug)]
 pub enum InstallerError {
     TargetRequiresUserMode,
+    MethodRequiresSystemMode,
     MissingSystemProfileDir(PathBuf),
     MissingHome,
     IncompleteManagedBlock(PathBuf),
+    RootRequired(PathBuf),
+    /// A foreign script would need a backup where an earlier one still sits.
+    BackupExists(PathBuf),
     Io {
         action: &'static str,
         path: PathBuf,
@@ -70,6 +123,9 @@ impl fmt::Display for InstallerError {
             Self::TargetRequiresUserMode => {
                 write!(f, "`--target` is only supported together with `--user`")
             }
+            Self::MethodRequiresSystemMode => {
+                write!(f, "`--method` is only supported for system-wide installs")
+            }
             Self::MissingSystemProfileDir(path) => write!(
                 f,
                 "directory '{}' not found, cannot install system-wide script",
@@ -83,6 +139,16 @@ impl fmt::Display for InstallerError {
                 "detected incomplete motdyn managed block in '{}'; clean it up manually first",
                 path.display()
             ),
+            Self::RootRequired(path) => write!(
+                f,
+                "cannot write '{}': system-wide installs must run as root (try sudo, or --user)",
+                path.display()
+            ),
+            Self::BackupExists(path) => write!(
+                f,
+                "backup '{}' already exists; move it aside before replacing the script again",
+                path.display()
+            ),
             Self::Io {
                 action,
                 path,
@@ -94,6 +160,18 @@ impl fmt::Display for InstallerError {
 
 impl std::error::Error for InstallerError {}
 
+/// What `install` did to the login hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallOutcome {
+    Installed,
+    /// A script we did not write was replaced after being copied to `backup`.
+    Replaced {
+        backup: PathBuf,
+    },
+    /// The hook was already in place with the same content.
+    AlreadyInstalled,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum UserBlockState {
     Missing,
@@ -101,85 +179,233 @@ enum UserBlockState {
     Incomplete,
 }
 
-pub fn do_install(user: bool, target: Option<UserProfileTarget>) -> Result<()> {
+pub fn do_install(
+    user: bool,
+    target: Option<UserProfileTarget>,
+    method: Option<SystemInstallMethod>,
+) -> Result<InstallOutcome> {
     if user {
+        if method.is_some() {
+            return Err(InstallerError::MethodRequiresSystemMode);
+        }
         install_user(target)
     } else {
         if target.is_some() {
             return Err(InstallerError::TargetRequiresUserMode);
         }
-        install_system()
+        install_system(method.unwrap_or(SystemInstallMethod::Profile))
     }
 }
 
-pub fn do_uninstall(user: bool, target: Option<UserProfileTarget>) -> Result<()> {
+/// Returns the scripts that were restored from a backup `install` made.
+pub fn do_uninstall(
+    user: bool,
+    target: Option<UserProfileTarget>,
+    method: Option<SystemInstallMethod>,
+) -> Result<Vec<PathBuf>> {
     if user {
-        uninstall_user(target)
+        if method.is_some() {
+            return Err(InstallerError::MethodRequiresSystemMode);
+        }
+        uninstall_user(target)?;
+        Ok(Vec::new())
     } else {
         if target.is_some() {
             return Err(InstallerError::TargetRequiresUserMode);
         }
-        uninstall_system()
+        let mut restored = Vec::new();
+        for method in SystemInstallMethod::methods(method) {
+            if uninstall_system(method)? {
+                restored.push(method.path().to_path_buf());
+            }
+        }
+        Ok(restored)
     }
 }
 
-pub fn do_status(user: bool, target: Option<UserProfileTarget>) -> Result<()> {
+pub fn do_status(
+    user: bool,
+    target: Option<UserProfileTarget>,
+    method: Option<SystemInstallMethod>,
+) -> Result<()> {
     if user {
+        if method.is_some() {
+            return Err(InstallerError::MethodRequiresSystemMode);
+        }
         print_user_status(target)?;
     } else {
         if target.is_some() {
             return Err(InstallerError::TargetRequiresUserMode);
         }
-        print_system_status();
+        for method in SystemInstallMethod::methods(method) {
+            print_system_status(method)?;
+        }
         print_user_status(None)?;
     }
     Ok(())
 }
 
-fn install_system() -> Result<()> {
-    let profile_dir = Path::new("/etc/profile.d");
-    if !profile_dir.exists() {
-        return Err(InstallerError::MissingSystemProfileDir(
-            profile_dir.to_path_buf(),
-        ));
+fn install_system(method: SystemInstallMethod) -> Result<InstallOutcome> {
+    let path = method.path();
+    if let Some(dir) = path.parent()
+        && !dir.exists()
+    {
+        return Err(InstallerError::MissingSystemProfileDir(dir.to_path_buf()));
     }
 
-    let mut file = File::create(SYSTEM_SCRIPT_PATH)
-        .map_err(|err| io_error("create system install script", SYSTEM_SCRIPT_PATH, err))?;
+    match method {
+        SystemInstallMethod::Profile => write_system_script(path, &profile_script()),
+        SystemInstallMethod::Motd => write_system_script(path, update_motd_script()),
+        SystemInstallMethod::Sshrc => {
+            // sshrc may already carry xauth handling; only touch our block.
+            let existing = read_optional_text_file(path)?;
+            if classify_user_block(&existing) == UserBlockState::Incomplete {
+                return Err(InstallerError::IncompleteManagedBlock(path.to_path_buf()));
+            }
+            let updated = upsert_sshrc_block(&existing);
+            if updated == existing {
+                return Ok(InstallOutcome::AlreadyInstalled);
+            }
+            fs::write(path, updated).map_err(|err| system_io_error("write sshrc", path, err))?;
+            Ok(InstallOutcome::Installed)
+        }
+    }
+}
 
-    writeln!(
-        file,
-        "#!/bin/sh\n# This script is auto-generated by 'motdyn install'.\n# It will run 'motdyn' on interactive login shells.\n# motdyn failures must never block or abort login.\nif [ -z \"${{{guard_env}:-}}\" ]; then\n    export {guard_env}=1\n    case \"$-\" in\n        *i*)\n            if command -v motdyn >/dev/null 2>&1; then\n                motdyn --compact || :\n            fi\n            ;;\n    esac\nfi\n",
-        guard_env = USER_GUARD_ENV,
-    )
-    .map_err(|err| io_error("write system install script", SYSTEM_SCRIPT_PATH, err))?;
+/// Leaves an identical script alone. A differing script that `install` did
+/// not write is copied to `<name>.bak` first, after checking that `path` can
+/// be written at all, so a non-root run changes nothing. An existing backup
+/// is never overwritten, since it holds the script uninstall puts back.
+fn write_system_script(path: &Path, content: &str) -> Result<InstallOutcome> {
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => Some(existing),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(system_io_error("read system install script", path, err)),
+    };
+    let mut outcome = InstallOutcome::Installed;
+    if let Some(existing) = existing {
+        if existing == content {
+            return Ok(InstallOutcome::AlreadyInstalled);
+        }
+        fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|err| system_io_error("open system install script", path, err))?;
+        if !existing.contains(GENERATED_MARKER) {
+            let backup = backup_path(path);
+            if backup.exists() {
+                return Err(InstallerError::BackupExists(backup));
+            }
+            fs::copy(path, &backup)
+                .map_err(|err| system_io_error("back up system install script", &backup, err))?;
+            outcome = InstallOutcome::Replaced { backup };
+        }
+    }
+
+    let mut file = File::create(path)
+        .map_err(|err| system_io_error("create system install script", path, err))?;
+    file.write_all(content.as_bytes())
+        .map_err(|err| system_io_error("write system install script", path, err))?;
 
     #[cfg(unix)]
     {
         let mut perms = file
             .metadata()
-            .map_err(|err| {
-                io_error(
-                    "read system install script metadata",
-                    SYSTEM_SCRIPT_PATH,
-                    err,
-                )
-            })?
+            .map_err(|err| system_io_error("read system install script metadata", path, err))?
             .permissions();
         perms.set_mode(0o755);
-        file.set_permissions(perms).map_err(|err| {
-            io_error(
-                "set system install script permissions",
-                SYSTEM_SCRIPT_PATH,
-                err,
-            )
-        })?;
+        file.set_permissions(perms)
+            .map_err(|err| system_io_error("set system install script permissions", path, err))?;
     }
 
-    Ok(())
+    Ok(outcome)
+}
+
+/// Removes the script and puts back the one `install` backed up, if any.
+/// Returns whether a backup was restored.
+fn remove_system_script(path: &Path) -> Result<bool> {
+    let backup = backup_path(path);
+    if backup.exists() {
+        fs::rename(&backup, path)
+            .map_err(|err| system_io_error("restore system install script", path, err))?;
+        return Ok(true);
+    }
+    if path.exists() {
+        fs::remove_file(path)
+            .map_err(|err| system_io_error("remove system install script", path, err))?;
+    }
+    Ok(false)
+}
+
+/// `motdyn.sh.bak` next to `motdyn.sh`; neither profile.d nor run-parts
+/// picks it up.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
 }
 
-fn install_user(target: Option<UserProfileTarget>) -> Result<()> {
+fn profile_script() -> String {
+    format!(
+        "#!/bin/sh\n# This script is auto-generated by 'motdyn install'.\n# It will run 'motdyn' on interactive login shells.\n# motdyn failures must never block or abort login.\nif [ -z \"${{{guard_env}:-}}\" ]; then\n    export {guard_env}=1\n    case \"$-\" in\n        *i*)\n            if command -v motdyn >/dev/null 2>&1; then\n                motdyn --compact || :\n            fi\n            ;;\n    esac\nfi\n\n",
+        guard_env = USER_GUARD_ENV,
+    )
+}
+
+fn update_motd_script() -> &'static str {
+    concat!(
+        "#!/bin/sh\n",
+        "# This script is auto-generated by 'motdyn install --method motd'.\n",
+        "# update-motd runs it as root and shows its output at login.\n",
+        "# Its stdout is a file, not a terminal, hence --force.\n",
+        "# motdyn failures must never block or abort login.\n",
+        "if command -v motdyn >/dev/null 2>&1; then\n",
+        "    motdyn --compact --force || :\n",
+        "fi\n"
+    )
+}
+
+/// Only sessions with a terminal print anything, so scp and sftp stay clean.
+/// The xauth handling sshd(8) performs itself when there is no sshrc, as
+/// given in the example in its manual page.
+const SSHRC_XAUTH_STANZA: &str = concat!(
+    "if read proto cookie && [ -n \"$DISPLAY\" ]; then\n",
+    "    if [ `echo $DISPLAY | cut -c1-10` = 'localhost:' ]; then\n",
+    "        # X11UseLocalhost=yes\n",
+    "        echo add unix:`echo $DISPLAY |\n",
+    "            cut -c11-` $proto $cookie\n",
+    "    else\n",
+    "        # X11UseLocalhost=no\n",
+    "        echo add $DISPLAY $proto $cookie\n",
+    "    fi | xauth -q -\n",
+    "fi\n"
+);
+
+/// Once sshrc exists sshd leaves xauth to it, so a new file has to carry the
+/// stock stanza or X11 forwarding stops working.
+fn upsert_sshrc_block(existing: &str) -> String {
+    let (cleaned, _) = remove_sshrc_block(existing);
+    let base = if cleaned.trim().is_empty() {
+        SSHRC_XAUTH_STANZA
+    } else {
+        cleaned.as_str()
+    };
+    upsert_managed_block(base, sshrc_block())
+}
+
+fn sshrc_block() -> &'static str {
+    concat!(
+        "# >>> motdyn >>>\n",
+        "# This block is auto-generated by 'motdyn install --method sshrc'.\n",
+        "# motdyn failures must never block or abort login.\n",
+        "if [ -n \"${SSH_TTY:-}\" ] && command -v motdyn >/dev/null 2>&1; then\n",
+        "    motdyn --compact || :\n",
+        "fi\n",
+        "# <<< motdyn <<<\n"
+    )
+}
+
+fn install_user(target: Option<UserProfileTarget>) -> Result<InstallOutcome> {
     let home = user_home_dir()?;
     let targets = resolve_user_targets(&home, target)?;
     let mut planned_updates = Vec::new();
@@ -192,8 +418,13 @@ fn install_user(target: Option<UserProfileTarget>) -> Result<()> {
         planned_updates.push((profile_path, existing));
     }
 
+    let mut outcome = InstallOutcome::AlreadyInstalled;
     for (profile_path, existing) in planned_updates {
-        let updated = upsert_user_block(&existing);
+        let updated = upsert_managed_block(&existing, user_install_block());
+        if updated == existing {
+            continue;
+        }
+        outcome = InstallOutcome::Installed;
         if let Some(parent) = profile_path.parent() {
             fs::create_dir_all(parent)
                 .map_err(|err| io_error("create profile directory", parent, err))?;
@@ -202,15 +433,36 @@ fn install_user(target: Option<UserProfileTarget>) -> Result<()> {
             .map_err(|err| io_error("write user profile", &profile_path, err))?;
     }
 
-    Ok(())
+    Ok(outcome)
 }
 
-fn uninstall_system() -> Result<()> {
-    if Path::new(SYSTEM_SCRIPT_PATH).exists() {
-        fs::remove_file(SYSTEM_SCRIPT_PATH)
-            .map_err(|err| io_error("remove system install script", SYSTEM_SCRIPT_PATH, err))?;
+/// Returns whether a backed-up script was restored in place of ours.
+fn uninstall_system(method: SystemInstallMethod) -> Result<bool> {
+    let path = method.path();
+    if method != SystemInstallMethod::Sshrc {
+        return remove_system_script(path);
     }
-    Ok(())
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let existing = fs::read_to_string(path).map_err(|err| io_error("read sshrc", path, err))?;
+    if classify_user_block(&existing) == UserBlockState::Incomplete {
+        return Err(InstallerError::IncompleteManagedBlock(path.to_path_buf()));
+    }
+    match remove_sshrc_block(&existing) {
+        (_, false) => {}
+        // An sshrc holding only our block, or our block and the stock xauth
+        // stanza, was created by us; drop it so sshd goes back to its default
+        // xauth handling.
+        (cleaned, true) if cleaned.trim().is_empty() || cleaned == SSHRC_XAUTH_STANZA => {
+            fs::remove_file(path).map_err(|err| system_io_error("remove sshrc", path, err))?;
+        }
+        (cleaned, true) => {
+            fs::write(path, cleaned).map_err(|err| system_io_error("write sshrc", path, err))?;
+        }
+    }
+    Ok(false)
 }
 
 fn uninstall_user(target: Option<UserProfileTarget>) -> Result<()> {
@@ -241,15 +493,28 @@ fn uninstall_user(target: Option<UserProfileTarget>) -> Result<()> {
     Ok(())
 }
 
-fn print_system_status() {
-    if Path::new(SYSTEM_SCRIPT_PATH).exists() {
-        println!("System install: present at {}", SYSTEM_SCRIPT_PATH);
+fn print_system_status(method: SystemInstallMethod) -> Result<()> {
+    let path = method.path();
+    let present = match method {
+        SystemInstallMethod::Sshrc => {
+            classify_user_block(&read_optional_text_file(path)?) == UserBlockState::Complete
+        }
+        _ => path.exists(),
+    };
+    if present {
+        println!(
+            "System install ({}): present at {}",
+            method.name(),
+            path.display()
+        );
     } else {
         println!(
-            "System install: not present (expected {}).",
-            SYSTEM_SCRIPT_PATH
+            "System install ({}): not present (expected {}).",
+            method.name(),
+            path.display()
         );
     }
+    Ok(())
 }
 
 fn print_user_status(target: Option<UserProfileTarget>) -> Result<()> {
@@ -386,7 +651,7 @@ fn classify_user_block(content: &str) -> UserBlockState {
     }
 }
 
-fn upsert_user_block(existing: &str) -> String {
+fn upsert_managed_block(existing: &str, block: &str) -> String {
     let (mut cleaned, _) = remove_user_block(existing);
     if !cleaned.is_empty() && !cleaned.ends_with('\n') {
         cleaned.push('\n');
@@ -394,10 +659,20 @@ fn upsert_user_block(existing: &str) -> String {
     if !cleaned.is_empty() {
         cleaned.push('\n');
     }
-    cleaned.push_str(user_install_block());
+    cleaned.push_str(block);
     cleaned
 }
 
+/// Like `remove_user_block`, but also drops the blank separator line
+/// `upsert_managed_block` put in front of the block.
+fn remove_sshrc_block(existing: &str) -> (String, bool) {
+    let (mut cleaned, removed) = remove_user_block(existing);
+    while cleaned.ends_with("\n\n") {
+        cleaned.pop();
+    }
+    (cleaned, removed)
+}
+
 fn remove_user_block(existing: &str) -> (String, bool) {
     let mut kept_lines = Vec::new();
     let mut removed = false;
@@ -440,6 +715,20 @@ fn read_optional_text_file(path: &Path) -> Result<String> {
     }
 }
 
+/// Like [`io_error`], but a permission error under `/etc` means the command
+/// was not run as root.
+fn system_io_error(
+    action: &'static str,
+    path: impl AsRef<Path>,
+    source: io::Error,
+) -> InstallerError {
+    if source.kind() == io::ErrorKind::PermissionDenied {
+        InstallerError::RootRequired(path.as_ref().to_path_buf())
+    } else {
+        io_error(action, path, source)
+    }
+}
+
 fn io_error(action: &'static str, path: impl AsRef<Path>, source: io::Error) -> InstallerError {
     InstallerError::Io {
         action,
@@ -451,6 +740,95 @@ fn io_error(action: &'static str, path: impl AsRef<Path>, source: io::Error) ->
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn write_system_script_is_idempotent_and_backs_up_foreign_scripts() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("motdyn.sh");
+        let script = profile_script();
+
+        assert_eq!(
+            write_system_script(&path, &script).unwrap(),
+            InstallOutcome::Installed
+        );
+        assert_eq!(
+            write_system_script(&path, &script).unwrap(),
+            InstallOutcome::AlreadyInstalled
+        );
+
+        // An older generated script is ours to replace without a backup.
+        fs::write(&path, script.replace("--compact", "")).unwrap();
+        assert_eq!(
+            write_system_script(&path, &script).unwrap(),
+            InstallOutcome::Installed
+        );
+        assert!(!dir.path().join("motdyn.sh.bak").exists());
+
+        fs::write(&path, "echo custom\n").unwrap();
+        assert_eq!(
+            write_system_script(&path, &script).unwrap(),
+            InstallOutcome::Replaced {
+                backup: dir.path().join("motdyn.sh.bak")
+            }
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), script);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("motdyn.sh.bak")).unwrap(),
+            "echo custom\n"
+        );
+    }
+
+    #[test]
+    fn write_system_script_keeps_an_earlier_backup() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("motdyn.sh");
+        let backup = dir.path().join("motdyn.sh.bak");
+        let script = profile_script();
+        fs::write(&path, "echo first\n").unwrap();
+        write_system_script(&path, &script).unwrap();
+
+        fs::write(&path, "echo second\n").unwrap();
+        assert!(matches!(
+            write_system_script(&path, &script),
+            Err(InstallerError::BackupExists(found)) if found == backup
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "echo second\n");
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "echo first\n");
+    }
+
+    #[test]
+    fn remove_system_script_restores_the_backup() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("99-motdyn");
+        fs::write(&path, update_motd_script()).unwrap();
+        assert!(!remove_system_script(&path).unwrap());
+        assert!(!path.exists());
+        assert!(!remove_system_script(&path).unwrap());
+
+        fs::write(&path, update_motd_script()).unwrap();
+        fs::write(dir.path().join("99-motdyn.bak"), "echo custom\n").unwrap();
+        assert!(remove_system_script(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "echo custom\n");
+        assert!(!dir.path().join("99-motdyn.bak").exists());
+    }
+
+    #[test]
+    fn permission_errors_on_system_paths_ask_for_root() {
+        let err = system_io_error(
+            "write sshrc",
+            SSHRC_PATH,
+            io::Error::from(io::ErrorKind::PermissionDenied),
+        );
+        assert_eq!(
+            err.to_string(),
+            "cannot write '/etc/ssh/sshrc': system-wide installs must run as root (try sudo, or --user)"
+        );
+        assert!(matches!(
+            system_io_error("write sshrc", SSHRC_PATH, io::Error::other("disk full")),
+            InstallerError::Io { .. }
+        ));
+    }
 
     #[test]
     fn upsert_user_block_replaces_existing_block() {
@@ -458,7 +836,7 @@ mod tests {
             "line 1\n{}\nold\n{}\nline 2\n",
             USER_BLOCK_START, USER_BLOCK_END
         );
-        let updated = upsert_user_block(&existing);
+        let updated = upsert_managed_block(&existing, user_install_block());
 
         assert_eq!(updated.matches(USER_BLOCK_START).count(), 1);
         assert_eq!(updated.matches(USER_BLOCK_END).count(), 1);
@@ -467,6 +845,60 @@ mod tests {
         assert!(!updated.contains("\nold\n"));
     }
 
+    #[test]
+    fn sshrc_block_appends_after_existing_content_and_removes_cleanly() {
+        let existing =
+            "if read proto cookie; then\n    echo add :0 $proto $cookie | xauth -q -\nfi\n";
+        let updated = upsert_managed_block(existing, sshrc_block());
+
+        assert!(updated.starts_with(existing));
+        assert!(updated.contains("SSH_TTY"));
+        assert_eq!(classify_user_block(&updated), UserBlockState::Complete);
+        assert_eq!(
+            upsert_managed_block(&updated, sshrc_block())
+                .matches(USER_BLOCK_START)
+                .count(),
+            1
+        );
+
+        let (cleaned, removed) = remove_sshrc_block(&updated);
+        assert!(removed);
+        assert_eq!(cleaned, existing);
+    }
+
+    #[test]
+    fn new_sshrc_keeps_x11_forwarding_working() {
+        let created = upsert_sshrc_block("");
+
+        assert!(created.starts_with(SSHRC_XAUTH_STANZA));
+        assert!(created.contains("xauth -q -"));
+        assert_eq!(classify_user_block(&created), UserBlockState::Complete);
+        assert_eq!(upsert_sshrc_block(&created), created);
+
+        let (cleaned, removed) = remove_sshrc_block(&created);
+        assert!(removed);
+        assert_eq!(cleaned, SSHRC_XAUTH_STANZA);
+    }
+
+    #[test]
+    fn system_install_method_maps_names_to_paths() {
+        assert_eq!(
+            SystemInstallMethod::parse_name("sshrc"),
+            Some(SystemInstallMethod::Sshrc)
+        );
+        assert_eq!(SystemInstallMethod::parse_name("cron"), None);
+        assert_eq!(
+            SystemInstallMethod::Motd.path(),
+            Path::new("/etc/update-motd.d/99-motdyn")
+        );
+        assert_eq!(
+            SystemInstallMethod::methods(None),
+            SystemInstallMethod::ALL.to_vec()
+        );
+        assert!(update_motd_script().starts_with("#!/bin/sh\n"));
+        assert!(profile_script().contains(USER_GUARD_ENV));
+    }
+
     #[test]
     fn remove_user_block_drops_only_managed_lines() {
         let existing = format!(
@@ -0,0 +1,25 @@
+//! The metric collection behind the `motdyn` CLI, for tools that want the
+//! same host facts without the banner. [`SystemMetrics`] serializes to the
+//! object `motdyn --json` prints. The API needs the default `full` feature
+//! set; slim builds only ship the binary's template renderer.
+
+#[cfg(feature = "config-toml")]
+pub mod config;
+#[cfg(feature = "full")]
+pub mod motd;
+
+#[cfg(feature = "full")]
+pub use motd::{CpuMetrics, DiskMetrics, SystemMetrics, UsageMetrics};
+
+/// Probes the modules the built-in defaults select for the current user,
+/// as `motdyn --json` does without a config file, and does not record the
+/// call as a login.
+///
+/// ```no_run
+/// let metrics = motdyn::collect_metrics();
+/// println!("{} is up {:?}s", metrics.host_name, metrics.uptime_secs);
+/// ```
+#[cfg(feature = "full")]
+pub fn collect_metrics() -> SystemMetrics {
+    motd::collect_metrics(motd::ModuleProfile::Auto, &config::MotdConfig::default())
+}
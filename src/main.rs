@@ -2,27 +2,36 @@ use osarg::{Arg, Error, Parser, help};
 use std::ffi::OsString;
 use std::path::PathBuf;
 
-#[cfg(feature = "config-toml")]
-mod config;
+mod completions;
 #[cfg(feature = "install-hooks")]
 mod installer;
-#[cfg(feature = "full")]
-mod motd;
 mod template;
 
 #[cfg(feature = "full")]
-use config::{expand_tilde, load_config, merge_config};
+use motdyn::{config, motd};
+
+#[cfg(feature = "full")]
+use config::{
+    LoadedConfig, MotdConfig, SessionKind, apply_host_overlay, apply_session_overlay, expand_tilde,
+    load_config, load_config_from_env, merge_config,
+};
 #[cfg(feature = "full")]
 use std::path::Path;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const DEFAULT_TEMPLATE_OUTPUT: &str = "Welcome!";
+#[cfg(feature = "full")]
+const STATIC_MOTD_PATH: &str = "/etc/motd";
+#[cfg(feature = "full")]
+const SYSTEM_CONFIG_PATH: &str = "/etc/motdyn/config.toml";
+#[cfg(feature = "full")]
+const USER_CONFIG_PATH: &str = "~/.config/motdyn/config.toml";
 
 #[cfg(feature = "full")]
 const ROOT_HELP_SECTIONS: &[help::Section<'static>] = &[
     help::Section::new(
         "commands:",
-        "  install      install motdyn into login startup hooks\n  uninstall    remove motdyn from login startup hooks\n  status       show whether motdyn is installed for login shells",
+        "  install      install motdyn into login startup hooks\n  uninstall    remove motdyn from login startup hooks\n  status       show whether motdyn is installed for login shells\n  check-update compare this build with the latest GitHub release\n  config validate  check both config files and print the merged result\n  preview      render the banner as a login would, recording nothing (try --config PATH)\n  generate     write a colorless banner to /etc/motd for cron or a systemd timer\n  completions SHELL  print a bash, zsh, fish or powershell completion script",
     ),
     help::Section::new(
         "template options:",
@@ -30,7 +39,7 @@ const ROOT_HELP_SECTIONS: &[help::Section<'static>] = &[
     ),
     help::Section::new(
         "motd options:",
-        "  -v, --verbose              show diagnostics\n      --profile auto|full|basic\n      --plain                disable ANSI color\n      --compact              use dense output\n      --section-headers      group output by section",
+        "  -v, --verbose              show diagnostics\n      --profile auto|full|basic\n      --plain                disable ANSI color\n      --color auto|always|never\n      --no-color             same as --color never\n      --compact              use dense output\n      --section-headers      group output by section\n      --format default|ansi-safe|toml|json\n      --json                 same as --format json\n      --json-style raw|human   numbers or display strings in JSON\n      --debug                log collector timing to stderr\n      --welcome-only         print only the welcome banner\n  -q, --quiet            print a one-line summary (alias --minimal)\n      --check                check thresholds and exit 0 ok, 1 warn, 2 critical\n      --force                print even without a terminal or under an SSH forced command\n      --config PATH          load only PATH instead of the system and user configs\n      --width N              fit module lines into N columns",
     ),
     help::Section::new(
         "standard options:",
@@ -40,6 +49,10 @@ const ROOT_HELP_SECTIONS: &[help::Section<'static>] = &[
 
 #[cfg(not(feature = "full"))]
 const ROOT_HELP_SECTIONS: &[help::Section<'static>] = &[
+    help::Section::new(
+        "commands:",
+        "  completions SHELL  print a bash, zsh, fish or powershell completion script",
+    ),
     help::Section::new(
         "template options:",
         "      --text TEXT          render TEXT as an environment template\n      --file PATH          render a local template file\n      --env-prefix PREFIX  read ${NAME} from PREFIXNAME",
@@ -53,10 +66,41 @@ const ROOT_HELP_SECTIONS: &[help::Section<'static>] = &[
 const ROOT_HELP: help::Help<'static> =
     help::Help::new("motdyn [OPTIONS] [COMMAND]", ROOT_HELP_SECTIONS);
 
+#[cfg(feature = "full")]
+const CONFIG_HELP_SECTIONS: &[help::Section<'static>] = &[
+    help::Section::new(
+        "commands:",
+        "  validate    check the system and user config files and print the merged config",
+    ),
+    help::Section::new(
+        "options:",
+        "  -h, --help             show help\n  -V, --version          show version\n      --config PATH      check only PATH",
+    ),
+];
+#[cfg(feature = "full")]
+const CONFIG_HELP: help::Help<'static> =
+    help::Help::new("motdyn config validate", CONFIG_HELP_SECTIONS);
+
+const COMPLETIONS_HELP_SECTIONS: &[help::Section<'static>] = &[help::Section::new(
+    "options:",
+    "  -h, --help             show help\n  -V, --version          show version\n  SHELL                  bash, zsh, fish, or powershell",
+)];
+const COMPLETIONS_HELP: help::Help<'static> =
+    help::Help::new("motdyn completions SHELL", COMPLETIONS_HELP_SECTIONS);
+
+#[cfg(feature = "full")]
+const GENERATE_HELP_SECTIONS: &[help::Section<'static>] = &[help::Section::new(
+    "options:",
+    "  -h, --help             show help\n  -V, --version          show version\n      --output PATH      write to PATH instead of /etc/motd\n      --config PATH      load only PATH instead of the system and user configs\n      --profile auto|full|basic",
+)];
+#[cfg(feature = "full")]
+const GENERATE_HELP: help::Help<'static> =
+    help::Help::new("motdyn generate [OPTIONS]", GENERATE_HELP_SECTIONS);
+
 #[cfg(feature = "install-hooks")]
 const INSTALL_HELP_SECTIONS: &[help::Section<'static>] = &[help::Section::new(
     "options:",
-    "  -h, --help             show help\n  -V, --version          show version\n      --user             install only for the current user\n      --target TARGET    profile, bash_profile, bash_login, or zprofile",
+    "  -h, --help             show help\n  -V, --version          show version\n      --user             install only for the current user\n      --target TARGET    profile, bash_profile, bash_login, or zprofile\n      --method METHOD    profile, sshrc, or motd (system-wide only)",
 )];
 #[cfg(feature = "install-hooks")]
 const INSTALL_HELP: help::Help<'static> =
@@ -65,7 +109,7 @@ const INSTALL_HELP: help::Help<'static> =
 #[cfg(feature = "install-hooks")]
 const UNINSTALL_HELP_SECTIONS: &[help::Section<'static>] = &[help::Section::new(
     "options:",
-    "  -h, --help             show help\n  -V, --version          show version\n      --user             remove only the current user's install hook\n      --target TARGET    profile, bash_profile, bash_login, or zprofile",
+    "  -h, --help             show help\n  -V, --version          show version\n      --user             remove only the current user's install hook\n      --target TARGET    profile, bash_profile, bash_login, or zprofile\n      --method METHOD    profile, sshrc, or motd (system-wide only)",
 )];
 #[cfg(feature = "install-hooks")]
 const UNINSTALL_HELP: help::Help<'static> =
@@ -74,7 +118,7 @@ const UNINSTALL_HELP: help::Help<'static> =
 #[cfg(feature = "install-hooks")]
 const STATUS_HELP_SECTIONS: &[help::Section<'static>] = &[help::Section::new(
     "options:",
-    "  -h, --help             show help\n  -V, --version          show version\n      --user             check only the current user's install hook\n      --target TARGET    profile, bash_profile, bash_login, or zprofile",
+    "  -h, --help             show help\n  -V, --version          show version\n      --user             check only the current user's install hook\n      --target TARGET    profile, bash_profile, bash_login, or zprofile\n      --method METHOD    profile, sshrc, or motd (system-wide only)",
 )];
 #[cfg(feature = "install-hooks")]
 const STATUS_HELP: help::Help<'static> =
@@ -92,8 +136,37 @@ struct Cli {
     compact: bool,
     #[cfg(feature = "full")]
     section_headers: bool,
+    #[cfg(feature = "full")]
+    format: FormatArg,
+    #[cfg(feature = "full")]
+    json_style: JsonStyleArg,
+    #[cfg(feature = "full")]
+    color: ColorArg,
+    #[cfg(feature = "full")]
+    debug: bool,
+    #[cfg(feature = "full")]
+    welcome_only: bool,
+    #[cfg(feature = "full")]
+    quiet: bool,
+    #[cfg(feature = "full")]
+    check: bool,
+    #[cfg(feature = "full")]
+    check_update: bool,
+    #[cfg(feature = "full")]
+    validate_config: bool,
+    #[cfg(feature = "full")]
+    preview: bool,
+    #[cfg(feature = "full")]
+    force: bool,
+    #[cfg(feature = "full")]
+    generate: Option<PathBuf>,
+    #[cfg(feature = "full")]
+    config: Option<PathBuf>,
+    #[cfg(feature = "full")]
+    width: Option<usize>,
     #[cfg(feature = "install-hooks")]
     cmd: Option<Commands>,
+    completions: Option<completions::Shell>,
     template: TemplateInput,
 }
 
@@ -141,6 +214,88 @@ impl From<ProfileArg> for motd::ModuleProfile {
     }
 }
 
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum FormatArg {
+    #[default]
+    Default,
+    AnsiSafe,
+    Toml,
+    Json,
+}
+
+#[cfg(feature = "full")]
+impl FormatArg {
+    fn parse(value: String) -> Result<Self, Error> {
+        match value.as_str() {
+            "default" => Ok(Self::Default),
+            "ansi-safe" => Ok(Self::AnsiSafe),
+            "toml" => Ok(Self::Toml),
+            "json" => Ok(Self::Json),
+            _ => Err(Error::invalid_value_for(value.into())),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum JsonStyleArg {
+    #[default]
+    Raw,
+    Human,
+}
+
+#[cfg(feature = "full")]
+impl JsonStyleArg {
+    fn parse(value: String) -> Result<Self, Error> {
+        match value.as_str() {
+            "raw" => Ok(Self::Raw),
+            "human" => Ok(Self::Human),
+            _ => Err(Error::invalid_value_for(value.into())),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl From<JsonStyleArg> for motd::JsonStyle {
+    fn from(value: JsonStyleArg) -> Self {
+        match value {
+            JsonStyleArg::Raw => Self::Raw,
+            JsonStyleArg::Human => Self::Human,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum ColorArg {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[cfg(feature = "full")]
+impl ColorArg {
+    fn parse(value: String) -> Result<Self, Error> {
+        match value.as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(Error::invalid_value_for(value.into())),
+        }
+    }
+
+    /// `auto` colors only a terminal and honors a non-empty `NO_COLOR`.
+    fn enabled(self, no_color: Option<OsString>, stdout_is_terminal: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => no_color.is_none_or(|value| value.is_empty()) && stdout_is_terminal,
+        }
+    }
+}
+
 #[cfg(feature = "install-hooks")]
 #[derive(Debug)]
 enum Commands {
@@ -154,6 +309,7 @@ enum Commands {
 struct CommandArgs {
     user: bool,
     target: Option<installer::UserProfileTarget>,
+    method: Option<installer::SystemInstallMethod>,
 }
 
 #[cfg(feature = "install-hooks")]
@@ -233,6 +389,54 @@ where
             Arg::Long("section-headers") => {
                 cli.section_headers = true;
             }
+            #[cfg(feature = "full")]
+            Arg::Long("format") => {
+                cli.format = FormatArg::parse(parser.string_owned()?)?;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("json") => {
+                cli.format = FormatArg::Json;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("json-style") => {
+                cli.json_style = JsonStyleArg::parse(parser.string_owned()?)?;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("color") => {
+                cli.color = ColorArg::parse(parser.string_owned()?)?;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("no-color") => {
+                cli.color = ColorArg::Never;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("debug") => {
+                cli.debug = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("welcome-only") | Arg::Long("art-only") => {
+                cli.welcome_only = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Short('q') | Arg::Long("quiet") | Arg::Long("minimal") => {
+                cli.quiet = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("check") => {
+                cli.check = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("force") => {
+                cli.force = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("config") => {
+                cli.config = Some(parser.value()?.to_path_buf());
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("width") => {
+                cli.width = Some(parse_width(parser.string_owned()?)?);
+            }
             Arg::Long("text") => {
                 cli.template.source = Some(TemplateSource::Text(parser.string_owned()?));
             }
@@ -249,6 +453,19 @@ where
                 "uninstall" => return parse_command(parser, cli, CommandKind::Uninstall),
                 #[cfg(feature = "install-hooks")]
                 "status" => return parse_command(parser, cli, CommandKind::Status),
+                #[cfg(feature = "full")]
+                "check-update" => {
+                    cli.check_update = true;
+                }
+                #[cfg(feature = "full")]
+                "preview" if !cli.preview => {
+                    cli.preview = true;
+                }
+                #[cfg(feature = "full")]
+                "config" => return parse_config_command(parser, cli),
+                #[cfg(feature = "full")]
+                "generate" => return parse_generate_command(parser, cli),
+                "completions" => return parse_completions_command(parser, cli),
                 _ => return Err(Error::unexpected_argument(value.to_os_string())),
             },
             other => return Err(other.unexpected()),
@@ -294,6 +511,50 @@ where
             Arg::Long("section-headers") => {
                 cli.section_headers = true;
             }
+            #[cfg(feature = "full")]
+            Arg::Long("format") => {
+                cli.format = FormatArg::parse(parser.string_owned()?)?;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("json") => {
+                cli.format = FormatArg::Json;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("json-style") => {
+                cli.json_style = JsonStyleArg::parse(parser.string_owned()?)?;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("color") => {
+                cli.color = ColorArg::parse(parser.string_owned()?)?;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("no-color") => {
+                cli.color = ColorArg::Never;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("debug") => {
+                cli.debug = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("welcome-only") | Arg::Long("art-only") => {
+                cli.welcome_only = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Short('q') | Arg::Long("quiet") | Arg::Long("minimal") => {
+                cli.quiet = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("check") => {
+                cli.check = true;
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("config") => {
+                cli.config = Some(parser.value()?.to_path_buf());
+            }
+            #[cfg(feature = "full")]
+            Arg::Long("width") => {
+                cli.width = Some(parse_width(parser.string_owned()?)?);
+            }
             Arg::Long("text") => {
                 cli.template.source = Some(TemplateSource::Text(parser.string_owned()?));
             }
@@ -310,6 +571,13 @@ where
                 let value = parser.string_owned()?;
                 args.target = Some(parse_user_profile_target(value)?);
             }
+            Arg::Long("method") => {
+                let value = parser.string_owned()?;
+                args.method = Some(
+                    installer::SystemInstallMethod::parse_name(&value)
+                        .ok_or_else(|| Error::invalid_value_for(value.into()))?,
+                );
+            }
             Arg::Value(value) => return Err(value.unexpected()),
             other => return Err(other.unexpected()),
         }
@@ -324,6 +592,95 @@ where
     Ok(CliAction::Run(cli))
 }
 
+#[cfg(feature = "full")]
+fn parse_width(value: String) -> Result<usize, Error> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|width| *width > 0)
+        .ok_or_else(|| Error::invalid_value_for(value.into()))
+}
+
+/// `config` takes exactly one action, `validate`, and no further arguments.
+#[cfg(feature = "full")]
+fn parse_config_command<I>(mut parser: Parser<I>, mut cli: Cli) -> Result<CliAction, Error>
+where
+    I: Iterator<Item = OsString>,
+{
+    while let Some(arg) = parser.next()? {
+        match arg {
+            Arg::Short('h') | Arg::Long("help") => return Ok(CliAction::Help(CONFIG_HELP)),
+            Arg::Short('V') | Arg::Long("version") => return Ok(CliAction::Version),
+            Arg::Long("config") => {
+                cli.config = Some(parser.value()?.to_path_buf());
+            }
+            Arg::Value(value) if !cli.validate_config && value.to_str()? == "validate" => {
+                cli.validate_config = true;
+            }
+            other => return Err(other.unexpected()),
+        }
+    }
+
+    if !cli.validate_config {
+        return Err(Error::missing_argument_for("config".into()));
+    }
+    Ok(CliAction::Run(cli))
+}
+
+/// `generate` writes to `--output`, or `/etc/motd` by default.
+#[cfg(feature = "full")]
+fn parse_generate_command<I>(mut parser: Parser<I>, mut cli: Cli) -> Result<CliAction, Error>
+where
+    I: Iterator<Item = OsString>,
+{
+    let mut output = PathBuf::from(STATIC_MOTD_PATH);
+    while let Some(arg) = parser.next()? {
+        match arg {
+            Arg::Short('h') | Arg::Long("help") => return Ok(CliAction::Help(GENERATE_HELP)),
+            Arg::Short('V') | Arg::Long("version") => return Ok(CliAction::Version),
+            Arg::Long("output") => {
+                output = parser.value()?.to_path_buf();
+            }
+            Arg::Long("config") => {
+                cli.config = Some(parser.value()?.to_path_buf());
+            }
+            Arg::Long("profile") => {
+                cli.profile = ProfileArg::parse(parser.string_owned()?)?;
+            }
+            other => return Err(other.unexpected()),
+        }
+    }
+
+    cli.generate = Some(output);
+    Ok(CliAction::Run(cli))
+}
+
+/// `completions` takes exactly one shell name.
+fn parse_completions_command<I>(mut parser: Parser<I>, mut cli: Cli) -> Result<CliAction, Error>
+where
+    I: Iterator<Item = OsString>,
+{
+    while let Some(arg) = parser.next()? {
+        match arg {
+            Arg::Short('h') | Arg::Long("help") => return Ok(CliAction::Help(COMPLETIONS_HELP)),
+            Arg::Short('V') | Arg::Long("version") => return Ok(CliAction::Version),
+            Arg::Value(value) if cli.completions.is_none() => {
+                let name = value.to_str()?;
+                cli.completions = Some(
+                    completions::Shell::parse_name(name)
+                        .ok_or_else(|| Error::invalid_value_for(name.to_string().into()))?,
+                );
+            }
+            other => return Err(other.unexpected()),
+        }
+    }
+
+    if cli.completions.is_none() {
+        return Err(Error::missing_argument_for("completions".into()));
+    }
+    Ok(CliAction::Run(cli))
+}
+
 #[cfg(feature = "install-hooks")]
 fn command_help(kind: CommandKind) -> help::Help<'static> {
     match kind {
@@ -340,6 +697,10 @@ fn parse_user_profile_target(value: String) -> Result<installer::UserProfileTarg
 }
 
 fn run_cli(cli: Cli) {
+    if let Some(shell) = cli.completions {
+        print!("{}", completions::script(shell));
+        return;
+    }
     if cli.template.source.is_some() {
         render_template_or_exit(&cli.template);
         return;
@@ -348,23 +709,39 @@ fn run_cli(cli: Cli) {
     #[cfg(feature = "install-hooks")]
     match cli.cmd {
         Some(Commands::Install(args)) => {
-            if let Err(e) = installer::do_install(args.user, args.target) {
-                eprintln!("Install failed: {}", e);
-                std::process::exit(1);
+            match installer::do_install(args.user, args.target, args.method) {
+                Ok(installer::InstallOutcome::Installed) => println!("Install successful!"),
+                Ok(installer::InstallOutcome::Replaced { backup }) => println!(
+                    "Install successful! The previous script was saved to {}.",
+                    backup.display()
+                ),
+                Ok(installer::InstallOutcome::AlreadyInstalled) => {
+                    println!("Already installed; nothing changed.")
+                }
+                Err(e) => {
+                    eprintln!("Install failed: {}", e);
+                    std::process::exit(1);
+                }
             }
-            println!("Install successful!");
             return;
         }
         Some(Commands::Uninstall(args)) => {
-            if let Err(e) = installer::do_uninstall(args.user, args.target) {
-                eprintln!("Uninstall failed: {}", e);
-                std::process::exit(1);
+            match installer::do_uninstall(args.user, args.target, args.method) {
+                Ok(restored) => {
+                    for path in restored {
+                        println!("Restored {} from its backup.", path.display());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Uninstall failed: {}", e);
+                    std::process::exit(1);
+                }
             }
             println!("Uninstall successful!");
             return;
         }
         Some(Commands::Status(args)) => {
-            if let Err(e) = installer::do_status(args.user, args.target) {
+            if let Err(e) = installer::do_status(args.user, args.target, args.method) {
                 eprintln!("Status check failed: {}", e);
                 std::process::exit(1);
             }
@@ -373,6 +750,20 @@ fn run_cli(cli: Cli) {
         None => {}
     }
 
+    #[cfg(feature = "full")]
+    if cli.check_update {
+        run_check_update();
+        return;
+    }
+
+    #[cfg(feature = "full")]
+    if cli.validate_config {
+        if !run_config_validate(cli.config.as_deref()) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
     #[cfg(feature = "full")]
     run_motd_safely(&cli);
 
@@ -402,24 +793,216 @@ fn render_template_or_exit(input: &TemplateInput) {
     }
 }
 
+/// The system config path, if it is read at all, and the user config path.
+/// `--config` loads one explicit file in the user slot and skips the system
+/// file, so nothing from the host's own configs leaks in.
+#[cfg(feature = "full")]
+fn config_paths(explicit: Option<&Path>) -> (Option<PathBuf>, PathBuf) {
+    match explicit {
+        Some(path) => (None, path.to_path_buf()),
+        None => (
+            Some(PathBuf::from(SYSTEM_CONFIG_PATH)),
+            expand_tilde(USER_CONFIG_PATH),
+        ),
+    }
+}
+
+/// System file, then user file, each with its matching `[hosts."..."]`
+/// tables applied, then the `[ssh]`/`[local]` overlay for this session, then
+/// `MOTDYN_*` overrides; each later layer wins. Also returns, per key set
+/// anywhere, the layer it was taken from, such as `farewell (env)`.
+#[cfg(feature = "full")]
+fn effective_config(
+    sys_cfg: Option<LoadedConfig>,
+    usr_cfg: LoadedConfig,
+    env_cfg: LoadedConfig,
+    session: SessionKind,
+    host_name: &str,
+) -> (MotdConfig, Vec<String>) {
+    let sys_keys = sys_cfg
+        .as_ref()
+        .map(|loaded| loaded.keys.clone())
+        .unwrap_or_default();
+    let (system_overlay, user_overlay) = match session {
+        SessionKind::Ssh => ("system [ssh]", "user [ssh]"),
+        SessionKind::Local => ("system [local]", "user [local]"),
+    };
+    let mut origins = std::collections::BTreeMap::new();
+    for (keys, source) in [
+        (sys_keys.for_host(host_name), "system"),
+        (usr_cfg.keys.for_host(host_name), "user"),
+        (sys_keys.for_session(session).clone(), system_overlay),
+        (usr_cfg.keys.for_session(session).clone(), user_overlay),
+        (env_cfg.keys.for_host(host_name), "env"),
+    ] {
+        for key in keys {
+            origins.insert(key, source);
+        }
+    }
+
+    let for_host =
+        |loaded: LoadedConfig| loaded.config.map(|cfg| apply_host_overlay(cfg, host_name));
+    let cfg = merge_config(
+        Some(apply_session_overlay(
+            merge_config(sys_cfg.and_then(for_host), for_host(usr_cfg)),
+            session,
+        )),
+        env_cfg.config,
+    );
+    let origins = origins
+        .into_iter()
+        .map(|(key, source)| format!("{} ({})", key, source))
+        .collect();
+    (cfg, origins)
+}
+
+/// Loads both config files and the `MOTDYN_*` overrides strictly, reporting
+/// every read, parse or validation error, then prints the merged config the
+/// next login would use. Returns `false` when any of them is invalid.
+#[cfg(feature = "full")]
+fn run_config_validate(config_path: Option<&Path>) -> bool {
+    let (sys_cfg_path, usr_cfg_path) = config_paths(config_path);
+    let sys_cfg = sys_cfg_path.as_deref().map(load_config);
+    let usr_cfg = load_config(&usr_cfg_path);
+    let (env_cfg, env_vars) = load_config_from_env();
+
+    let mut valid = true;
+    for (path, loaded) in sys_cfg_path
+        .iter()
+        .zip(&sys_cfg)
+        .chain([(&usr_cfg_path, &usr_cfg)])
+    {
+        println!("{}: {}", path.display(), loaded.status_label());
+        for warning in &loaded.warnings {
+            eprintln!("warning: {}", warning);
+        }
+        if let Some(note) = loaded.note() {
+            eprintln!("{}", note);
+            valid = false;
+        }
+    }
+    if config_path.is_some() && usr_cfg.config.is_none() && valid {
+        eprintln!("config file '{}' does not exist", usr_cfg_path.display());
+        valid = false;
+    }
+    if env_vars.is_empty() {
+        println!("environment: none");
+    } else {
+        println!(
+            "environment: {} ({})",
+            env_cfg.status_label(),
+            env_vars.join(", ")
+        );
+    }
+    if let Some(note) = env_cfg.note() {
+        eprintln!("{}", note);
+        valid = false;
+    }
+
+    let session = SessionKind::detect();
+    let host_name = motd::host_name().unwrap_or_default();
+    let (merged_cfg, _) = effective_config(sys_cfg, usr_cfg, env_cfg, session, &host_name);
+    println!();
+    println!("Effective config ({} session):", session.key());
+    println!("{:#?}", merged_cfg);
+    valid
+}
+
+/// Decides both `--color auto` and whether a login banner is printed.
+#[cfg(feature = "full")]
+fn stdout_is_terminal() -> bool {
+    std::io::IsTerminal::is_terminal(&std::io::stdout())
+}
+
+/// scp, rsync, ansible and `ssh host command` parse what they read, so the
+/// banner is only printed to a terminal and never under an SSH forced
+/// command, which sets `SSH_ORIGINAL_COMMAND`.
+#[cfg(feature = "full")]
+fn interactive_session(stdout_is_terminal: bool, original_command: Option<OsString>) -> bool {
+    stdout_is_terminal && original_command.is_none_or(|command| command.is_empty())
+}
+
+/// Only the plain login banner is held back for automation: `--check`, the
+/// metrics formats, `generate`, `preview` and the outputs meant for scripts
+/// or pasting (`--welcome-only`, `--quiet`, `--format ansi-safe`) always run.
+#[cfg(feature = "full")]
+fn prints_login_banner(cli: &Cli) -> bool {
+    !(cli.force
+        || cli.check
+        || cli.preview
+        || cli.welcome_only
+        || cli.quiet
+        || cli.generate.is_some()
+        || matches!(
+            cli.format,
+            FormatArg::Toml | FormatArg::Json | FormatArg::AnsiSafe
+        ))
+}
+
+#[cfg(feature = "full")]
+fn should_run(cli: &Cli, stdout_is_terminal: bool, original_command: Option<OsString>) -> bool {
+    !prints_login_banner(cli) || interactive_session(stdout_is_terminal, original_command)
+}
+
 #[cfg(feature = "full")]
 fn run_motd(cli: &Cli) {
-    let sys_cfg_path = Path::new("/etc/motdyn/config.toml");
-    let usr_cfg_path = expand_tilde("~/.config/motdyn/config.toml");
+    motd::init_debug(cli.debug);
+    let stdout_is_terminal = stdout_is_terminal();
+    if !should_run(
+        cli,
+        stdout_is_terminal,
+        std::env::var_os("SSH_ORIGINAL_COMMAND"),
+    ) {
+        return;
+    }
+    #[cfg(feature = "color")]
+    colored::control::set_override(
+        cli.color
+            .enabled(std::env::var_os("NO_COLOR"), stdout_is_terminal),
+    );
+    let (sys_cfg_path, usr_cfg_path) = config_paths(cli.config.as_deref());
 
-    let sys_cfg = load_config(sys_cfg_path);
+    let sys_cfg = sys_cfg_path.as_deref().map(load_config);
     let usr_cfg = load_config(&usr_cfg_path);
-    let render_ctx = motd::RenderContext {
-        system_config_path: sys_cfg_path.display().to_string(),
-        system_config_status: sys_cfg.status_label().to_string(),
+    let (env_cfg, env_vars) = load_config_from_env();
+    let session = SessionKind::detect();
+    let mut render_ctx = motd::RenderContext {
+        system_config_path: SYSTEM_CONFIG_PATH.to_string(),
+        system_config_status: sys_cfg
+            .as_ref()
+            .map_or("skipped (--config)", |loaded| loaded.status_label())
+            .to_string(),
         user_config_path: usr_cfg_path.display().to_string(),
         user_config_status: usr_cfg.status_label().to_string(),
-        config_notes: [sys_cfg.note(), usr_cfg.note()]
-            .into_iter()
-            .flatten()
+        env_overrides: env_vars.iter().map(ToString::to_string).collect(),
+        config_origins: Vec::new(),
+        config_notes: sys_cfg
+            .iter()
+            .chain([&usr_cfg])
+            .flat_map(|loaded| loaded.warnings.iter().cloned())
+            .chain(
+                [
+                    sys_cfg.as_ref().and_then(|loaded| loaded.note()),
+                    usr_cfg.note(),
+                    env_cfg.note(),
+                ]
+                .into_iter()
+                .flatten(),
+            )
             .collect(),
+        session: session.key().to_string(),
+        mode: if cli.generate.is_some() {
+            motd::RenderMode::Static
+        } else if cli.preview {
+            motd::RenderMode::Preview
+        } else {
+            motd::RenderMode::Login
+        },
     };
-    let mut merged_cfg = merge_config(sys_cfg.config, usr_cfg.config);
+    let host_name = motd::host_name().unwrap_or_default();
+    let (mut merged_cfg, config_origins) =
+        effective_config(sys_cfg, usr_cfg, env_cfg, session, &host_name);
+    render_ctx.config_origins = config_origins;
 
     if cli.plain {
         merged_cfg.output.plain = Some(true);
@@ -430,10 +1013,68 @@ fn run_motd(cli: &Cli) {
     if cli.section_headers {
         merged_cfg.output.section_headers = Some(true);
     }
+    if cli.format == FormatArg::AnsiSafe {
+        merged_cfg.output.ansi_safe = Some(true);
+    }
+    if let Some(width) = cli.width {
+        merged_cfg.output.width = Some(width);
+    }
+
+    if cli.welcome_only {
+        motd::render_welcome_only(&merged_cfg);
+        return;
+    }
+    if cli.check {
+        std::process::exit(motd::run_check(&merged_cfg));
+    }
+    if let Some(path) = cli.generate.as_deref() {
+        merged_cfg.output.ansi_safe = Some(true);
+        if let Err(err) = motd::write_static(path, cli.profile.into(), &merged_cfg, &render_ctx) {
+            eprintln!("Generate failed: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+    let metrics_format = match cli.format {
+        FormatArg::Toml => Some(motd::MetricsFormat::Toml),
+        FormatArg::Json => Some(motd::MetricsFormat::Json(cli.json_style.into())),
+        FormatArg::Default | FormatArg::AnsiSafe => None,
+    };
+    if let Some(format) = metrics_format {
+        if let Err(err) = motd::render_metrics(format, cli.profile.into(), &merged_cfg) {
+            eprintln!("Failed to format metrics: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if cli.quiet {
+        motd::render_quiet(cli.profile.into(), &merged_cfg, &render_ctx);
+        return;
+    }
 
     motd::render(cli.verbose, cli.profile.into(), &merged_cfg, &render_ctx);
 }
 
+#[cfg(feature = "full")]
+fn run_check_update() {
+    match motd::check_for_update() {
+        Ok(check) if check.update_available() => println!(
+            "motdyn {} is available (running {}): {}",
+            check.latest,
+            check.current,
+            motd::RELEASES_PAGE_URL
+        ),
+        Ok(check) => println!(
+            "motdyn {} is up to date (latest release {})",
+            check.current, check.latest
+        ),
+        Err(err) => {
+            eprintln!("Update check failed: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
 #[cfg(feature = "full")]
 fn run_motd_safely(cli: &Cli) {
     let previous_hook = std::panic::take_hook();
@@ -468,6 +1109,18 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "install-hooks")]
+    #[test]
+    fn osarg_parses_system_install_methods() {
+        match parse_run(&["install", "--method", "sshrc"]).cmd {
+            Some(Commands::Install(args)) => {
+                assert_eq!(args.method, Some(installer::SystemInstallMethod::Sshrc));
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+        assert!(parse_cli_from_args(["uninstall", "--method", "cron"]).is_err());
+    }
+
     #[cfg(feature = "full")]
     #[test]
     fn osarg_parses_profile_values() {
@@ -478,6 +1131,270 @@ mod tests {
         assert_eq!(cli.profile, ProfileArg::Full);
     }
 
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_accepts_art_only_alias() {
+        assert!(parse_run(&["--welcome-only"]).welcome_only);
+        assert!(parse_run(&["--art-only"]).welcome_only);
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_accepts_quiet_and_minimal() {
+        assert!(parse_run(&["-q"]).quiet);
+        assert!(parse_run(&["--quiet"]).quiet);
+        assert!(parse_run(&["--minimal"]).quiet);
+        assert!(!parse_run(&[]).quiet);
+        assert!(parse_run(&["--check"]).check);
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_check_update_command() {
+        assert!(parse_run(&["check-update"]).check_update);
+        assert!(!parse_run(&[]).check_update);
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_preview_command() {
+        let cli = parse_run(&["preview", "--config", "/tmp/theme.toml", "--compact"]);
+        assert!(cli.preview);
+        assert!(cli.compact);
+        assert_eq!(cli.config, Some(PathBuf::from("/tmp/theme.toml")));
+        assert!(!parse_run(&[]).preview);
+        assert!(parse_cli_from_args(["preview", "preview"]).is_err());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_generate_command() {
+        assert_eq!(
+            parse_run(&["generate"]).generate,
+            Some(PathBuf::from("/etc/motd"))
+        );
+        let cli = parse_run(&[
+            "generate",
+            "--output",
+            "/run/motd.dynamic",
+            "--profile",
+            "full",
+        ]);
+        assert_eq!(cli.generate, Some(PathBuf::from("/run/motd.dynamic")));
+        assert_eq!(cli.profile, ProfileArg::Full);
+        assert_eq!(parse_run(&[]).generate, None);
+        assert!(matches!(
+            parse_cli_from_args(["generate", "--help"]),
+            Ok(CliAction::Help(_))
+        ));
+        assert!(parse_cli_from_args(["generate", "--compact"]).is_err());
+        assert!(parse_cli_from_args(["generate", "extra"]).is_err());
+    }
+
+    #[test]
+    fn osarg_parses_completions_command() {
+        assert_eq!(
+            parse_run(&["completions", "zsh"]).completions,
+            Some(completions::Shell::Zsh)
+        );
+        assert_eq!(parse_run(&[]).completions, None);
+        assert!(matches!(
+            parse_cli_from_args(["completions", "--help"]),
+            Ok(CliAction::Help(_))
+        ));
+        assert!(parse_cli_from_args(["completions"]).is_err());
+        assert!(parse_cli_from_args(["completions", "tcsh"]).is_err());
+        assert!(parse_cli_from_args(["completions", "bash", "fish"]).is_err());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_config_validate_command() {
+        assert!(parse_run(&["config", "validate"]).validate_config);
+        assert!(!parse_run(&[]).validate_config);
+        assert!(matches!(
+            parse_cli_from_args(["config", "validate", "--help"]),
+            Ok(CliAction::Help(_))
+        ));
+        assert!(parse_cli_from_args(["config"]).is_err());
+        assert!(parse_cli_from_args(["config", "check"]).is_err());
+        assert!(parse_cli_from_args(["config", "validate", "validate"]).is_err());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_config_path_anywhere() {
+        assert_eq!(
+            parse_run(&["--config", "/tmp/motd.toml"]).config,
+            Some(PathBuf::from("/tmp/motd.toml"))
+        );
+        assert_eq!(
+            parse_run(&["config", "validate", "--config=/tmp/motd.toml"]).config,
+            Some(PathBuf::from("/tmp/motd.toml"))
+        );
+        assert_eq!(parse_run(&[]).config, None);
+        assert!(parse_cli_from_args(["--config"]).is_err());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_width_override() {
+        assert_eq!(parse_run(&["--width", "60"]).width, Some(60));
+        assert_eq!(parse_run(&[]).width, None);
+        assert!(parse_cli_from_args(["--width", "0"]).is_err());
+        assert!(parse_cli_from_args(["--width", "wide"]).is_err());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn config_paths_skips_the_system_config_for_an_explicit_file() {
+        let explicit = Path::new("/tmp/motd.toml");
+        assert_eq!(config_paths(Some(explicit)), (None, explicit.to_path_buf()));
+
+        let (system, user) = config_paths(None);
+        assert_eq!(system, Some(PathBuf::from(SYSTEM_CONFIG_PATH)));
+        assert!(user.ends_with(".config/motdyn/config.toml"));
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn effective_config_layers_host_session_and_env_overrides() {
+        let loaded = |config: MotdConfig, keys: &str| LoadedConfig {
+            config: Some(config),
+            status: config::ConfigLoadStatus::Loaded,
+            warnings: Vec::new(),
+            keys: config::ConfigKeys::from_table(&keys.parse().unwrap()),
+        };
+        let sys_cfg = MotdConfig {
+            farewell: Some("system".into()),
+            welcome: Some("system".into()),
+            hosts: Some(
+                [(
+                    "web-*".to_string(),
+                    MotdConfig {
+                        header: Some("web".into()),
+                        ..MotdConfig::default()
+                    },
+                )]
+                .into(),
+            ),
+            ..MotdConfig::default()
+        };
+        let usr_cfg = MotdConfig {
+            ssh: Some(Box::new(MotdConfig {
+                farewell: Some("ssh".into()),
+                welcome: Some("ssh".into()),
+                ..MotdConfig::default()
+            })),
+            ..MotdConfig::default()
+        };
+        let env_cfg = MotdConfig {
+            farewell: Some("env".into()),
+            ..MotdConfig::default()
+        };
+
+        let (cfg, origins) = effective_config(
+            Some(loaded(
+                sys_cfg,
+                "farewell = \"system\"\nwelcome = \"system\"\n[hosts.\"web-*\"]\nheader = \"web\"\n[hosts.db]\ntemplate = \"db\"\n",
+            )),
+            loaded(
+                usr_cfg,
+                "[ssh]\nfarewell = \"ssh\"\nwelcome = \"ssh\"\n[local]\nheader = \"local\"\n",
+            ),
+            loaded(env_cfg, "farewell = \"env\"\n"),
+            SessionKind::Ssh,
+            "web-01",
+        );
+        assert_eq!(cfg.farewell.as_deref(), Some("env"));
+        assert_eq!(cfg.welcome.as_deref(), Some("ssh"));
+        assert_eq!(cfg.header.as_deref(), Some("web"));
+        assert_eq!(
+            origins,
+            vec!["farewell (env)", "header (system)", "welcome (user [ssh])"]
+        );
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_format_values() {
+        let cli = parse_run(&["--format", "ansi-safe"]);
+        assert_eq!(cli.format, FormatArg::AnsiSafe);
+
+        assert_eq!(parse_run(&["--format", "toml"]).format, FormatArg::Toml);
+        assert_eq!(parse_run(&["--format", "json"]).format, FormatArg::Json);
+        assert_eq!(parse_run(&["--json"]).format, FormatArg::Json);
+        assert_eq!(parse_run(&["--json"]).json_style, JsonStyleArg::Raw);
+        assert_eq!(
+            parse_run(&["--json", "--json-style", "human"]).json_style,
+            JsonStyleArg::Human
+        );
+        assert!(parse_cli_from_args(["--json-style", "pretty"]).is_err());
+        assert!(parse_cli_from_args(["--format", "yaml"]).is_err());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn osarg_parses_color_choices() {
+        assert_eq!(parse_run(&[]).color, ColorArg::Auto);
+        assert_eq!(parse_run(&["--color", "always"]).color, ColorArg::Always);
+        assert_eq!(parse_run(&["--no-color"]).color, ColorArg::Never);
+        assert!(parse_cli_from_args(["--color", "sometimes"]).is_err());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn color_choice_honors_no_color_and_terminal_detection() {
+        assert!(ColorArg::Auto.enabled(None, true));
+        assert!(ColorArg::Auto.enabled(Some(OsString::new()), true));
+        assert!(!ColorArg::Auto.enabled(Some("1".into()), true));
+        assert!(!ColorArg::Auto.enabled(None, false));
+        assert!(ColorArg::Always.enabled(Some("1".into()), false));
+        assert!(!ColorArg::Never.enabled(None, true));
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn banner_is_held_back_for_automation_unless_forced() {
+        assert!(interactive_session(true, None));
+        assert!(interactive_session(true, Some(OsString::new())));
+        assert!(!interactive_session(false, None));
+        assert!(!interactive_session(true, Some("rsync --server .".into())));
+
+        assert!(prints_login_banner(&parse_run(&[])));
+        assert!(!prints_login_banner(&parse_run(&["--quiet"])));
+        assert!(!prints_login_banner(&parse_run(&["--force"])));
+        assert!(!prints_login_banner(&parse_run(&["--check"])));
+        assert!(!prints_login_banner(&parse_run(&["--json"])));
+        assert!(!prints_login_banner(&parse_run(&["preview"])));
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn scripting_outputs_run_without_a_terminal() {
+        for args in [
+            &["--welcome-only"][..],
+            &["--quiet"],
+            &["-q"],
+            &["--format", "ansi-safe"],
+        ] {
+            let cli = parse_run(args);
+            assert!(should_run(&cli, false, None), "{args:?}");
+            assert!(
+                should_run(&cli, false, Some("rsync --server .".into())),
+                "{args:?}"
+            );
+        }
+
+        assert!(!should_run(&parse_run(&[]), false, None));
+        assert!(!should_run(
+            &parse_run(&["--format", "default"]),
+            false,
+            None
+        ));
+        assert!(should_run(&parse_run(&[]), true, None));
+    }
+
     #[cfg(all(feature = "full", feature = "install-hooks"))]
     #[test]
     fn osarg_parses_global_options_after_subcommand() {
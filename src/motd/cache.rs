@@ -0,0 +1,183 @@
+use std::env;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+
+use crate::config::MotdConfig;
+
+use super::probe::{read_probe_cache, write_probe_cache};
+use super::types::{ModuleKind, RenderedItem, ViewerRole};
+
+/// Root's cache; `/run` is a tmpfs, so a reboot clears it.
+const ROOT_OUTPUT_CACHE_PATH: &str = "/run/motdyn/cache";
+const BOOT_ID_PATH: &str = "/proc/sys/kernel/random/boot_id";
+
+/// Rendered items of the modules that do not change per login, plus the
+/// health alerts found alongside them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct CachedOutput {
+    pub(super) modules: Vec<(ModuleKind, Vec<RenderedItem>)>,
+    pub(super) alerts: Vec<String>,
+}
+
+impl CachedOutput {
+    /// Puts freshly rendered per-session modules back between the cached
+    /// ones, in `modules` order.
+    pub(super) fn merge(
+        &self,
+        modules: &[ModuleKind],
+        mut fresh: Vec<(ModuleKind, Vec<RenderedItem>)>,
+    ) -> Vec<(ModuleKind, Vec<RenderedItem>)> {
+        modules
+            .iter()
+            .filter_map(|module| {
+                let take = |groups: &mut Vec<(ModuleKind, Vec<RenderedItem>)>| {
+                    let index = groups.iter().position(|(kind, _)| kind == module)?;
+                    Some(groups.remove(index))
+                };
+                take(&mut fresh).or_else(|| {
+                    self.modules
+                        .iter()
+                        .find(|(kind, _)| kind == module)
+                        .cloned()
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct OutputCache {
+    pub(super) path: PathBuf,
+    pub(super) ttl_secs: u64,
+    pub(super) key: String,
+    pub(super) boot_id: String,
+}
+
+impl OutputCache {
+    /// `None` unless `cache_ttl_secs` is set and the viewer has a runtime
+    /// directory. The key covers the config, the module list and whether
+    /// colors are on, so any change there misses the cache.
+    pub(super) fn resolve(
+        cfg: &MotdConfig,
+        role: ViewerRole,
+        modules: &[ModuleKind],
+        colored: bool,
+    ) -> Option<Self> {
+        let ttl_secs = cfg.cache_ttl_secs.filter(|ttl| *ttl > 0)?;
+        let path = match role {
+            ViewerRole::Root => PathBuf::from(ROOT_OUTPUT_CACHE_PATH),
+            ViewerRole::User => PathBuf::from(env::var_os("XDG_RUNTIME_DIR")?).join("motdyn/cache"),
+        };
+        let mut hasher = DefaultHasher::new();
+        format!("{:?}", cfg).hash(&mut hasher);
+        modules.hash(&mut hasher);
+        colored.hash(&mut hasher);
+        Some(Self {
+            path,
+            ttl_secs,
+            key: format!("{:016x}", hasher.finish()),
+            boot_id: fs::read_to_string(BOOT_ID_PATH)
+                .map(|id| id.trim().to_string())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// The cached output when it is fresh, written by this boot with the
+    /// same key, and covers every cacheable module in `modules`.
+    pub(super) fn load(&self, modules: &[ModuleKind]) -> Option<CachedOutput> {
+        let body = read_probe_cache(&self.path, self.ttl_secs)?;
+        let cached = parse_cached_output(&body, &self.key, &self.boot_id, modules)?;
+        modules
+            .iter()
+            .filter(|module| !module.is_per_session())
+            .all(|module| cached.modules.iter().any(|(kind, _)| kind == module))
+            .then_some(cached)
+    }
+
+    pub(super) fn store(&self, output: &CachedOutput) -> Result<(), String> {
+        write_probe_cache(
+            &self.path,
+            &format_cached_output(output, &self.key, &self.boot_id),
+        )
+    }
+}
+
+/// One `key=value` record per line; labels and values are escaped so tabs
+/// and newlines inside them survive the round trip.
+pub(super) fn format_cached_output(output: &CachedOutput, key: &str, boot_id: &str) -> String {
+    let mut body = format!("key={}\nboot_id={}\n", key, boot_id);
+    for (module, items) in &output.modules {
+        body.push_str(&format!("module={}\n", module.key()));
+        for item in items {
+            body.push_str(&format!(
+                "item={}\t{}\n",
+                escape_field(&item.label),
+                escape_field(&item.value)
+            ));
+        }
+    }
+    for alert in &output.alerts {
+        body.push_str(&format!("alert={}\n", escape_field(alert)));
+    }
+    body
+}
+
+/// `None` when the key or boot id differs or any record is malformed.
+pub(super) fn parse_cached_output(
+    body: &str,
+    key: &str,
+    boot_id: &str,
+    modules: &[ModuleKind],
+) -> Option<CachedOutput> {
+    let mut lines = body.lines();
+    if lines.next()?.strip_prefix("key=")? != key
+        || lines.next()?.strip_prefix("boot_id=")? != boot_id
+    {
+        return None;
+    }
+    let mut output = CachedOutput::default();
+    for line in lines {
+        let (name, value) = line.split_once('=')?;
+        match name {
+            "module" => {
+                let module = modules.iter().find(|module| module.key() == value)?;
+                output.modules.push((*module, Vec::new()));
+            }
+            "item" => {
+                let (label, value) = value.split_once('\t')?;
+                output.modules.last_mut()?.1.push(RenderedItem {
+                    label: unescape_field(label),
+                    value: unescape_field(value),
+                });
+            }
+            "alert" => output.alerts.push(unescape_field(value)),
+            _ => return None,
+        }
+    }
+    Some(output)
+}
+
+fn escape_field(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape_field(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
@@ -0,0 +1,116 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, NaiveDate, Utc};
+
+const PEM_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const PEM_END: &str = "-----END CERTIFICATE-----";
+const DER_SEQUENCE: u8 = 0x30;
+const DER_EXPLICIT_VERSION: u8 = 0xa0;
+const DER_UTC_TIME: u8 = 0x17;
+const DER_GENERALIZED_TIME: u8 = 0x18;
+
+/// Reads the `notAfter` field from the first certificate in a PEM or DER blob.
+pub(super) fn parse_certificate_not_after(bytes: &[u8]) -> Result<DateTime<Utc>, String> {
+    let der = decode_certificate_bytes(bytes)?;
+    let (certificate, _) = read_sequence(&der)?;
+    let (tbs_certificate, _) = read_sequence(certificate)?;
+
+    let mut rest = tbs_certificate;
+    if rest.first() == Some(&DER_EXPLICIT_VERSION) {
+        rest = read_element(rest)?.2;
+    }
+    // serialNumber, signature and issuer precede the validity sequence.
+    for _ in 0..3 {
+        rest = read_element(rest)?.2;
+    }
+
+    let (validity, _) = read_sequence(rest)?;
+    let (_, _, after_not_before) = read_element(validity)?;
+    let (tag, not_after, _) = read_element(after_not_before)?;
+    parse_der_time(tag, not_after)
+}
+
+fn decode_certificate_bytes(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    if bytes.first() == Some(&DER_SEQUENCE) {
+        return Ok(bytes.to_vec());
+    }
+
+    let text = String::from_utf8_lossy(bytes);
+    let start = text
+        .find(PEM_BEGIN)
+        .ok_or_else(|| "no PEM certificate found".to_string())?
+        + PEM_BEGIN.len();
+    let end = text[start..]
+        .find(PEM_END)
+        .ok_or_else(|| "unterminated PEM certificate".to_string())?
+        + start;
+    let body = text[start..end]
+        .chars()
+        .filter(|ch| !ch.is_ascii_whitespace())
+        .collect::<String>();
+
+    STANDARD
+        .decode(body)
+        .map_err(|err| format!("invalid PEM body: {}", err))
+}
+
+fn read_sequence(input: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    match read_element(input)? {
+        (DER_SEQUENCE, content, rest) => Ok((content, rest)),
+        (tag, _, _) => Err(format!("expected DER sequence, found tag 0x{:02x}", tag)),
+    }
+}
+
+fn read_element(input: &[u8]) -> Result<(u8, &[u8], &[u8]), String> {
+    let truncated = || "truncated DER data".to_string();
+    let (&tag, rest) = input.split_first().ok_or_else(truncated)?;
+    let (&first, mut rest) = rest.split_first().ok_or_else(truncated)?;
+
+    let len = if first < 0x80 {
+        usize::from(first)
+    } else {
+        let width = usize::from(first & 0x7f);
+        if width == 0 || width > 4 || rest.len() < width {
+            return Err("unsupported DER length".to_string());
+        }
+        let (len_bytes, after_len) = rest.split_at(width);
+        rest = after_len;
+        len_bytes
+            .iter()
+            .fold(0usize, |acc, byte| (acc << 8) | usize::from(*byte))
+    };
+
+    if rest.len() < len {
+        return Err(truncated());
+    }
+    let (content, rest) = rest.split_at(len);
+    Ok((tag, content, rest))
+}
+
+fn parse_der_time(tag: u8, content: &[u8]) -> Result<DateTime<Utc>, String> {
+    let value = std::str::from_utf8(content).map_err(|_| "invalid notAfter encoding")?;
+    let digits = value
+        .strip_suffix('Z')
+        .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_digit()))
+        .ok_or_else(|| format!("unsupported notAfter value '{}'", value))?;
+
+    let (year, rest) = match (tag, digits.len()) {
+        (DER_UTC_TIME, 12) => {
+            let year = digits[..2].parse::<i32>().unwrap_or_default();
+            (
+                if year >= 50 { 1900 + year } else { 2000 + year },
+                &digits[2..],
+            )
+        }
+        (DER_GENERALIZED_TIME, 14) => {
+            (digits[..4].parse::<i32>().unwrap_or_default(), &digits[4..])
+        }
+        _ => return Err(format!("unsupported notAfter value '{}'", value)),
+    };
+    let field = |index: usize| rest[index..index + 2].parse::<u32>().unwrap_or_default();
+
+    NaiveDate::from_ymd_opt(year, field(0), field(2))
+        .and_then(|date| date.and_hms_opt(field(4), field(6), field(8)))
+        .map(|datetime| datetime.and_utc())
+        .ok_or_else(|| format!("invalid notAfter value '{}'", value))
+}
@@ -0,0 +1,163 @@
+use super::render::threshold_paint_kind;
+use super::types::{ModuleKind, OutputSettings, PaintKind, SystemSnapshot, Threshold};
+
+/// What `--check` probes, whatever the `modules` list says.
+pub(super) const CHECK_MODULES: &[ModuleKind] = &[
+    ModuleKind::Load,
+    ModuleKind::Cpu,
+    ModuleKind::Memory,
+    ModuleKind::Swap,
+    ModuleKind::Disk,
+    ModuleKind::Temperature,
+];
+
+/// Nagios plugin states, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum CheckStatus {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl CheckStatus {
+    pub(super) fn exit_code(self) -> i32 {
+        match self {
+            Self::Ok => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Warning => "WARNING",
+            Self::Critical => "CRITICAL",
+        }
+    }
+
+    /// Judged as printed with `decimals`, like the banner colors.
+    fn judge(value: f64, decimals: usize, threshold: Threshold) -> Self {
+        match threshold_paint_kind(value, decimals, threshold) {
+            Some(PaintKind::Red) => Self::Critical,
+            Some(_) => Self::Warning,
+            None => Self::Ok,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CheckResult {
+    pub(super) status: CheckStatus,
+    pub(super) message: String,
+}
+
+/// One result per measured figure; sensors or swap the host lacks are not
+/// checked at all.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct CheckReport {
+    pub(super) results: Vec<CheckResult>,
+}
+
+impl CheckReport {
+    pub(super) fn from_snapshot(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Self {
+        let thresholds = settings.thresholds;
+        let mut report = Self::default();
+        let percent = |value: f64| format!("{:.0}%", value);
+
+        if snapshot.memory.total_gb > 0.0 {
+            report.push(
+                format!("memory {:.0}%", snapshot.memory.ratio),
+                CheckStatus::judge(snapshot.memory.ratio, 0, thresholds.memory),
+                thresholds.memory,
+                percent,
+            );
+        }
+        if snapshot.swap.total_gb > 0.0 {
+            report.push(
+                format!("swap {:.0}%", snapshot.swap.ratio),
+                CheckStatus::judge(snapshot.swap.ratio, 0, thresholds.swap),
+                thresholds.swap,
+                percent,
+            );
+        }
+        for disk in &snapshot.disk_items {
+            if disk.timed_out || !settings.disk_alerts_enabled(&disk.mount_path) {
+                continue;
+            }
+            report.push(
+                format!("disk {} {:.0}%", disk.mount_path, disk.ratio),
+                CheckStatus::judge(disk.ratio, 0, thresholds.disk),
+                thresholds.disk,
+                percent,
+            );
+        }
+        let cores = snapshot.cpu_online.unwrap_or(snapshot.cpu_count);
+        if let Some(load) = snapshot
+            .load_average
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse::<f64>().ok())
+            && cores > 0
+        {
+            report.push(
+                format!("load {:.2} on {} cores", load, cores),
+                CheckStatus::judge(load / cores as f64, 2, thresholds.load),
+                thresholds.load,
+                |value| format!("{:.2}/core", value),
+            );
+        }
+        if let Some(celsius) = snapshot.cpu_temperature {
+            report.push(
+                format!("temp {}", settings.temperature_unit.format(celsius)),
+                CheckStatus::judge(celsius, 1, thresholds.temperature),
+                thresholds.temperature,
+                |value| settings.temperature_unit.format(value),
+            );
+        }
+        report
+    }
+
+    /// The worst status of any check; `Ok` when nothing was measured.
+    pub(super) fn status(&self) -> CheckStatus {
+        self.results
+            .iter()
+            .map(|result| result.status)
+            .max()
+            .unwrap_or(CheckStatus::Ok)
+    }
+
+    /// `MOTDYN CRITICAL - disk / 96% (crit 90%), memory 80% (warn 75%)`,
+    /// naming only the failed checks.
+    pub(super) fn summary(&self) -> String {
+        let status = self.status();
+        if status == CheckStatus::Ok {
+            return format!(
+                "MOTDYN OK - {} checks within thresholds",
+                self.results.len()
+            );
+        }
+        let failed = self
+            .results
+            .iter()
+            .filter(|result| result.status != CheckStatus::Ok)
+            .map(|result| result.message.as_str())
+            .collect::<Vec<_>>();
+        format!("MOTDYN {} - {}", status.label(), failed.join(", "))
+    }
+
+    fn push(
+        &mut self,
+        measured: String,
+        status: CheckStatus,
+        threshold: Threshold,
+        limit: impl Fn(f64) -> String,
+    ) {
+        let message = match status {
+            CheckStatus::Ok => measured,
+            CheckStatus::Warning => format!("{} (warn {})", measured, limit(threshold.warn)),
+            CheckStatus::Critical => format!("{} (crit {})", measured, limit(threshold.crit)),
+        };
+        self.results.push(CheckResult { status, message });
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(feature = "sysinfo")]
+use std::collections::HashSet;
+#[cfg(feature = "sysinfo")]
+use std::time::Duration;
+
+use super::probe::{collect_disk_usage_items, parse_cpuinfo, parse_meminfo, read_uptime_secs};
+#[cfg(feature = "sysinfo")]
+use super::probe::{disk_mount_label, run_with_deadline, summarize_disk_usage};
+use super::types::{DiskMountSelection, DiskUsageItem, SnapshotDiagnostics, UsageSummary};
+
+/// The host figures every banner, `--check` and `--json` start from: CPU,
+/// memory, uptime and disk usage. `collect_snapshot` reads them through
+/// this trait, so another backend plugs in without touching the probes
+/// or renderers built on top.
+pub(super) trait HostCollector {
+    fn uptime_secs(&self) -> Option<u64>;
+
+    /// CPU brand, logical CPU count and physical core count when known.
+    fn cpu_info(&self) -> (String, usize, Option<usize>);
+
+    /// Memory total and available, then swap total and free, in kB.
+    fn memory_kb(&self) -> (u64, u64, u64, u64);
+
+    /// The root filesystem summary and one item per selected mount; a mount
+    /// that does not answer within `timeout_ms` is marked as timed out.
+    fn disks(
+        &self,
+        selection: &DiskMountSelection,
+        timeout_ms: u64,
+        diagnostics: &mut SnapshotDiagnostics,
+    ) -> (Option<UsageSummary>, Vec<DiskUsageItem>);
+}
+
+/// `/proc` and `statvfs` on Linux, `sysctl` on macOS. Still compiled under
+/// `sysinfo`, where nothing selects it, so the native parsers keep building.
+#[cfg_attr(feature = "sysinfo", allow(dead_code))]
+pub(super) struct NativeCollector;
+
+#[cfg_attr(feature = "sysinfo", allow(dead_code))]
+impl HostCollector for NativeCollector {
+    fn uptime_secs(&self) -> Option<u64> {
+        read_uptime_secs()
+    }
+
+    fn cpu_info(&self) -> (String, usize, Option<usize>) {
+        parse_cpuinfo()
+    }
+
+    fn memory_kb(&self) -> (u64, u64, u64, u64) {
+        parse_meminfo()
+    }
+
+    fn disks(
+        &self,
+        selection: &DiskMountSelection,
+        timeout_ms: u64,
+        diagnostics: &mut SnapshotDiagnostics,
+    ) -> (Option<UsageSummary>, Vec<DiskUsageItem>) {
+        collect_disk_usage_items(selection, timeout_ms, diagnostics)
+    }
+}
+
+/// The `sysinfo` crate, for platforms the native parsers do not cover.
+#[cfg(feature = "sysinfo")]
+pub(super) struct SysinfoCollector;
+
+#[cfg(feature = "sysinfo")]
+impl HostCollector for SysinfoCollector {
+    fn uptime_secs(&self) -> Option<u64> {
+        Some(sysinfo::System::uptime()).filter(|secs| *secs > 0)
+    }
+
+    fn cpu_info(&self) -> (String, usize, Option<usize>) {
+        let system = sysinfo::System::new_with_specifics(
+            sysinfo::RefreshKind::nothing().with_cpu(sysinfo::CpuRefreshKind::nothing()),
+        );
+        let brand = system
+            .cpus()
+            .first()
+            .map(|cpu| cpu.brand().trim())
+            .filter(|brand| !brand.is_empty())
+            .unwrap_or("Unknown CPU")
+            .to_string();
+        (
+            brand,
+            system.cpus().len(),
+            sysinfo::System::physical_core_count(),
+        )
+    }
+
+    fn memory_kb(&self) -> (u64, u64, u64, u64) {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        (
+            system.total_memory() / 1024,
+            system.available_memory() / 1024,
+            system.total_swap() / 1024,
+            system.free_swap() / 1024,
+        )
+    }
+
+    fn disks(
+        &self,
+        selection: &DiskMountSelection,
+        timeout_ms: u64,
+        diagnostics: &mut SnapshotDiagnostics,
+    ) -> (Option<UsageSummary>, Vec<DiskUsageItem>) {
+        // Listing reads only the mount table; the sizes are fetched per disk
+        // below, so a hung NFS mount is held to the same deadline as natively.
+        let disks =
+            sysinfo::Disks::new_with_refreshed_list_specifics(sysinfo::DiskRefreshKind::nothing());
+        let mut seen_sources = HashSet::new();
+        let mut seen_paths = HashSet::new();
+        let mut mounts = Vec::new();
+        let mut selected = Vec::new();
+        for disk in Vec::from(disks) {
+            let source = disk.name().to_string_lossy().into_owned();
+            let mount_path = disk.mount_point().to_string_lossy().into_owned();
+            let fstype = disk.file_system().to_string_lossy().into_owned();
+            let Some(label) = disk_mount_label(&source, &mount_path, &fstype, selection) else {
+                continue;
+            };
+            if !seen_sources.insert(source) || !seen_paths.insert(mount_path.clone()) {
+                continue;
+            }
+            mounts.push((mount_path, fstype, label));
+            selected.push(disk);
+        }
+
+        let jobs = selected
+            .into_iter()
+            .map(|mut disk| {
+                move || {
+                    disk.refresh_specifics(sysinfo::DiskRefreshKind::nothing().with_storage());
+                    let total_bytes = disk.total_space();
+                    (total_bytes > 0).then(|| {
+                        (
+                            total_bytes,
+                            total_bytes.saturating_sub(disk.available_space()),
+                        )
+                    })
+                }
+            })
+            .collect();
+        let usages = run_with_deadline(jobs, Duration::from_millis(timeout_ms));
+        summarize_disk_usage(mounts, usages, timeout_ms, diagnostics)
+    }
+}
+
+/// The backend this build reads host figures from: the native parsers, or
+/// `sysinfo` when that feature is enabled.
+pub(super) fn host_collector() -> &'static dyn HostCollector {
+    #[cfg(feature = "sysinfo")]
+    {
+        &SysinfoCollector
+    }
+    #[cfg(not(feature = "sysinfo"))]
+    {
+        &NativeCollector
+    }
+}
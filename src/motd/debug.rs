@@ -0,0 +1,59 @@
+use std::env;
+use std::fmt::{self, Debug};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns on stderr debug logs when `--debug` is passed or `RUST_LOG` asks
+/// for debug output from the `motdyn` target.
+pub fn init(flag: bool) {
+    let from_env = env::var("RUST_LOG")
+        .map(|value| rust_log_enables_debug(&value))
+        .unwrap_or(false);
+    ENABLED.store(flag || from_env, Ordering::Relaxed);
+}
+
+pub(super) fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub(super) fn log(args: fmt::Arguments<'_>) {
+    if enabled() {
+        eprintln!("[motdyn debug] {}", args);
+    }
+}
+
+/// Runs a collector and logs how long it took along with what it returned.
+pub(super) fn timed<T: Debug>(collector: &str, probe: impl FnOnce() -> T) -> T {
+    if !enabled() {
+        return probe();
+    }
+
+    let started = Instant::now();
+    let value = probe();
+    log(format_args!(
+        "collector={} elapsed={:.1}ms result={:?}",
+        collector,
+        started.elapsed().as_secs_f64() * 1000.0,
+        value
+    ));
+    value
+}
+
+/// Accepts only directives naming the `motdyn` target, `motdyn`,
+/// `motdyn=debug` or `motdyn=trace`, so a global `RUST_LOG=debug` exported
+/// for another tool does not print collector timings at every login. Only
+/// that on/off switch is read: there are no per-module targets, span
+/// filters or other levels.
+pub(super) fn rust_log_enables_debug(value: &str) -> bool {
+    value.split(',').map(str::trim).any(|directive| {
+        let (target, level) = directive.split_once('=').unwrap_or((directive, "trace"));
+        let target = target.trim();
+        (target == "motdyn" || target.starts_with("motdyn::"))
+            && matches!(
+                level.trim().to_ascii_lowercase().as_str(),
+                "debug" | "trace"
+            )
+    })
+}
@@ -0,0 +1,165 @@
+use super::render::format_gb;
+use super::types::{ModuleKind, OutputSettings, SystemSnapshot, UsageSummary};
+
+/// Output of a config `template` with its `{placeholders}` filled in.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct RenderedLayout {
+    pub(super) text: String,
+    pub(super) unknown: Vec<String>,
+}
+
+/// Every placeholder a layout template may use and the module that feeds it.
+const PLACEHOLDERS: &[(&str, ModuleKind)] = &[
+    ("hostname", ModuleKind::Host),
+    ("ip", ModuleKind::Network),
+    ("iface", ModuleKind::Network),
+    ("user", ModuleKind::User),
+    ("time", ModuleKind::Time),
+    ("uptime", ModuleKind::Uptime),
+    ("load", ModuleKind::Load),
+    ("os", ModuleKind::Os),
+    ("kernel", ModuleKind::Kernel),
+    ("virtualization", ModuleKind::Virtualization),
+    ("cpu", ModuleKind::Cpu),
+    ("cpu_cores", ModuleKind::Cpu),
+    ("cpu_threads", ModuleKind::Cpu),
+    ("mem_used", ModuleKind::Memory),
+    ("mem_total", ModuleKind::Memory),
+    ("mem_percent", ModuleKind::Memory),
+    ("swap_used", ModuleKind::Swap),
+    ("swap_total", ModuleKind::Swap),
+    ("swap_percent", ModuleKind::Swap),
+    ("disk_used", ModuleKind::Disk),
+    ("disk_total", ModuleKind::Disk),
+    ("disk_percent", ModuleKind::Disk),
+    ("updates", ModuleKind::Updates),
+];
+
+/// The modules that must be probed to fill the placeholders in `template`.
+pub(super) fn layout_modules(template: &str) -> Vec<ModuleKind> {
+    let mut modules = Vec::new();
+    for segment in parse_layout(template) {
+        if let Segment::Placeholder(name) = segment
+            && let Some((_, module)) = PLACEHOLDERS.iter().find(|(key, _)| *key == name)
+            && !modules.contains(module)
+        {
+            modules.push(*module);
+        }
+    }
+    modules
+}
+
+/// Substitutes `{name}` placeholders; `{{` and `}}` print literal braces and
+/// unknown placeholders are left as written.
+pub(super) fn render_layout(
+    template: &str,
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> RenderedLayout {
+    let mut layout = RenderedLayout::default();
+    for segment in parse_layout(template) {
+        match segment {
+            Segment::Text(text) => layout.text.push_str(text),
+            Segment::Placeholder(name) => match placeholder_value(name, snapshot, settings) {
+                Some(value) => layout.text.push_str(&value),
+                None => {
+                    layout.text.push('{');
+                    layout.text.push_str(name);
+                    layout.text.push('}');
+                    if !layout.unknown.iter().any(|unknown| unknown == name) {
+                        layout.unknown.push(name.to_string());
+                    }
+                }
+            },
+        }
+    }
+    layout
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+fn parse_layout(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        if index > 0 {
+            segments.push(Segment::Text(&rest[..index]));
+        }
+        let brace = &rest[index..index + 1];
+        rest = &rest[index + 1..];
+        if let Some(after) = rest.strip_prefix(brace) {
+            segments.push(Segment::Text(brace));
+            rest = after;
+            continue;
+        }
+        match (brace, rest.find('}')) {
+            ("{", Some(close)) if is_placeholder_name(&rest[..close]) => {
+                segments.push(Segment::Placeholder(&rest[..close]));
+                rest = &rest[close + 1..];
+            }
+            _ => segments.push(Segment::Text(brace)),
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    segments
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+fn placeholder_value(
+    name: &str,
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> Option<String> {
+    let usage = |summary: Option<UsageSummary>, field: fn(UsageSummary) -> String| {
+        summary.map(field).unwrap_or_default()
+    };
+    let used = |summary: UsageSummary| format_gb(summary.used_gb);
+    let total = |summary: UsageSummary| format_gb(summary.total_gb);
+    let percent = |summary: UsageSummary| format!("{:.2}%", summary.ratio);
+
+    let value = match name {
+        "hostname" => settings
+            .display_hostname
+            .clone()
+            .unwrap_or_else(|| snapshot.host_name.clone()),
+        "ip" => snapshot.main_ip.clone(),
+        "iface" => snapshot.main_iface.clone(),
+        "user" => snapshot.current_user.clone(),
+        "time" => snapshot.now_str_with_tz.clone(),
+        "uptime" => snapshot.uptime_str.clone(),
+        "load" => snapshot.load_average.clone(),
+        "os" => format!("{} {}", snapshot.os_name, snapshot.os_version),
+        "kernel" => snapshot.kernel_version.clone(),
+        "virtualization" => snapshot.virt_info.clone().unwrap_or_default(),
+        "cpu" => snapshot.cpu_brand.clone(),
+        "cpu_cores" => snapshot
+            .cpu_physical_cores
+            .unwrap_or(snapshot.cpu_count)
+            .to_string(),
+        "cpu_threads" => snapshot.cpu_count.to_string(),
+        "mem_used" => used(snapshot.memory),
+        "mem_total" => total(snapshot.memory),
+        "mem_percent" => percent(snapshot.memory),
+        "swap_used" => used(snapshot.swap),
+        "swap_total" => total(snapshot.swap),
+        "swap_percent" => percent(snapshot.swap),
+        "disk_used" => usage(snapshot.root_disk, used),
+        "disk_total" => usage(snapshot.root_disk, total),
+        "disk_percent" => usage(snapshot.root_disk, percent),
+        "updates" => snapshot.update_summary.clone(),
+        _ => return None,
+    };
+    Some(value)
+}
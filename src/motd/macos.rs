@@ -0,0 +1,154 @@
+#[cfg(target_os = "macos")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(target_os = "macos")]
+use super::probe::{parse_loadavg_content, run_command_with_timeout};
+
+#[cfg(target_os = "macos")]
+const MACOS_PROBE_TIMEOUT_MS: u64 = 150;
+
+/// Reads `sw_vers` into (product name, product version).
+#[cfg(target_os = "macos")]
+pub(super) fn get_os_info() -> ((String, String), &'static str) {
+    match command_stdout("sw_vers", &[]).and_then(|output| parse_sw_vers_output(&output)) {
+        Some(result) => (result, "sw_vers"),
+        None => (("macOS".to_string(), String::new()), "sw_vers unavailable"),
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn read_uptime_secs() -> Option<u64> {
+    let boot_secs = parse_boottime(&sysctl_value("kern.boottime")?)?;
+    let now_secs = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    now_secs.checked_sub(boot_secs)
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn parse_load_average() -> Option<String> {
+    parse_loadavg_content(sysctl_value("vm.loadavg")?.trim_matches(['{', '}', ' ']))
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn read_kernel_release() -> Option<String> {
+    sysctl_value("kern.osrelease")
+}
+
+#[cfg(target_os = "macos")]
+pub(super) fn read_host_name() -> Option<String> {
+    sysctl_value("kern.hostname")
+}
+
+/// Same shape as the `/proc/meminfo` reader: total, available, swap total
+/// and swap free, all in kB.
+#[cfg(target_os = "macos")]
+pub(super) fn parse_meminfo() -> (u64, u64, u64, u64) {
+    let mem_total = sysctl_value("hw.memsize")
+        .and_then(|value| value.parse::<u64>().ok())
+        .map_or(0, |bytes| bytes / 1024);
+    let mem_available = command_stdout("vm_stat", &[])
+        .and_then(|output| parse_vm_stat_available_kb(&output))
+        .unwrap_or(0);
+    let (swap_total, swap_free) = sysctl_value("vm.swapusage")
+        .and_then(|value| parse_swapusage(&value))
+        .unwrap_or((0, 0));
+    (mem_total, mem_available, swap_total, swap_free)
+}
+
+/// Same shape as the `/proc/cpuinfo` reader: brand, logical CPUs and
+/// physical cores.
+#[cfg(target_os = "macos")]
+pub(super) fn parse_cpuinfo() -> (String, usize, Option<usize>) {
+    let count = |name| sysctl_value(name).and_then(|value| value.parse::<usize>().ok());
+    let brand = sysctl_value("machdep.cpu.brand_string")
+        .or_else(|| sysctl_value("hw.model"))
+        .unwrap_or_else(|| "Unknown CPU".to_string());
+    (
+        brand,
+        count("hw.logicalcpu").unwrap_or(0),
+        count("hw.physicalcpu"),
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn sysctl_value(name: &str) -> Option<String> {
+    command_stdout("sysctl", &["-n", name])
+        .map(|output| output.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = run_command_with_timeout(program, args, &[], MACOS_PROBE_TIMEOUT_MS).ok()?;
+    output.status.success().then_some(output.stdout)
+}
+
+/// Parses `ProductName:` and `ProductVersion:` from `sw_vers`.
+pub(super) fn parse_sw_vers_output(output: &str) -> Option<(String, String)> {
+    let field = |key: &str| {
+        output.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.trim().to_string())
+        })
+    };
+    Some((
+        field("ProductName")?,
+        field("ProductVersion").unwrap_or_default(),
+    ))
+}
+
+/// Extracts the boot time in seconds from `kern.boottime`, which reads like
+/// `{ sec = 1712345678, usec = 123456 } Fri Apr  5 19:34:38 2024`.
+pub(super) fn parse_boottime(value: &str) -> Option<u64> {
+    let after = value.split_once("sec =")?.1;
+    after
+        .trim_start()
+        .split(|ch: char| !ch.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Reads swap total and free in kB from `vm.swapusage`, which reads like
+/// `total = 2048.00M  used = 1024.00M  free = 1024.00M  (encrypted)`.
+pub(super) fn parse_swapusage(value: &str) -> Option<(u64, u64)> {
+    let field = |key: &str| {
+        let rest = value.split_once(&format!("{} = ", key))?.1;
+        let amount = rest.split_whitespace().next()?;
+        let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+        let kb = match unit {
+            "K" => 1.0,
+            "M" => 1024.0,
+            "G" => 1024.0 * 1024.0,
+            _ => return None,
+        };
+        Some((number.parse::<f64>().ok()? * kb) as u64)
+    };
+    Some((field("total")?, field("free")?))
+}
+
+/// Counts free, inactive and speculative pages from `vm_stat` as available
+/// memory, in kB.
+pub(super) fn parse_vm_stat_available_kb(output: &str) -> Option<u64> {
+    let page_size = output
+        .lines()
+        .next()?
+        .split_once("page size of ")?
+        .1
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    let pages = output
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            matches!(
+                name.trim(),
+                "Pages free" | "Pages inactive" | "Pages speculative"
+            )
+            .then(|| value.trim().trim_end_matches('.').parse::<u64>().ok())
+            .flatten()
+        })
+        .sum::<u64>();
+    Some(pages * page_size / 1024)
+}
@@ -0,0 +1,356 @@
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+use super::probe::format_uptime;
+use super::render::format_bytes;
+use super::types::{BYTES_PER_GB, DiskUsageItem, SystemSnapshot, UsageSummary};
+
+/// Machine-readable output formats for the collected metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    Toml,
+    Json(JsonStyle),
+}
+
+/// Which fields `--json` writes: `raw` numbers in bytes, seconds and
+/// percent, or the `human` strings the banner prints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonStyle {
+    #[default]
+    Raw,
+    Human,
+}
+
+/// Collected metrics with raw numbers: bytes, seconds and percentages. This
+/// is the object `--json` prints.
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemMetrics {
+    pub host_name: String,
+    pub main_interface: String,
+    pub main_ipv4: String,
+    pub user: String,
+    pub source_ip: String,
+    pub logged_in_users: usize,
+    pub time: String,
+    pub uptime_secs: Option<u64>,
+    pub load_average: Vec<f64>,
+    pub os_name: String,
+    pub os_version: String,
+    pub kernel_version: String,
+    pub virtualization: Option<String>,
+    pub cpu: CpuMetrics,
+    pub memory: UsageMetrics,
+    pub swap: UsageMetrics,
+    pub disks: Vec<DiskMetrics>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuMetrics {
+    pub brand: String,
+    pub cores: usize,
+    pub physical_cores: Option<usize>,
+    pub online: Option<usize>,
+    pub quota: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageMetrics {
+    pub used_bytes: u64,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskMetrics {
+    pub mount: String,
+    pub fstype: String,
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+    pub percent: f64,
+}
+
+impl SystemMetrics {
+    pub(super) fn from_snapshot(snapshot: &SystemSnapshot) -> Self {
+        Self {
+            host_name: snapshot.host_name.clone(),
+            main_interface: snapshot.main_iface.clone(),
+            main_ipv4: snapshot.main_ip.clone(),
+            user: snapshot.current_user.clone(),
+            source_ip: snapshot.from_ip.clone(),
+            logged_in_users: snapshot.login_user_count,
+            time: snapshot.now_str_with_tz.clone(),
+            uptime_secs: snapshot.uptime_secs,
+            load_average: snapshot
+                .load_average
+                .split_whitespace()
+                .filter_map(|value| value.parse().ok())
+                .collect(),
+            os_name: snapshot.os_name.clone(),
+            os_version: snapshot.os_version.clone(),
+            kernel_version: snapshot.kernel_version.clone(),
+            virtualization: snapshot.virt_info.clone(),
+            cpu: CpuMetrics {
+                brand: snapshot.cpu_brand.clone(),
+                cores: snapshot.cpu_count,
+                physical_cores: snapshot.cpu_physical_cores,
+                online: snapshot.cpu_online,
+                quota: snapshot.cpu_quota,
+            },
+            memory: UsageMetrics::from_summary(snapshot.memory),
+            swap: UsageMetrics::from_summary(snapshot.swap),
+            disks: snapshot
+                .disk_items
+                .iter()
+                .filter(|disk| !disk.timed_out)
+                .map(DiskMetrics::from_item)
+                .collect(),
+        }
+    }
+
+    pub(super) fn to_json(&self, style: JsonStyle) -> Result<String, String> {
+        let json = match style {
+            JsonStyle::Raw => serde_json::to_string_pretty(self),
+            JsonStyle::Human => serde_json::to_string_pretty(&HumanMetrics::from_report(self)),
+        };
+        json.map(|json| json + "\n").map_err(|err| err.to_string())
+    }
+
+    pub(super) fn to_toml(&self) -> Result<String, String> {
+        match serde_json::to_value(self).map_err(|err| err.to_string())? {
+            Value::Object(table) => Ok(format_toml_table(&table, &[])),
+            _ => Err("metrics did not serialize to a table".to_string()),
+        }
+    }
+}
+
+/// The `--json-style human` view of [`SystemMetrics`]: `uptime` replaces
+/// `uptime_secs`, and sizes and percentages become display strings.
+#[derive(Debug, Clone, Serialize)]
+struct HumanMetrics<'a> {
+    host_name: &'a str,
+    main_interface: &'a str,
+    main_ipv4: &'a str,
+    user: &'a str,
+    source_ip: &'a str,
+    logged_in_users: usize,
+    time: &'a str,
+    uptime: Option<String>,
+    load_average: String,
+    os_name: &'a str,
+    os_version: &'a str,
+    kernel_version: &'a str,
+    virtualization: Option<&'a str>,
+    cpu: &'a CpuMetrics,
+    memory: HumanUsage,
+    swap: HumanUsage,
+    disks: Vec<HumanDisk<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct HumanUsage {
+    used: String,
+    free: String,
+    total: String,
+    percent: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct HumanDisk<'a> {
+    mount: &'a str,
+    fstype: &'a str,
+    used: String,
+    total: String,
+    percent: String,
+}
+
+impl<'a> HumanMetrics<'a> {
+    fn from_report(report: &'a SystemMetrics) -> Self {
+        Self {
+            host_name: &report.host_name,
+            main_interface: &report.main_interface,
+            main_ipv4: &report.main_ipv4,
+            user: &report.user,
+            source_ip: &report.source_ip,
+            logged_in_users: report.logged_in_users,
+            time: &report.time,
+            uptime: report.uptime_secs.map(format_uptime),
+            load_average: report
+                .load_average
+                .iter()
+                .map(|value| format!("{:.2}", value))
+                .collect::<Vec<_>>()
+                .join(" "),
+            os_name: &report.os_name,
+            os_version: &report.os_version,
+            kernel_version: &report.kernel_version,
+            virtualization: report.virtualization.as_deref(),
+            cpu: &report.cpu,
+            memory: HumanUsage::from_metrics(&report.memory),
+            swap: HumanUsage::from_metrics(&report.swap),
+            disks: report
+                .disks
+                .iter()
+                .map(|disk| HumanDisk {
+                    mount: &disk.mount,
+                    fstype: &disk.fstype,
+                    used: format_bytes(disk.used_bytes),
+                    total: format_bytes(disk.total_bytes),
+                    percent: format_percent(disk.percent),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl HumanUsage {
+    fn from_metrics(usage: &UsageMetrics) -> Self {
+        Self {
+            used: format_bytes(usage.used_bytes),
+            free: format_bytes(usage.free_bytes),
+            total: format_bytes(usage.total_bytes),
+            percent: format_percent(usage.percent),
+        }
+    }
+}
+
+fn format_percent(percent: f64) -> String {
+    format!("{:.2}%", percent)
+}
+
+impl UsageMetrics {
+    fn from_summary(summary: UsageSummary) -> Self {
+        let used_bytes = (summary.used_gb * BYTES_PER_GB).round() as u64;
+        let total_bytes = (summary.total_gb * BYTES_PER_GB).round() as u64;
+        Self {
+            used_bytes,
+            free_bytes: total_bytes.saturating_sub(used_bytes),
+            total_bytes,
+            percent: round_percent(summary.ratio),
+        }
+    }
+}
+
+impl DiskMetrics {
+    fn from_item(item: &DiskUsageItem) -> Self {
+        Self {
+            mount: item.mount_path.clone(),
+            fstype: item.fstype.clone(),
+            used_bytes: item.used_bytes,
+            total_bytes: item.total_bytes,
+            percent: round_percent(item.ratio),
+        }
+    }
+}
+
+fn round_percent(ratio: f64) -> f64 {
+    (ratio * 100.0).round() / 100.0
+}
+
+/// Writes plain keys first, then sub-tables as `[a.b]` and lists of tables as
+/// `[[a.b]]`, which is the order TOML requires. `null` values are omitted.
+fn format_toml_table(table: &Map<String, Value>, path: &[String]) -> String {
+    let mut out = String::new();
+    for (key, value) in table {
+        if is_table_like(value) || value.is_null() {
+            continue;
+        }
+        out.push_str(&format!("{} = {}\n", toml_key(key), toml_inline(value)));
+    }
+
+    for (key, value) in table {
+        let mut child_path = path.to_vec();
+        child_path.push(toml_key(key));
+        match value {
+            Value::Object(child) => {
+                out.push_str(&format!("\n[{}]\n", child_path.join(".")));
+                out.push_str(&format_toml_table(child, &child_path));
+            }
+            Value::Array(items) if is_table_like(value) => {
+                for item in items {
+                    if let Value::Object(child) = item {
+                        out.push_str(&format!("\n[[{}]]\n", child_path.join(".")));
+                        out.push_str(&format_toml_table(child, &child_path));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn is_table_like(value: &Value) -> bool {
+    match value {
+        Value::Object(_) => true,
+        Value::Array(items) => !items.is_empty() && items.iter().all(Value::is_object),
+        _ => false,
+    }
+}
+
+fn toml_inline(value: &Value) -> String {
+    match value {
+        Value::Null => "\"\"".to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Number(number) => toml_number(number),
+        Value::String(text) => toml_string(text),
+        Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .filter(|item| !item.is_null())
+                .map(toml_inline)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(table) => format!(
+            "{{ {} }}",
+            table
+                .iter()
+                .filter(|(_, item)| !item.is_null())
+                .map(|(key, item)| format!("{} = {}", toml_key(key), toml_inline(item)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// TOML floats need a fractional part or exponent, so `25` becomes `25.0`.
+fn toml_number(number: &Number) -> String {
+    let text = number.to_string();
+    if number.is_f64() && !text.contains(['.', 'e', 'E']) {
+        format!("{}.0", text)
+    } else {
+        text
+    }
+}
+
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-')
+    {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+fn toml_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => out.push_str(&format!("\\u{:04X}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -1,18 +1,50 @@
+mod cache;
+mod cert;
+mod check;
+mod collector;
+mod debug;
+mod layout;
+#[cfg(any(target_os = "macos", test))]
+mod macos;
+mod metrics;
 mod probe;
+#[cfg(feature = "remote-welcome")]
+mod release;
 mod render;
+mod schedule;
+#[cfg(unix)]
+mod syslog;
 #[cfg(test)]
 mod tests;
 mod types;
 mod welcome;
 
 use crate::config::MotdConfig;
-use probe::collect_snapshot;
+use cache::{CachedOutput, OutputCache};
+use check::{CHECK_MODULES, CheckReport};
+use chrono::Local;
+pub use debug::init as init_debug;
+use layout::{layout_modules, render_layout};
+pub use metrics::{CpuMetrics, DiskMetrics, JsonStyle, MetricsFormat, SystemMetrics, UsageMetrics};
+use probe::{collect_snapshot, read_top_memory_processes};
+#[cfg(feature = "remote-welcome")]
+pub use release::{RELEASES_PAGE_URL, check_for_update};
 use render::{
-    build_verbose_items, current_viewer_role, format_aligned_items, paint, render_module_lines,
-    resolve_modules, resolve_output_settings,
+    build_verbose_items, clip_line_to_width, collect_health_alerts, current_viewer_role,
+    format_aligned_items, format_module_lines, paint, paint_highlight, paste_safe_line,
+    paste_safe_text, render_module_item_groups, render_module_lines, render_quiet_line,
+    resolve_line_width, resolve_modules, resolve_output_settings, resolve_spacing_settings,
+    terminal_columns,
 };
-use types::{DEFAULT_FAREWELL, PaintKind};
-pub use types::{ModuleProfile, RenderContext};
+use schedule::active_scheduled_banners;
+use std::fs;
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::path::Path;
+use types::{
+    DEFAULT_FAREWELL, PaintKind, ProbeIssue, SnapshotOptions, SystemSnapshot,
+    TOP_MEMORY_PROCESS_COUNT, WelcomeResolution, WelcomeSource,
+};
+pub use types::{ModuleProfile, RenderContext, RenderMode};
 use welcome::resolve_welcome_text;
 
 pub fn render(verbose: bool, profile: ModuleProfile, cfg: &MotdConfig, ctx: &RenderContext) {
@@ -21,6 +53,167 @@ pub fn render(verbose: bool, profile: ModuleProfile, cfg: &MotdConfig, ctx: &Ren
     }
 }
 
+/// Renders the banner without colors and writes it to `path`, replacing the
+/// file in one rename so a login never reads it half-written.
+pub fn write_static(
+    path: &Path,
+    profile: ModuleProfile,
+    cfg: &MotdConfig,
+    ctx: &RenderContext,
+) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut text = build_output(false, profile, cfg, ctx).join("\n");
+    text.push('\n');
+    let staging = path.with_extension(format!("tmp{}", std::process::id()));
+    fs::write(&staging, text)
+        .and_then(|_| fs::set_permissions(&staging, fs::Permissions::from_mode(0o644)))
+        .and_then(|_| fs::rename(&staging, path))
+        .map_err(|err| {
+            let _ = fs::remove_file(&staging);
+            format!("failed to write {}: {}", path.display(), err)
+        })
+}
+
+/// Probes the modules `cfg` selects for the current user and returns the
+/// figures `--json` prints; unlike the CLI it does not record a login.
+pub fn collect_metrics(profile: ModuleProfile, cfg: &MotdConfig) -> SystemMetrics {
+    collect_metrics_with(profile, cfg, false)
+}
+
+fn collect_metrics_with(
+    profile: ModuleProfile,
+    cfg: &MotdConfig,
+    record_login: bool,
+) -> SystemMetrics {
+    let selection = resolve_modules(cfg, current_viewer_role(), profile);
+    SystemMetrics::from_snapshot(&collect_snapshot(
+        &selection.modules,
+        cfg,
+        SnapshotOptions {
+            probe_disks: true,
+            record_login,
+        },
+    ))
+}
+
+/// Prints the collected metrics in a machine-readable format, without
+/// welcome text, colors or farewell.
+pub fn render_metrics(
+    format: MetricsFormat,
+    profile: ModuleProfile,
+    cfg: &MotdConfig,
+) -> Result<(), String> {
+    let report = collect_metrics_with(profile, cfg, true);
+    let text = match format {
+        MetricsFormat::Toml => report.to_toml()?,
+        MetricsFormat::Json(style) => report.to_json(style)?,
+    };
+    print!("{}", text);
+    Ok(())
+}
+
+/// Prints a Nagios-style summary of the threshold checks and returns the
+/// exit code: 0 when all pass, 1 on a warning and 2 on a critical value.
+pub fn run_check(cfg: &MotdConfig) -> i32 {
+    let snapshot = collect_snapshot(
+        CHECK_MODULES,
+        cfg,
+        SnapshotOptions {
+            probe_disks: true,
+            record_login: false,
+        },
+    );
+    let report = CheckReport::from_snapshot(&snapshot, &resolve_output_settings(cfg));
+    println!("{}", report.summary());
+    report.status().exit_code()
+}
+
+/// The kernel host name that `[hosts."..."]` config tables are matched
+/// against; `display_hostname` does not change it.
+pub fn host_name() -> Option<String> {
+    probe::read_host_name()
+}
+
+/// Prints the `--quiet` one-liner: no welcome text, banners or farewell.
+pub fn render_quiet(profile: ModuleProfile, cfg: &MotdConfig, ctx: &RenderContext) {
+    let selection = resolve_modules(cfg, current_viewer_role(), profile);
+    let snapshot = collect_snapshot(
+        &selection.modules,
+        cfg,
+        SnapshotOptions {
+            probe_disks: true,
+            record_login: ctx.mode == RenderMode::Login,
+        },
+    );
+    log_login_event(cfg, ctx, &snapshot);
+    let line = clip_line_to_width(
+        &render_quiet_line(&selection.modules, &snapshot, &resolve_output_settings(cfg)),
+        resolve_line_width(cfg),
+    );
+    if cfg.output.ansi_safe.unwrap_or(false) {
+        println!("{}", paste_safe_line(&line));
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Prints only the configured welcome banner; nothing when none is configured.
+pub fn render_welcome_only(cfg: &MotdConfig) {
+    if let Some(text) = build_welcome_only(cfg) {
+        println!("{}", text);
+    }
+}
+
+fn build_welcome_only(cfg: &MotdConfig) -> Option<String> {
+    let welcome = resolve_welcome_text(cfg);
+    if welcome.source == WelcomeSource::Default {
+        return None;
+    }
+    let gutter = cfg.spacing.gutter.unwrap_or(0);
+    let columns = terminal_columns().map(|columns| columns.saturating_sub(gutter));
+    let text = indent_lines(vec![fit_welcome_text(cfg, welcome.text, columns)], gutter).concat();
+    if cfg.output.ansi_safe.unwrap_or(false) {
+        return Some(paste_safe_text(&text));
+    }
+    Some(text)
+}
+
+/// `show_header = false` drops the welcome block; `header` replaces the
+/// default `Welcome!`, and an empty one drops it as well.
+fn resolve_header_text(cfg: &MotdConfig, welcome: &WelcomeResolution) -> Option<String> {
+    if !cfg.show_header.unwrap_or(true) {
+        return None;
+    }
+    let text = match (&welcome.source, cfg.header.as_deref()) {
+        (WelcomeSource::Default, Some(header)) => header.to_string(),
+        _ => welcome.text.clone(),
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// Keeps at most `max_ascii_lines` welcome lines, marking the cut with `…`,
+/// then clips each line to the terminal width when `clip_ascii_art` is set
+/// and the width is known.
+fn fit_welcome_text(cfg: &MotdConfig, text: String, columns: Option<usize>) -> String {
+    let text = match cfg.output.max_ascii_lines {
+        Some(limit) if text.lines().count() > limit => {
+            let mut kept = text.lines().take(limit).collect::<Vec<_>>();
+            kept.push("…");
+            kept.join("\n")
+        }
+        _ => text,
+    };
+    match columns {
+        Some(width) if cfg.output.clip_ascii_art.unwrap_or(false) => text
+            .lines()
+            .map(|line| clip_line_to_width(line, width))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => text,
+    }
+}
+
 fn build_output(
     verbose: bool,
     profile: ModuleProfile,
@@ -28,19 +221,131 @@ fn build_output(
     ctx: &RenderContext,
 ) -> Vec<String> {
     let welcome = resolve_welcome_text(cfg);
-    let selection = resolve_modules(cfg, current_viewer_role(), profile);
-    let output = resolve_output_settings(cfg);
-    let snapshot = collect_snapshot(&selection.modules, cfg);
+    let role = current_viewer_role();
+    let mut selection = resolve_modules(cfg, role, profile);
+    if ctx.mode == RenderMode::Static {
+        selection.modules.retain(|module| !module.is_per_session());
+    }
+    let mut output = resolve_output_settings(cfg);
+    for ignored in &output.ignored_colors {
+        eprintln!("motdyn: ignoring [colors] entry: {}", ignored);
+    }
+    let spacing = resolve_spacing_settings(cfg, &output);
+    output.line_width = Some(resolve_line_width(cfg).saturating_sub(spacing.gutter));
+    let banners = active_scheduled_banners(
+        cfg.scheduled_banners.as_deref().unwrap_or_default(),
+        Local::now().naive_local(),
+    );
+    for note in &banners.skipped {
+        debug::log(format_args!("{}", note));
+    }
+    // Verbose details and templates read the whole snapshot, so only the
+    // plain module list is served from the output cache; only logins touch it.
+    let cache = if verbose || ctx.mode != RenderMode::Login || cfg.template.is_some() {
+        None
+    } else {
+        let colored = paint("x", PaintKind::Red, &output) != "x";
+        OutputCache::resolve(cfg, role, &selection.modules, colored)
+    };
+    let cached = cache
+        .as_ref()
+        .and_then(|cache| cache.load(&selection.modules));
+    if let Some(cache) = &cache {
+        let state = if cached.is_some() { "hit" } else { "miss" };
+        debug::log(format_args!(
+            "output cache {}: {}",
+            state,
+            cache.path.display()
+        ));
+    }
+    // A layout template replaces the module list, so only what it names is probed.
+    let probed_modules = match (cfg.template.as_deref(), &cached) {
+        (Some(template), _) => layout_modules(template),
+        (None, Some(_)) => selection
+            .modules
+            .iter()
+            .copied()
+            .filter(|module| module.is_per_session())
+            .collect(),
+        (None, None) => selection.modules.clone(),
+    };
+    let mut snapshot = collect_snapshot(
+        &probed_modules,
+        cfg,
+        SnapshotOptions {
+            probe_disks: cached.is_none(),
+            record_login: ctx.mode == RenderMode::Login,
+        },
+    );
+    if verbose {
+        match debug::timed("top_memory", || {
+            read_top_memory_processes(Path::new("/proc"), TOP_MEMORY_PROCESS_COUNT)
+        }) {
+            Ok(processes) => snapshot.top_memory = processes,
+            Err(err) => snapshot.diagnostics.note(ProbeIssue::TopMemoryFailed(err)),
+        }
+    }
+    log_login_event(cfg, ctx, &snapshot);
     let mut lines = Vec::new();
 
-    if !output.compact {
-        lines.push(String::new());
+    push_blank_lines(&mut lines, spacing.before_welcome);
+    if let Some(environment) = &output.environment
+        && let Some(banner) = &environment.banner
+    {
+        lines.push(match environment.color {
+            Some(color) => paint_highlight(banner, color, &output),
+            None => paint(banner, PaintKind::Alert, &output),
+        });
     }
-    lines.push(welcome.text.clone());
-    if !output.compact {
-        lines.push(String::new());
+    if let Some(header) = resolve_header_text(cfg, &welcome) {
+        lines.push(fit_welcome_text(
+            cfg,
+            header,
+            terminal_columns().map(|columns| columns.saturating_sub(spacing.gutter)),
+        ));
+        push_blank_lines(&mut lines, spacing.after_welcome);
+    }
+    if !banners.messages.is_empty() {
+        for message in &banners.messages {
+            lines.push(paint(message, PaintKind::Yellow, &output));
+        }
+        push_blank_lines(&mut lines, spacing.after_welcome);
+    }
+    match cfg.template.as_deref() {
+        Some(template) => {
+            let layout = render_layout(template, &snapshot, &output);
+            if verbose {
+                for name in &layout.unknown {
+                    eprintln!("motdyn: unknown template placeholder '{{{}}}'", name);
+                }
+            }
+            lines.push(layout.text);
+        }
+        None => match &cache {
+            None => lines.extend(render_module_lines(&selection.modules, &snapshot, &output)),
+            Some(cache) => {
+                let fresh = render_module_item_groups(&probed_modules, &snapshot, &output);
+                let module_items = match &cached {
+                    Some(cached) => cached.merge(&selection.modules, fresh),
+                    None => {
+                        let cacheable = CachedOutput {
+                            modules: fresh
+                                .iter()
+                                .filter(|(module, _)| !module.is_per_session())
+                                .cloned()
+                                .collect(),
+                            alerts: collect_health_alerts(&snapshot, &output),
+                        };
+                        if let Err(err) = cache.store(&cacheable) {
+                            debug::log(format_args!("output cache: {}", err));
+                        }
+                        fresh
+                    }
+                };
+                lines.extend(format_module_lines(module_items, &output));
+            }
+        },
     }
-    lines.extend(render_module_lines(&selection.modules, &snapshot, &output));
 
     if verbose {
         if !output.compact {
@@ -48,25 +353,102 @@ fn build_output(
         }
         lines.push(paint("Verbose details:", PaintKind::Header, &output));
         lines.extend(format_aligned_items(
-            &build_verbose_items(cfg, ctx, &selection, &welcome, &snapshot, &output),
+            &build_verbose_items(cfg, ctx, &selection, &welcome, &banners, &snapshot, &output),
             &output,
         ));
     }
 
-    if !output.compact {
-        lines.push(String::new());
+    push_blank_lines(&mut lines, spacing.before_farewell);
+    let alerts = if cfg.farewell_on_alert.unwrap_or(false) {
+        match &cached {
+            Some(cached) => cached.alerts.clone(),
+            None => collect_health_alerts(&snapshot, &output),
+        }
+    } else {
+        Vec::new()
+    };
+    lines.push(if alerts.is_empty() {
+        paint(
+            resolve_farewell_text(cfg, farewell_seed()),
+            PaintKind::Header,
+            &output,
+        )
+    } else {
+        paint(format_alert_farewell(&alerts), PaintKind::Red, &output)
+    });
+
+    let lines = indent_lines(lines, spacing.gutter);
+    if cfg.output.ansi_safe.unwrap_or(false) {
+        return lines.iter().map(|line| paste_safe_text(line)).collect();
+    }
+    lines
+}
+
+fn log_login_event(cfg: &MotdConfig, ctx: &RenderContext, snapshot: &SystemSnapshot) {
+    #[cfg(unix)]
+    if ctx.mode == RenderMode::Login
+        && cfg.log_to_syslog.unwrap_or(false)
+        && let Err(err) = syslog::record_login_event(snapshot)
+    {
+        debug::log(format_args!("syslog: {}", err));
+    }
+    #[cfg(not(unix))]
+    let _ = (cfg, ctx, snapshot);
+}
+
+/// Prefixes every non-empty line, including each line of multi-line
+/// entries such as the welcome art, with `gutter` spaces.
+fn indent_lines(lines: Vec<String>, gutter: usize) -> Vec<String> {
+    if gutter == 0 {
+        return lines;
     }
-    lines.push(paint(
-        resolve_farewell_text(cfg),
-        PaintKind::Header,
-        &output,
-    ));
+    let margin = " ".repeat(gutter);
     lines
+        .into_iter()
+        .map(|entry| {
+            entry
+                .split('\n')
+                .map(|line| {
+                    if line.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{}{}", margin, line)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect()
+}
+
+fn push_blank_lines(lines: &mut Vec<String>, count: usize) {
+    lines.extend(std::iter::repeat_n(String::new(), count));
+}
+
+fn format_alert_farewell(alerts: &[String]) -> String {
+    format!("⚠ Attention needed: {}", alerts.join("; "))
 }
 
-fn resolve_farewell_text(cfg: &MotdConfig) -> String {
-    match cfg.farewell.as_deref() {
-        Some(text) if !text.trim().is_empty() => text.to_string(),
+/// A scalar `farewell` wins over `farewells`; otherwise `seed` picks one
+/// entry of the list, and an empty list falls back to the default.
+fn resolve_farewell_text(cfg: &MotdConfig, seed: u64) -> String {
+    if let Some(text) = cfg
+        .farewell
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+    {
+        return text.to_string();
+    }
+    match cfg.farewells.as_deref() {
+        Some(farewells) if !farewells.is_empty() => {
+            farewells[(seed % farewells.len() as u64) as usize].clone()
+        }
         _ => DEFAULT_FAREWELL.to_string(),
     }
 }
+
+/// `RandomState` is keyed from OS randomness once per process, which is
+/// enough to vary the farewell per login without pulling in `rand`.
+fn farewell_seed() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
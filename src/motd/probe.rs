@@ -1,23 +1,58 @@
+use chrono::format::{Item, StrftimeItems};
 use chrono::{
-    DateTime, Datelike, Duration as ChronoDuration, Local, LocalResult, NaiveDateTime, TimeZone,
+    DateTime, Datelike, Duration as ChronoDuration, FixedOffset, Local, LocalResult, NaiveDateTime,
+    TimeZone, Utc,
 };
+use std::collections::{HashMap, HashSet};
 use std::env;
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader, Read};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::net::IpAddr;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::net::UnixStream;
 use std::path::Path;
 use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc;
 use std::thread::{self, JoinHandle};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 #[cfg(unix)]
 use rustix::fs::statvfs;
 
-use crate::config::MotdConfig;
+use crate::config::{CertCheckConfig, MotdConfig, ServiceFdCheckConfig, expand_tilde};
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use sha2::{Digest, Sha256};
+
+use super::cert::parse_certificate_not_after;
+use super::collector::{HostCollector, host_collector};
+use super::debug;
+#[cfg(target_os = "macos")]
+pub(super) use super::macos::read_host_name;
+#[cfg(target_os = "macos")]
+use super::macos::{get_os_info, parse_load_average, read_kernel_release};
+#[cfg(target_os = "macos")]
+pub(super) use super::macos::{parse_cpuinfo, parse_meminfo, read_uptime_secs};
+#[cfg(feature = "remote-welcome")]
+use super::release::cached_update_check;
 
 use super::types::{
-    FailedLoginBucket, FailedLoginInfo, FailedLoginSeverity, LastLoginInfo, LastLoginRecord,
-    LoginSessionKind, ModuleKind, NetworkProbeError, ProbeIssue, RenderedItem, SnapshotDiagnostics,
-    SourceRelation, SystemSnapshot, UsageSummary,
+    AuthorizedKey, AuthorizedKeysFile, BatteryStatus, CONTAINER_STORAGE_TIMEOUT_MS,
+    CertificateStatus, ContainerCounts, ContainerStorage, CpuTopology, DEFAULT_CPU_USAGE_SAMPLE_MS,
+    DEFAULT_DISK_TIMEOUT_MS, DEFAULT_RECENT_LOGINS_LIMIT, DEFAULT_RECENT_LOGINS_STATE_PATH,
+    DEFAULT_TIME_FORMAT, DEFAULT_TIME_FORMAT_12H, DOCKER_SOCKET_PATH, DiskMountSelection,
+    DiskUsageItem, FailedLoginBucket, FailedLoginInfo, FailedLoginSeverity, FdUsage, FdUsageStatus,
+    GPU_PROBE_TIMEOUT_MS, GpuInfo, InterfaceAddress, KernelPreemption, LastLoginInfo,
+    LastLoginRecord, LoginSessionKind, MAX_PROC_SCAN_ENTRIES, MacStatus, MemoryProcess, ModuleKind,
+    NEEDRESTART_CACHE_PATH, NEEDRESTART_CACHE_TTL_SECS, NEEDRESTART_FAILURE_CACHE_TTL_SECS,
+    NetThroughput, NetworkLink, NetworkProbeError, OomSummary, PROC_COMM_MAX_BYTES,
+    PROC_STAT_TICKS_PER_SEC, PSEUDO_FILESYSTEMS, PressureAverages, PressureStall, ProbeIssue,
+    REBOOT_REQUIRED_PATH, REVERSE_DNS_TIMEOUT_MS, RecentLogin, RenderedItem, SSH_PORT,
+    STORAGE_POOL_TIMEOUT_MS, SWAP_ACTIVITY_SAMPLE_MS, SeLinuxMode, SnapshotDiagnostics,
+    SnapshotOptions, SourceRelation, SwapActivity, SystemSnapshot, TIMEZONE_COMMAND_TIMEOUT_MS,
+    TOP_CPU_SAMPLE_MS, TOP_USERS_LIMIT, TopProcess, UPDATE_NOTIFIER_PATH, UsageSummary,
+    UserProcessCount, ZONEINFO_DIR,
 };
 
 #[cfg(target_os = "linux")]
@@ -29,7 +64,12 @@ use super::types::{
 const CORE_PROBE_TIMEOUT_MS: u64 = 120;
 const OPTIONAL_PROBE_TIMEOUT_MS: u64 = 150;
 const UPDATES_PROBE_TIMEOUT_MS: u64 = 250;
+const NEEDRESTART_PROBE_TIMEOUT_MS: u64 = 1500;
 const COMMAND_POLL_INTERVAL_MS: u64 = 10;
+#[cfg(not(target_os = "macos"))]
+const LOAD_AVERAGE_SOURCE: &str = "/proc/loadavg";
+#[cfg(target_os = "macos")]
+const LOAD_AVERAGE_SOURCE: &str = "sysctl vm.loadavg";
 
 #[derive(Debug)]
 pub(super) struct TimedCommandOutput {
@@ -53,19 +93,49 @@ pub(super) struct FailedLoginEvent {
     pub(super) via: Option<String>,
 }
 
+/// Disk usage feeds the alerts and metrics whatever the module list, so it
+/// is probed unless `options.probe_disks` is off.
 pub(super) fn collect_snapshot(
     requested_modules: &[ModuleKind],
     cfg: &MotdConfig,
+    options: SnapshotOptions,
+) -> SystemSnapshot {
+    collect_snapshot_from(host_collector(), requested_modules, cfg, options)
+}
+
+/// [`collect_snapshot`] with CPU, memory, uptime and disks read from
+/// `collector` instead of this build's backend.
+pub(super) fn collect_snapshot_from(
+    collector: &dyn HostCollector,
+    requested_modules: &[ModuleKind],
+    cfg: &MotdConfig,
+    options: SnapshotOptions,
 ) -> SystemSnapshot {
+    let started = Instant::now();
     let mut diagnostics = SnapshotDiagnostics::default();
-    let ((os_name, os_version), os_source) = get_os_info();
+    let ((os_name, os_version), os_source) = debug::timed("os", get_os_info);
     diagnostics.os_source = os_source.to_string();
     let now = Local::now();
-    let now_str_with_tz = now.format("%Y-%m-%d %H:%M:%S %:z").to_string();
-    let uptime_str = parse_uptime().unwrap_or_else(|| "unknown".to_string());
+    let (display_now, timezone_issue) = current_time_in_zone(now, cfg.timezone.as_deref());
+    if let Some(issue) = timezone_issue {
+        eprintln!("motdyn: {}", issue);
+    }
+    let (time_format, time_format_issue) = resolve_time_format(cfg);
+    if let Some(issue) = time_format_issue {
+        eprintln!("motdyn: {}", issue);
+    }
+    let now_str_with_tz = display_now.format(time_format).to_string();
+    let uptime_secs = debug::timed("uptime", || collector.uptime_secs());
+    let uptime_formatter = match cfg.uptime_format.as_deref() {
+        Some("compact") => format_uptime_compact,
+        _ => format_uptime,
+    };
+    let uptime_str = uptime_secs
+        .map(uptime_formatter)
+        .unwrap_or_else(|| "unknown".to_string());
     let load_average = if module_enabled(requested_modules, ModuleKind::Load) {
-        diagnostics.load_source = "/proc/loadavg".to_string();
-        match parse_load_average() {
+        diagnostics.load_source = LOAD_AVERAGE_SOURCE.to_string();
+        match debug::timed("load", parse_load_average) {
             Some(value) => value,
             None => {
                 diagnostics.degrade(ModuleKind::Load, ProbeIssue::LoadAverageReadFailed);
@@ -75,21 +145,110 @@ pub(super) fn collect_snapshot(
     } else {
         String::new()
     };
-    let kernel_version = read_first_line("/proc/sys/kernel/osrelease")
-        .unwrap_or_else(|| "Unknown kernel".to_string());
-    let host_name =
-        read_first_line("/proc/sys/kernel/hostname").unwrap_or_else(|| "Unknown host".to_string());
-    let (cpu_brand, cpu_count) = parse_cpuinfo();
-    let (mem_total, mem_free, swap_total, swap_free) = parse_meminfo();
-    let (current_user, from_ip) = get_current_user_and_ip();
-    let (login_user_count, login_user_count_source) = get_logged_in_user_count();
+    let kernel_version = read_kernel_release().unwrap_or_else(|| "Unknown kernel".to_string());
+    let kernel_preemption = if module_enabled(requested_modules, ModuleKind::Kernel) {
+        Some(debug::timed("kernel_preemption", || {
+            read_kernel_preemption(&kernel_version)
+        }))
+    } else {
+        None
+    };
+    let host_name = read_host_name().unwrap_or_else(|| "Unknown host".to_string());
+    let (cpu_brand, cpu_count, cpu_physical_cores) = debug::timed("cpu", || collector.cpu_info());
+    let (cpu_online, cpu_quota) = if module_enabled(requested_modules, ModuleKind::Cpu) {
+        debug::timed("cpu_limits", || {
+            (read_online_cpu_count(), read_cgroup_cpu_quota())
+        })
+    } else {
+        (None, None)
+    };
+    let cpu_topology = if module_enabled(requested_modules, ModuleKind::Cpu)
+        && cfg.show_cpu_topology.unwrap_or(false)
+    {
+        match debug::timed("cpu_topology", probe_cpu_topology) {
+            Ok(Some(topology)) => {
+                diagnostics.cpu_topology_source = "lscpu".to_string();
+                Some(topology)
+            }
+            Ok(None) => {
+                diagnostics.cpu_topology_source =
+                    "lscpu not installed; using /proc/cpuinfo".to_string();
+                None
+            }
+            Err(err) => {
+                diagnostics.note(ProbeIssue::CpuTopologyFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let cpu_usage = if module_enabled(requested_modules, ModuleKind::Cpu)
+        && cfg.show_cpu_usage.unwrap_or(false)
+    {
+        let sample_ms = cfg
+            .cpu_usage_sample_ms
+            .unwrap_or(DEFAULT_CPU_USAGE_SAMPLE_MS);
+        match debug::timed("cpu_usage", || sample_cpu_usage(sample_ms)) {
+            Ok(usage) => Some(usage),
+            Err(err) => {
+                diagnostics.note(ProbeIssue::CpuUsageFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let gpus =
+        if module_enabled(requested_modules, ModuleKind::Cpu) && cfg.show_gpu.unwrap_or(false) {
+            match debug::timed("gpu", probe_gpus) {
+                Ok(gpus) => gpus,
+                Err(err) => {
+                    diagnostics.note(ProbeIssue::GpuProbeFailed(err));
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+    let (mem_total, mem_free, swap_total, swap_free) =
+        debug::timed("memory", || collector.memory_kb());
+    // Without swap there is nothing to page, so the 200 ms sample is skipped.
+    let swap_activity = if module_enabled(requested_modules, ModuleKind::Swap)
+        && cfg.show_swap_activity.unwrap_or(false)
+        && swap_total > 0
+    {
+        match debug::timed("swap_activity", sample_swap_activity) {
+            Ok(activity) => Some(activity),
+            Err(err) => {
+                diagnostics.note(ProbeIssue::SwapActivityFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let (current_user, from_ip) = debug::timed("user", get_current_user_and_ip);
+    let from_host = if module_enabled(requested_modules, ModuleKind::User)
+        && cfg.resolve_hostname.unwrap_or(false)
+        && from_ip != "unknown"
+    {
+        debug::timed("reverse_dns", || resolve_client_host(&from_ip))
+    } else {
+        None
+    };
+    let (login_user_count, login_user_count_source) =
+        debug::timed("login_user_count", get_logged_in_user_count);
     diagnostics.login_user_count_source = login_user_count_source.to_string();
-    let (virt_info, virtualization_source, virtualization_issue) = detect_virtualization();
+    let (virt_info, virtualization_source, virtualization_issue) =
+        debug::timed("virtualization", detect_virtualization);
     diagnostics.virtualization_source = virtualization_source;
     diagnostics.network_source = "ip route/ip addr".to_string();
     let last_login = if module_enabled(requested_modules, ModuleKind::LastLogin) {
-        diagnostics.last_login_source = "lastlog".to_string();
-        match probe_last_login(&current_user, &from_ip, now) {
+        diagnostics.last_login_source = "last/lastlog".to_string();
+        match debug::timed("last_login", || {
+            probe_last_login(&current_user, &from_ip, now)
+        }) {
             Ok(value) => value,
             Err(err) => {
                 diagnostics.degrade(ModuleKind::LastLogin, ProbeIssue::LastLoginProbeFailed(err));
@@ -101,7 +260,9 @@ pub(super) fn collect_snapshot(
     };
     let failed_login = if module_enabled(requested_modules, ModuleKind::FailedLogin) {
         diagnostics.failed_login_source = "lastb".to_string();
-        match probe_failed_login(&current_user, &from_ip, now) {
+        match debug::timed("failed_login", || {
+            probe_failed_login(&current_user, &from_ip, now)
+        }) {
             Ok(value) => value,
             Err(err) => {
                 diagnostics.degrade(
@@ -116,7 +277,9 @@ pub(super) fn collect_snapshot(
     };
     let service_items = if module_enabled(requested_modules, ModuleKind::Services) {
         diagnostics.service_status_source = "systemctl is-active".to_string();
-        match probe_service_statuses(cfg.service_status.services.as_deref().unwrap_or(&[])) {
+        match debug::timed("services", || {
+            probe_service_statuses(cfg.service_status.services.as_deref().unwrap_or(&[]))
+        }) {
             Ok(items) => items,
             Err(err) => {
                 diagnostics.degrade(
@@ -133,7 +296,7 @@ pub(super) fn collect_snapshot(
         Vec::new()
     };
     let update_summary = if module_enabled(requested_modules, ModuleKind::Updates) {
-        match probe_package_updates() {
+        match debug::timed("updates", probe_package_updates) {
             Ok((summary, source)) => {
                 diagnostics.updates_source = source;
                 summary
@@ -146,8 +309,339 @@ pub(super) fn collect_snapshot(
     } else {
         String::new()
     };
+    let needrestart_count = if module_enabled(requested_modules, ModuleKind::NeedRestart) {
+        match debug::timed("needrestart", probe_needrestart) {
+            Ok(Some((count, source))) => {
+                diagnostics.needrestart_source = source;
+                Some(count)
+            }
+            Ok(None) => {
+                diagnostics.needrestart_source = "needrestart not installed".to_string();
+                None
+            }
+            Err(err) => {
+                diagnostics.degrade(
+                    ModuleKind::NeedRestart,
+                    ProbeIssue::NeedRestartProbeFailed(err),
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let reboot_required = if module_enabled(requested_modules, ModuleKind::RebootRequired) {
+        let (required, source) = debug::timed("reboot_required", probe_reboot_required);
+        diagnostics.reboot_required_source = source;
+        required
+    } else {
+        None
+    };
+    let failed_units = if module_enabled(requested_modules, ModuleKind::FailedUnits) {
+        match debug::timed("failed_units", probe_failed_units) {
+            Ok(units) => units,
+            Err(err) => {
+                diagnostics.note(ProbeIssue::FailedUnitsProbeFailed(err));
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let auto_updates_enabled = if module_enabled(requested_modules, ModuleKind::AutoUpdates) {
+        match debug::timed("auto_updates", probe_auto_updates) {
+            Some((enabled, source)) => {
+                diagnostics.auto_updates_source = source;
+                Some(enabled)
+            }
+            None => {
+                diagnostics.auto_updates_source = "no apt or dnf found".to_string();
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let certificates = if module_enabled(requested_modules, ModuleKind::Certificates) {
+        let statuses = debug::timed("certificates", || {
+            probe_certificates(cfg.cert_checks.as_deref().unwrap_or(&[]))
+        });
+        for status in &statuses {
+            if let Err(message) = &status.days_left {
+                diagnostics.degrade(
+                    ModuleKind::Certificates,
+                    ProbeIssue::CertificateCheckFailed {
+                        label: status.label.clone(),
+                        message: message.clone(),
+                    },
+                );
+            }
+        }
+        statuses
+    } else {
+        Vec::new()
+    };
+    let authorized_keys = if module_enabled(requested_modules, ModuleKind::AuthorizedKeys) {
+        let (files, failures) = debug::timed("authorized_keys", probe_authorized_keys);
+        for (path, message) in failures {
+            diagnostics.degrade(
+                ModuleKind::AuthorizedKeys,
+                ProbeIssue::AuthorizedKeysFailed { path, message },
+            );
+        }
+        files
+    } else {
+        Vec::new()
+    };
+    let fd_usages = if module_enabled(requested_modules, ModuleKind::FdUsage) {
+        let statuses = debug::timed("fd_usage", || {
+            probe_fd_usages(cfg.service_fd_checks.as_deref().unwrap_or(&[]))
+        });
+        for status in &statuses {
+            if let Err(message) = &status.usage {
+                diagnostics.degrade(
+                    ModuleKind::FdUsage,
+                    ProbeIssue::FdUsageProbeFailed {
+                        label: status.label.clone(),
+                        message: message.clone(),
+                    },
+                );
+            }
+        }
+        statuses
+    } else {
+        Vec::new()
+    };
+    let recent_logins = if module_enabled(requested_modules, ModuleKind::RecentLogins) {
+        let state_path = expand_tilde(
+            cfg.recent_logins
+                .state_path
+                .as_deref()
+                .unwrap_or(DEFAULT_RECENT_LOGINS_STATE_PATH),
+        );
+        diagnostics.recent_logins_source = state_path.display().to_string();
+        let current = RecentLogin {
+            when_secs: unix_now_secs(),
+            user: current_user.clone(),
+            from: from_ip.clone(),
+        };
+        let limit = cfg
+            .recent_logins
+            .limit
+            .unwrap_or(DEFAULT_RECENT_LOGINS_LIMIT);
+        match debug::timed("recent_logins", || {
+            if options.record_login {
+                record_recent_login(&state_path, limit, current)
+            } else {
+                read_recent_logins(&state_path, limit)
+            }
+        }) {
+            Ok(previous) => previous,
+            Err(err) => {
+                diagnostics.degrade(
+                    ModuleKind::RecentLogins,
+                    ProbeIssue::RecentLoginsFailed(err),
+                );
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let oom = if module_enabled(requested_modules, ModuleKind::Oom) {
+        match debug::timed("oom", read_kernel_log) {
+            Ok((log, source)) => {
+                diagnostics.oom_source = source.to_string();
+                Some(parse_oom_kill_events(&log))
+            }
+            Err(err) => {
+                diagnostics.degrade(ModuleKind::Oom, ProbeIssue::OomProbeFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let top_cpu = if module_enabled(requested_modules, ModuleKind::TopCpu) {
+        match debug::timed("top_cpu", || sample_top_cpu_process(Path::new("/proc"))) {
+            Ok(top) => top,
+            Err(err) => {
+                diagnostics.degrade(ModuleKind::TopCpu, ProbeIssue::TopCpuProbeFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
 
-    let main_iface = match get_default_interface() {
+    let top_users = if module_enabled(requested_modules, ModuleKind::TopUsers) {
+        match debug::timed("top_users", || probe_top_users(Path::new("/proc"))) {
+            Ok(users) => users,
+            Err(err) => {
+                diagnostics.degrade(ModuleKind::TopUsers, ProbeIssue::TopUsersProbeFailed(err));
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let (process_count, ssh_session_count) =
+        if module_enabled(requested_modules, ModuleKind::Processes) {
+            let process_count = match debug::timed("processes", || {
+                count_processes(Path::new("/proc"))
+            }) {
+                Ok(count) => Some(count),
+                Err(err) => {
+                    diagnostics.degrade(ModuleKind::Processes, ProbeIssue::ProcessCountFailed(err));
+                    None
+                }
+            };
+            (
+                process_count,
+                debug::timed("ssh_sessions", read_ssh_session_count),
+            )
+        } else {
+            (None, None)
+        };
+
+    let mac_status = module_enabled(requested_modules, ModuleKind::Mac)
+        .then(|| debug::timed("mac", read_mac_status));
+
+    let container_storage = if module_enabled(requested_modules, ModuleKind::ContainerStorage) {
+        match debug::timed("container_storage", probe_container_storage) {
+            Ok(Some((storage, source))) => {
+                diagnostics.container_storage_source = source;
+                Some(storage)
+            }
+            Ok(None) => {
+                diagnostics.container_storage_source = "no container runtime found".to_string();
+                None
+            }
+            Err(err) => {
+                diagnostics.degrade(
+                    ModuleKind::ContainerStorage,
+                    ProbeIssue::ContainerStorageFailed(err),
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let net_throughput = if module_enabled(requested_modules, ModuleKind::Network)
+        && cfg.show_net_throughput.unwrap_or(false)
+    {
+        let sample_ms = cfg.net_throughput_sample_ms.filter(|ms| *ms > 0);
+        match debug::timed("net_throughput", || probe_net_throughput(sample_ms)) {
+            Ok(throughput) => Some(throughput),
+            Err(err) => {
+                diagnostics.note(ProbeIssue::NetThroughputFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let pressure = if cfg.show_pressure.unwrap_or(false) {
+        let read = |module: ModuleKind, resource: &str| {
+            module_enabled(requested_modules, module)
+                .then(|| read_pressure(resource))
+                .flatten()
+        };
+        debug::timed("pressure", || PressureStall {
+            cpu: read(ModuleKind::Cpu, "cpu"),
+            memory: read(ModuleKind::Memory, "memory"),
+            io: read(ModuleKind::Disk, "io"),
+        })
+    } else {
+        PressureStall::default()
+    };
+
+    let containers = if module_enabled(requested_modules, ModuleKind::Virtualization)
+        && cfg.show_containers.unwrap_or(false)
+    {
+        match debug::timed("containers", probe_container_counts) {
+            Ok(Some((counts, source))) => {
+                diagnostics.containers_source = source;
+                Some(counts)
+            }
+            Ok(None) => {
+                diagnostics.containers_source = "no container runtime found".to_string();
+                None
+            }
+            Err(err) => {
+                diagnostics.note(ProbeIssue::ContainerCountFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let network_links = if module_enabled(requested_modules, ModuleKind::Link) {
+        debug::timed("link", || read_network_links(Path::new("/sys/class/net")))
+    } else {
+        Vec::new()
+    };
+
+    let interface_addresses = if module_enabled(requested_modules, ModuleKind::Addresses) {
+        match debug::timed("addresses", || get_interfaces(cfg.interfaces.as_deref())) {
+            Ok(addresses) => addresses,
+            Err(err) => {
+                diagnostics.degrade(
+                    ModuleKind::Addresses,
+                    ProbeIssue::InterfaceAddressesFailed(err),
+                );
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let cpu_temperature = if module_enabled(requested_modules, ModuleKind::Temperature) {
+        debug::timed("temperature", || {
+            read_cpu_temperature(Path::new("/sys/class"))
+        })
+    } else {
+        None
+    };
+
+    let batteries = if module_enabled(requested_modules, ModuleKind::Battery) {
+        debug::timed("battery", || {
+            parse_battery(Path::new("/sys/class/power_supply"))
+        })
+    } else {
+        Vec::new()
+    };
+
+    let storage_pools = if module_enabled(requested_modules, ModuleKind::Pools) {
+        debug::timed("pools", || probe_storage_pools(&mut diagnostics))
+    } else {
+        Vec::new()
+    };
+
+    let newer_release = if module_enabled(requested_modules, ModuleKind::UpdateCheck) {
+        match debug::timed("update_check", probe_update_check) {
+            Ok((latest, source)) => {
+                diagnostics.update_check_source = source;
+                latest
+            }
+            Err(err) => {
+                diagnostics.degrade(ModuleKind::UpdateCheck, ProbeIssue::UpdateCheckFailed(err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let main_iface = match debug::timed("network_interface", get_default_interface) {
         Ok(iface) => iface,
         Err(err) => {
             diagnostics.degrade(ModuleKind::Network, ProbeIssue::Network(err));
@@ -157,7 +651,7 @@ pub(super) fn collect_snapshot(
     let main_ip = if main_iface == "unknown" {
         "unknown".to_string()
     } else {
-        match get_interface_ipv4(&main_iface) {
+        match debug::timed("network_ipv4", || get_interface_ipv4(&main_iface)) {
             Ok(ip) => ip,
             Err(err) => {
                 diagnostics.degrade(ModuleKind::Network, ProbeIssue::Network(err));
@@ -187,11 +681,35 @@ pub(super) fn collect_snapshot(
     if mem_total == 0 {
         diagnostics.degrade(ModuleKind::Memory, ProbeIssue::MemoryInfoMissing);
     }
+    let cgroup_memory = debug::timed("memory_cgroup", read_cgroup_memory)
+        .filter(|(limit_kb, _)| mem_total == 0 || *limit_kb < mem_total);
+    let memory = match cgroup_memory {
+        Some((limit_kb, current_kb)) => {
+            usage_summary(limit_kb, limit_kb.saturating_sub(current_kb))
+        }
+        None => usage_summary(mem_total, mem_free),
+    };
     if from_ip == "unknown" {
         diagnostics.note(ProbeIssue::SshConnectionMissing);
     }
 
-    let (root_disk, disk_items) = collect_disk_usage_items();
+    let (root_disk, disk_items) = if options.probe_disks {
+        debug::timed("disk", || {
+            collector.disks(
+                &disk_mount_selection(cfg),
+                cfg.disk_timeout_ms.unwrap_or(DEFAULT_DISK_TIMEOUT_MS),
+                &mut diagnostics,
+            )
+        })
+    } else {
+        (None, Vec::new())
+    };
+    debug::log(format_args!(
+        "snapshot collected in {:.1}ms, degraded={:?}, issues={}",
+        started.elapsed().as_secs_f64() * 1000.0,
+        diagnostics.degraded_modules,
+        diagnostics.issues.len()
+    ));
 
     SystemSnapshot {
         host_name,
@@ -199,17 +717,34 @@ pub(super) fn collect_snapshot(
         main_ip,
         current_user,
         from_ip,
+        from_host,
         login_user_count,
         now_str_with_tz,
         uptime_str,
+        uptime_secs,
         load_average,
         os_name,
         os_version,
         kernel_version,
+        kernel_preemption,
         virt_info,
         cpu_brand,
         cpu_count,
-        memory: usage_summary(mem_total, mem_free),
+        cpu_physical_cores,
+        cpu_online,
+        cpu_quota,
+        cpu_topology,
+        swap_activity,
+        cpu_usage,
+        gpus,
+        cpu_temperature,
+        batteries,
+        net_throughput,
+        pressure,
+        storage_pools,
+        top_memory: Vec::new(),
+        memory,
+        memory_cgroup: cgroup_memory.is_some(),
         swap: usage_summary(swap_total, swap_free),
         root_disk,
         disk_items,
@@ -217,103 +752,1391 @@ pub(super) fn collect_snapshot(
         failed_login,
         service_items,
         update_summary,
+        needrestart_count,
+        auto_updates_enabled,
+        reboot_required,
+        failed_units,
+        certificates,
+        fd_usages,
+        recent_logins,
+        oom,
+        top_cpu,
+        top_users,
+        process_count,
+        ssh_session_count,
+        mac_status,
+        container_storage,
+        containers,
+        network_links,
+        interface_addresses,
+        newer_release,
+        authorized_keys,
         diagnostics,
     }
 }
 
-pub(super) fn parse_loadavg_content(content: &str) -> Option<String> {
-    let mut parts = content.split_whitespace();
-    let one = parts.next()?;
-    let five = parts.next()?;
-    let fifteen = parts.next()?;
-    Some(format!("{} {} {}", one, five, fifteen))
+/// `time_format` wins over `time_24h`. chrono only reports a bad specifier
+/// when the timestamp is displayed, so the format is checked up front and
+/// replaced by the default together with a warning.
+pub(super) fn resolve_time_format(cfg: &MotdConfig) -> (&str, Option<String>) {
+    let default = if cfg.time_24h.unwrap_or(true) {
+        DEFAULT_TIME_FORMAT
+    } else {
+        DEFAULT_TIME_FORMAT_12H
+    };
+    match cfg.time_format.as_deref() {
+        Some(format) if StrftimeItems::new(format).any(|item| item == Item::Error) => (
+            default,
+            Some(format!(
+                "ignoring invalid time_format '{}', using '{}'",
+                format, default
+            )),
+        ),
+        Some(format) => (format, None),
+        None => (default, None),
+    }
+}
+
+/// Shifts the current time into the configured IANA zone. The zone is looked
+/// up in the system tzdata and its current offset read from `date +%z`, so an
+/// unknown name or a missing `date` falls back to the local zone.
+fn current_time_in_zone(
+    now: DateTime<Local>,
+    timezone: Option<&str>,
+) -> (DateTime<FixedOffset>, Option<String>) {
+    let now = now.fixed_offset();
+    let Some(name) = timezone else {
+        return (now, None);
+    };
+    let offset = is_known_timezone(Path::new(ZONEINFO_DIR), name)
+        .then(|| {
+            run_command_with_timeout(
+                "date",
+                &["+%z"],
+                &[("TZ", name)],
+                TIMEZONE_COMMAND_TIMEOUT_MS,
+            )
+            .ok()
+        })
+        .flatten()
+        .filter(|output| output.status.success())
+        .and_then(|output| parse_utc_offset(output.stdout.trim()));
+    match offset {
+        Some(offset) => (now.with_timezone(&offset), None),
+        None => (
+            now,
+            Some(format!(
+                "ignoring unknown timezone '{}', using the local zone",
+                name
+            )),
+        ),
+    }
+}
+
+pub(super) fn is_known_timezone(zoneinfo_dir: &Path, name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('/')
+        && !name
+            .split('/')
+            .any(|part| part.is_empty() || part == "." || part == "..")
+        && zoneinfo_dir.join(name).is_file()
+}
+
+/// Parses a `date +%z` offset such as `+0530` or `-0800`.
+pub(super) fn parse_utc_offset(value: &str) -> Option<FixedOffset> {
+    let (sign, digits) = match value.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let hours = digits[..2].parse::<i32>().ok()?;
+    let minutes = digits[2..].parse::<i32>().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+pub(super) fn parse_loadavg_content(content: &str) -> Option<String> {
+    let mut parts = content.split_whitespace();
+    let one = parts.next()?;
+    let five = parts.next()?;
+    let fifteen = parts.next()?;
+    Some(format!("{} {} {}", one, five, fifteen))
+}
+
+pub(super) fn parse_lastlog_output(output: &str) -> Option<Option<ParsedLastLoginRecord>> {
+    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next()?;
+    let data = lines.next()?;
+
+    if data.contains("**Never logged in**") {
+        return Some(None);
+    }
+
+    let port_start = header.find("Port")?;
+    let from_start = header.find("From")?;
+    let latest_start = header.find("Latest")?;
+    if !(port_start < from_start && from_start < latest_start) {
+        return None;
+    }
+
+    let via = normalize_detail_field(&slice_column(data, port_start, from_start));
+    let from = normalize_detail_field(&slice_column(data, from_start, latest_start));
+    let latest = data.get(latest_start..)?.trim();
+    if latest.is_empty() {
+        return None;
+    }
+
+    Some(Some(ParsedLastLoginRecord {
+        when: latest.to_string(),
+        from,
+        via,
+    }))
+}
+
+/// Reads `last -i -w -n 2 USER` and returns the newest session other than
+/// the one still running, or `Some(None)` when wtmp holds no earlier session.
+pub(super) fn parse_last_output(output: &str) -> Option<Option<ParsedLastLoginRecord>> {
+    let entries = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("wtmp begins"))
+        .collect::<Vec<_>>();
+    let previous = match entries.as_slice() {
+        [current, rest @ ..] if current.contains("still logged in") => rest.first(),
+        entries => entries.first(),
+    };
+    let Some(line) = previous else {
+        return Some(None);
+    };
+
+    let mut parts = line.split_whitespace();
+    let _user = parts.next()?;
+    let via = normalize_detail_field(parts.next()?);
+    // `-i` prints 0.0.0.0 for logins without a remote host.
+    let from = normalize_detail_field(parts.next()?).filter(|from| from != "0.0.0.0");
+    let when_tokens = parts.take(4).collect::<Vec<_>>();
+    if when_tokens.len() < 4 {
+        return None;
+    }
+
+    Some(Some(ParsedLastLoginRecord {
+        when: when_tokens.join(" "),
+        from,
+        via,
+    }))
+}
+
+pub(super) fn parse_lastb_output(
+    output: &str,
+    now: DateTime<Local>,
+) -> Option<Vec<FailedLoginEvent>> {
+    let entries = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("btmp begins"))
+        .collect::<Vec<_>>();
+
+    if entries.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut parsed = Vec::new();
+    for line in entries {
+        let mut parts = line.split_whitespace();
+        let _user = parts.next()?;
+        let via = normalize_detail_field(parts.next().unwrap_or_default());
+        let from = normalize_detail_field(parts.next().unwrap_or_default());
+        let when_tokens = parts.take(4).collect::<Vec<_>>();
+        if when_tokens.len() < 4 {
+            return None;
+        }
+
+        let when_raw = when_tokens.join(" ");
+        parsed.push(FailedLoginEvent {
+            when: parse_lastb_timestamp(&when_raw, now),
+            when_raw,
+            from,
+            via,
+        });
+    }
+
+    Some(parsed)
+}
+
+pub(super) fn parse_apt_upgradable_output(output: &str) -> usize {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.contains("[upgradable from:"))
+        .count()
+}
+
+/// Reads the count from update-notifier's summary, which says either
+/// "12 updates can be applied immediately." or "12 packages can be updated.".
+pub(super) fn parse_update_notifier_count(content: &str) -> Option<usize> {
+    content.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        let count = words.next()?.parse::<usize>().ok()?;
+        matches!(
+            words.next(),
+            Some("updates" | "update" | "packages" | "package")
+        )
+        .then_some(count)
+    })
+}
+
+pub(super) fn parse_dnf_check_update_output(output: &str) -> usize {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            !line.is_empty()
+                && !line.starts_with("Last metadata expiration check:")
+                && line.split_whitespace().count() >= 3
+        })
+        .count()
+}
+
+pub(super) fn parse_needrestart_batch_output(output: &str) -> usize {
+    output
+        .lines()
+        .filter(|line| line.trim_start().starts_with("NEEDRESTART-SVC:"))
+        .count()
+}
+
+pub(super) fn parse_proc_limits_nofile(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|soft| soft.parse::<u64>().ok())
+}
+
+/// The kernel keeps only the first 15 bytes of a command name in
+/// `/proc/<pid>/comm`, so `systemd-resolved` is found as `systemd-resolve`.
+pub(super) fn find_pid_by_comm(proc_root: &Path, name: &str) -> Option<u32> {
+    let mut end = name.len().min(PROC_COMM_MAX_BYTES);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    let comm_name = &name[..end];
+    fs::read_dir(proc_root)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            let comm = fs::read_to_string(entry.path().join("comm")).ok()?;
+            (comm.trim() == comm_name).then_some(pid)
+        })
+        .min()
+}
+
+/// Counts the `ESTABLISHED` rows of a `/proc/<pid>/net/tcp` or `tcp6` table
+/// whose socket inode is one of the process's open sockets.
+pub(super) fn count_established_connections(content: &str, socket_inodes: &HashSet<u64>) -> usize {
+    content
+        .lines()
+        .skip(1)
+        .filter(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            fields.get(3) == Some(&"01")
+                && fields
+                    .get(9)
+                    .and_then(|inode| inode.parse::<u64>().ok())
+                    .is_some_and(|inode| socket_inodes.contains(&inode))
+        })
+        .count()
+}
+
+/// Returns the command name and `utime + stime` ticks from `/proc/<pid>/stat`.
+pub(super) fn parse_proc_stat_cpu_ticks(content: &str) -> Option<(String, u64)> {
+    let open = content.find('(')?;
+    // The command name may itself contain parentheses; it ends at the last one.
+    let close = content.rfind(')')?;
+    let name = content.get(open + 1..close)?.to_string();
+    let mut fields = content.get(close + 1..)?.split_whitespace().skip(11);
+    let utime = fields.next()?.parse::<u64>().ok()?;
+    let stime = fields.next()?.parse::<u64>().ok()?;
+    Some((name, utime + stime))
+}
+
+fn read_process_cpu_ticks(proc_root: &Path) -> Result<HashMap<u32, (String, u64)>, String> {
+    let entries = fs::read_dir(proc_root)
+        .map_err(|err| format!("failed to read {}: {}", proc_root.display(), err))?;
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            // Processes can exit between listing and reading; skip them.
+            let stat = fs::read_to_string(entry.path().join("stat")).ok()?;
+            Some((pid, parse_proc_stat_cpu_ticks(&stat)?))
+        })
+        .collect())
+}
+
+/// Reads `VmRSS` in kB from `/proc/<pid>/status`; kernel threads have none.
+pub(super) fn parse_status_vm_rss_kb(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// The `limit` processes with the most resident memory, largest first. Only
+/// the first `MAX_PROC_SCAN_ENTRIES` pids are looked at, so hosts with huge
+/// process tables do not slow the login down.
+pub(super) fn read_top_memory_processes(
+    proc_root: &Path,
+    limit: usize,
+) -> Result<Vec<MemoryProcess>, String> {
+    let entries = fs::read_dir(proc_root)
+        .map_err(|err| format!("failed to read {}: {}", proc_root.display(), err))?;
+    let mut processes = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .take(MAX_PROC_SCAN_ENTRIES)
+        .filter_map(|pid| {
+            // Processes can exit between listing and reading; skip them.
+            let dir = proc_root.join(pid.to_string());
+            let rss_kb = parse_status_vm_rss_kb(&fs::read_to_string(dir.join("status")).ok()?)?;
+            let name = fs::read_to_string(dir.join("comm"))
+                .ok()?
+                .trim()
+                .to_string();
+            Some(MemoryProcess { pid, name, rss_kb })
+        })
+        .filter(|process| process.rss_kb > 0)
+        .collect::<Vec<_>>();
+    processes.sort_by(|left, right| {
+        right
+            .rss_kb
+            .cmp(&left.rss_kb)
+            .then(left.pid.cmp(&right.pid))
+    });
+    processes.truncate(limit);
+    Ok(processes)
+}
+
+/// Picks the process with the largest CPU tick delta between two samples.
+/// Pids missing from either sample, or reused under another name, are skipped.
+pub(super) fn pick_top_cpu_process(
+    before: &HashMap<u32, (String, u64)>,
+    after: &HashMap<u32, (String, u64)>,
+    elapsed: Duration,
+) -> Option<TopProcess> {
+    let elapsed_ticks = elapsed.as_secs_f64() * PROC_STAT_TICKS_PER_SEC as f64;
+    if elapsed_ticks <= 0.0 {
+        return None;
+    }
+
+    after
+        .iter()
+        .filter_map(|(pid, (name, ticks))| {
+            let (before_name, before_ticks) = before.get(pid)?;
+            (before_name == name && ticks > before_ticks)
+                .then(|| (*pid, name, ticks - before_ticks))
+        })
+        .max_by(|left, right| left.2.cmp(&right.2).then(right.0.cmp(&left.0)))
+        .map(|(_, name, delta)| TopProcess {
+            name: name.clone(),
+            cpu_percent: delta as f64 / elapsed_ticks * 100.0,
+        })
+}
+
+fn sample_top_cpu_process(proc_root: &Path) -> Result<Option<TopProcess>, String> {
+    let own_pid = std::process::id();
+    let started = Instant::now();
+    let mut before = read_process_cpu_ticks(proc_root)?;
+    thread::sleep(Duration::from_millis(TOP_CPU_SAMPLE_MS));
+    let mut after = read_process_cpu_ticks(proc_root)?;
+    let elapsed = started.elapsed();
+
+    before.remove(&own_pid);
+    after.remove(&own_pid);
+    Ok(pick_top_cpu_process(&before, &after, elapsed))
+}
+
+fn probe_top_users(proc_root: &Path) -> Result<Vec<UserProcessCount>, String> {
+    let entries = fs::read_dir(proc_root)
+        .map_err(|err| format!("failed to read {}: {}", proc_root.display(), err))?;
+    let mut counts = HashMap::new();
+    for entry in entries.flatten() {
+        if !entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.bytes().all(|byte| byte.is_ascii_digit()))
+        {
+            continue;
+        }
+        // Processes can exit between listing and stat; skip them.
+        if let Ok(metadata) = entry.metadata() {
+            *counts.entry(metadata.uid()).or_insert(0) += 1;
+        }
+    }
+    let names = fs::read_to_string("/etc/passwd")
+        .map(|content| parse_passwd_names(&content))
+        .unwrap_or_default();
+    Ok(rank_top_users(&counts, &names, TOP_USERS_LIMIT))
+}
+
+/// Counts the numeric `/proc` entries, one per process.
+pub(super) fn count_processes(proc_root: &Path) -> Result<usize, String> {
+    let entries = fs::read_dir(proc_root)
+        .map_err(|err| format!("failed to read {}: {}", proc_root.display(), err))?;
+    Ok(entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.bytes().all(|byte| byte.is_ascii_digit()))
+        })
+        .count())
+}
+
+/// `None` when neither `/proc/net/tcp` nor `/proc/net/tcp6` is readable.
+fn read_ssh_session_count() -> Option<usize> {
+    let tables = ["/proc/net/tcp", "/proc/net/tcp6"]
+        .into_iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect::<Vec<_>>();
+    if tables.is_empty() {
+        return None;
+    }
+    Some(
+        tables
+            .iter()
+            .map(|table| count_established_on_port(table, SSH_PORT))
+            .sum(),
+    )
+}
+
+/// Counts `ESTABLISHED` (state `01`) rows of a `/proc/net/tcp` table whose
+/// local address ends in `port`, written in hex.
+pub(super) fn count_established_on_port(table: &str, port: u16) -> usize {
+    table
+        .lines()
+        .skip(1)
+        .filter(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            fields.len() > 3
+                && fields[3] == "01"
+                && fields[1]
+                    .rsplit_once(':')
+                    .and_then(|(_, hex)| u16::from_str_radix(hex, 16).ok())
+                    == Some(port)
+        })
+        .count()
+}
+
+/// Asks the Docker API socket first, then the `docker` or `podman` CLI.
+fn probe_container_storage() -> Result<Option<(ContainerStorage, String)>, String> {
+    let socket_path = env::var("DOCKER_HOST")
+        .ok()
+        .and_then(|host| host.strip_prefix("unix://").map(str::to_string))
+        .unwrap_or_else(|| DOCKER_SOCKET_PATH.to_string());
+    if Path::new(&socket_path).exists() {
+        match query_unix_socket_http(&socket_path, "/system/df") {
+            Ok(body) => {
+                if let Some(storage) = parse_docker_system_df_json(&body) {
+                    return Ok(Some((storage, socket_path)));
+                }
+                debug::log(format_args!("{}: unexpected /system/df body", socket_path));
+            }
+            Err(err) => debug::log(format_args!("{}: {}", socket_path, err)),
+        }
+    }
+
+    let Some(runtime) = ["docker", "podman"]
+        .into_iter()
+        .find(|runtime| command_exists(runtime))
+    else {
+        return Ok(None);
+    };
+    let output = run_command_with_timeout(
+        runtime,
+        &[
+            "system",
+            "df",
+            "--format",
+            "{{.Type}}\t{{.Size}}\t{{.Reclaimable}}",
+        ],
+        &[("LC_ALL", "C")],
+        CONTAINER_STORAGE_TIMEOUT_MS,
+    )?;
+    if !output.status.success() {
+        return Err(format!(
+            "'{} system df' exited with {}",
+            runtime, output.status
+        ));
+    }
+    parse_system_df_table(&output.stdout)
+        .map(|storage| Some((storage, format!("{} system df", runtime))))
+        .ok_or_else(|| format!("'{} system df' printed no sizes", runtime))
+}
+
+/// Lists ZFS pools when `zpool` is installed and Btrfs filesystems when any
+/// is mounted and `btrfs` is installed; a failing tool degrades the module
+/// without hiding what the other found.
+fn probe_storage_pools(diagnostics: &mut SnapshotDiagnostics) -> Vec<DiskUsageItem> {
+    let mut pools = Vec::new();
+    let mut sources = Vec::new();
+    if command_exists("zpool") {
+        match probe_zfs_pools() {
+            Ok(found) => {
+                pools.extend(found);
+                sources.push("zpool list");
+            }
+            Err(err) => diagnostics.degrade(ModuleKind::Pools, ProbeIssue::StoragePoolsFailed(err)),
+        }
+    }
+    let btrfs_mounts = fs::read_to_string("/proc/mounts")
+        .map(|content| parse_btrfs_mounts(&content))
+        .unwrap_or_default();
+    if !btrfs_mounts.is_empty() && command_exists("btrfs") {
+        for mount_path in btrfs_mounts {
+            match probe_btrfs_usage(&mount_path) {
+                Ok((used_bytes, total_bytes)) => pools.push(disk_usage_item(
+                    mount_path.clone(),
+                    "btrfs".to_string(),
+                    &format!("Btrfs ({}):", mount_path),
+                    total_bytes,
+                    used_bytes,
+                )),
+                Err(err) => {
+                    diagnostics.degrade(ModuleKind::Pools, ProbeIssue::StoragePoolsFailed(err))
+                }
+            }
+        }
+        sources.push("btrfs filesystem usage");
+    }
+    diagnostics.storage_pools_source = if sources.is_empty() {
+        "no ZFS pool or Btrfs filesystem found".to_string()
+    } else {
+        sources.join(", ")
+    };
+    pools
+}
+
+/// Pool sizes come from `zpool list`; the pools' root datasets, when `zfs`
+/// answers, replace them with the space datasets can actually use.
+fn probe_zfs_pools() -> Result<Vec<DiskUsageItem>, String> {
+    let output = run_command_with_timeout(
+        "zpool",
+        &["list", "-H", "-p", "-o", "name,size,alloc,free"],
+        &[("LC_ALL", "C")],
+        STORAGE_POOL_TIMEOUT_MS,
+    )?;
+    if !output.status.success() {
+        return Err(format!("'zpool list' exited with {}", output.status));
+    }
+    let names = output
+        .stdout
+        .lines()
+        .filter_map(|line| line.split('\t').next())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    let mut args = vec!["list", "-H", "-p", "-o", "name,used,avail"];
+    args.extend(&names);
+    let datasets =
+        match run_command_with_timeout("zfs", &args, &[("LC_ALL", "C")], STORAGE_POOL_TIMEOUT_MS) {
+            Ok(datasets) if datasets.status.success() => datasets.stdout,
+            Ok(datasets) => {
+                debug::log(format_args!("'zfs list' exited with {}", datasets.status));
+                String::new()
+            }
+            Err(err) => {
+                debug::log(format_args!("zfs list: {}", err));
+                String::new()
+            }
+        };
+    Ok(parse_zfs_pools(&output.stdout, &datasets))
+}
+
+/// Builds one item per `name<TAB>size<TAB>alloc<TAB>free` row of
+/// `zpool list -Hp`. A matching `name<TAB>used<TAB>avail` row from
+/// `zfs list -Hp` wins, since it accounts for parity, reservations and
+/// compression.
+pub(super) fn parse_zfs_pools(zpool_output: &str, zfs_output: &str) -> Vec<DiskUsageItem> {
+    let datasets = zfs_output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?;
+            let used = fields.next()?.trim().parse::<u64>().ok()?;
+            let avail = fields.next()?.trim().parse::<u64>().ok()?;
+            Some((name, (used, used.saturating_add(avail))))
+        })
+        .collect::<HashMap<_, _>>();
+    zpool_output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let size = fields.next()?.trim().parse::<u64>().ok()?;
+            let alloc = fields.next()?.trim().parse::<u64>().ok()?;
+            let (used_bytes, total_bytes) = datasets.get(name).copied().unwrap_or((alloc, size));
+            Some(disk_usage_item(
+                name.to_string(),
+                "zfs".to_string(),
+                &format!("ZFS pool ({}):", name),
+                total_bytes,
+                used_bytes,
+            ))
+        })
+        .collect()
+}
+
+/// Btrfs mount points from `/proc/mounts`, once per device.
+pub(super) fn parse_btrfs_mounts(content: &str) -> Vec<String> {
+    let mut seen_sources = HashSet::new();
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            match fields.as_slice() {
+                [source, mount_path, "btrfs", ..] if seen_sources.insert(*source) => {
+                    Some(mount_path.to_string())
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn probe_btrfs_usage(mount_path: &str) -> Result<(u64, u64), String> {
+    let output = run_command_with_timeout(
+        "btrfs",
+        &["filesystem", "usage", "-b", mount_path],
+        &[("LC_ALL", "C")],
+        STORAGE_POOL_TIMEOUT_MS,
+    )?;
+    if !output.status.success() {
+        return Err(format!(
+            "'btrfs filesystem usage {}' exited with {}",
+            mount_path, output.status
+        ));
+    }
+    parse_btrfs_usage(&output.stdout)
+        .ok_or_else(|| format!("'btrfs filesystem usage {}' printed no usage", mount_path))
+}
+
+/// Used and total bytes from the `Overall:` block of
+/// `btrfs filesystem usage -b`. `Used` counts every copy, so it is divided
+/// by the data ratio; the total is that plus `Free (estimated)`.
+pub(super) fn parse_btrfs_usage(output: &str) -> Option<(u64, u64)> {
+    let field = |key: &str| {
+        output.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix(key)?
+                .split_whitespace()
+                .next()?
+                .parse::<f64>()
+                .ok()
+        })
+    };
+    let ratio = field("Data ratio:")
+        .filter(|ratio| *ratio > 0.0)
+        .unwrap_or(1.0);
+    let used = (field("Used:")? / ratio).round() as u64;
+    let free = field("Free (estimated):")? as u64;
+    Some((used, used.saturating_add(free)))
+}
+
+/// Asks the Docker API socket first, then the `docker` or `podman` CLI;
+/// `None` when no runtime is installed.
+fn probe_container_counts() -> Result<Option<(ContainerCounts, String)>, String> {
+    let socket_path = env::var("DOCKER_HOST")
+        .ok()
+        .and_then(|host| host.strip_prefix("unix://").map(str::to_string))
+        .unwrap_or_else(|| DOCKER_SOCKET_PATH.to_string());
+    if Path::new(&socket_path).exists() {
+        match query_unix_socket_http(&socket_path, "/containers/json?all=1") {
+            Ok(body) => {
+                if let Some(counts) = parse_docker_containers_json(&body) {
+                    return Ok(Some((counts, socket_path)));
+                }
+                debug::log(format_args!(
+                    "{}: unexpected /containers/json body",
+                    socket_path
+                ));
+            }
+            Err(err) => debug::log(format_args!("{}: {}", socket_path, err)),
+        }
+    }
+
+    let Some(runtime) = ["docker", "podman"]
+        .into_iter()
+        .find(|runtime| command_exists(runtime))
+    else {
+        return Ok(None);
+    };
+    let output = run_command_with_timeout(
+        runtime,
+        &["ps", "--all", "--format", "{{.State}}"],
+        &[("LC_ALL", "C")],
+        CONTAINER_STORAGE_TIMEOUT_MS,
+    )?;
+    if !output.status.success() {
+        return Err(format!("'{} ps' exited with {}", runtime, output.status));
+    }
+    Ok(Some((
+        count_container_states(&output.stdout),
+        format!("{} ps", runtime),
+    )))
+}
+
+/// Counts the `State` of each entry in a Docker `/containers/json` reply.
+pub(super) fn parse_docker_containers_json(body: &str) -> Option<ContainerCounts> {
+    let containers = serde_json::from_str::<serde_json::Value>(body).ok()?;
+    let containers = containers.as_array()?;
+    Some(ContainerCounts {
+        running: containers
+            .iter()
+            .filter(|container| {
+                container.get("State").and_then(serde_json::Value::as_str) == Some("running")
+            })
+            .count(),
+        total: containers.len(),
+    })
+}
+
+/// Counts one `{{.State}}` line per container from `docker ps` or `podman ps`.
+pub(super) fn count_container_states(output: &str) -> ContainerCounts {
+    let states = output
+        .lines()
+        .map(str::trim)
+        .filter(|state| !state.is_empty())
+        .collect::<Vec<_>>();
+    ContainerCounts {
+        running: states
+            .iter()
+            .filter(|state| state.eq_ignore_ascii_case("running"))
+            .count(),
+        total: states.len(),
+    }
+}
+
+/// Sends a bare HTTP/1.0 GET over a Unix socket and returns the body of a 200 reply.
+fn query_unix_socket_http(socket_path: &str, request_path: &str) -> Result<String, String> {
+    let timeout = Some(Duration::from_millis(CONTAINER_STORAGE_TIMEOUT_MS));
+    let mut stream = UnixStream::connect(socket_path).map_err(|err| err.to_string())?;
+    stream
+        .set_read_timeout(timeout)
+        .and_then(|_| stream.set_write_timeout(timeout))
+        .map_err(|err| err.to_string())?;
+    write!(
+        stream,
+        "GET {} HTTP/1.0\r\nHost: localhost\r\n\r\n",
+        request_path
+    )
+    .map_err(|err| err.to_string())?;
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|err| err.to_string())?;
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| "malformed HTTP response".to_string())?;
+    let status = head.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(format!("unexpected response '{}'", status));
+    }
+    Ok(body.to_string())
+}
+
+/// Sums the Docker `/system/df` reply. Images count as reclaimable when no
+/// container uses them; the rest follows `docker system df`.
+pub(super) fn parse_docker_system_df_json(body: &str) -> Option<ContainerStorage> {
+    let value = serde_json::from_str::<serde_json::Value>(body).ok()?;
+    let list = |key: &str| {
+        value
+            .get(key)
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let size = |item: &serde_json::Value, key: &str| {
+        item.get(key)
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(0)
+            .max(0) as u64
+    };
+
+    let images = list("Images");
+    let image_total = value
+        .get("LayersSize")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or_else(|| images.iter().map(|image| size(image, "Size")).sum());
+    let image_in_use = images
+        .iter()
+        .filter(|image| size(image, "Containers") > 0)
+        .map(|image| size(image, "Size").saturating_sub(size(image, "SharedSize")))
+        .sum::<u64>();
+
+    let containers = list("Containers");
+    let container_total = containers.iter().map(|c| size(c, "SizeRw")).sum::<u64>();
+    let container_idle = containers
+        .iter()
+        .filter(|c| c.get("State").and_then(serde_json::Value::as_str) != Some("running"))
+        .map(|c| size(c, "SizeRw"))
+        .sum::<u64>();
+
+    let volumes = list("Volumes");
+    let volume_usage = |volume: &serde_json::Value, key: &str| {
+        volume.get("UsageData").map_or(0, |usage| size(usage, key))
+    };
+    let volume_total = volumes.iter().map(|v| volume_usage(v, "Size")).sum::<u64>();
+    let volume_unused = volumes
+        .iter()
+        .filter(|v| volume_usage(v, "RefCount") == 0)
+        .map(|v| volume_usage(v, "Size"))
+        .sum::<u64>();
+
+    let cache = list("BuildCache");
+    let cache_total = cache.iter().map(|entry| size(entry, "Size")).sum::<u64>();
+    let cache_idle = cache
+        .iter()
+        .filter(|entry| entry.get("InUse").and_then(serde_json::Value::as_bool) != Some(true))
+        .map(|entry| size(entry, "Size"))
+        .sum::<u64>();
+
+    Some(ContainerStorage {
+        total_bytes: image_total + container_total + volume_total + cache_total,
+        reclaimable_bytes: image_total.saturating_sub(image_in_use)
+            + container_idle
+            + volume_unused
+            + cache_idle,
+    })
+}
+
+/// Sums `Type<TAB>Size<TAB>Reclaimable` rows from `docker|podman system df`.
+pub(super) fn parse_system_df_table(output: &str) -> Option<ContainerStorage> {
+    let mut storage = None;
+    for line in output.lines() {
+        let mut fields = line.split('\t').skip(1);
+        let (Some(size), Some(reclaimable)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let reclaimable = reclaimable.split_whitespace().next().unwrap_or_default();
+        let (Some(size), Some(reclaimable)) = (parse_si_size(size), parse_si_size(reclaimable))
+        else {
+            continue;
+        };
+        let total = storage.get_or_insert(ContainerStorage {
+            total_bytes: 0,
+            reclaimable_bytes: 0,
+        });
+        total.total_bytes += size;
+        total.reclaimable_bytes += reclaimable;
+    }
+    storage
+}
+
+/// Parses the decimal sizes container CLIs print, such as `42.1GB` or `512kB`.
+pub(super) fn parse_si_size(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(value.len());
+    let number = value[..split].parse::<f64>().ok()?;
+    let scale = match value[split..].trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1.0,
+        "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        "PB" => 1e15,
+        _ => return None,
+    };
+    Some((number * scale).round() as u64)
+}
+
+/// Lists interfaces backed by a device, skipping loopback, bridges and veth pairs.
+pub(super) fn read_network_links(sys_class_net: &Path) -> Vec<NetworkLink> {
+    let Ok(entries) = fs::read_dir(sys_class_net) else {
+        return Vec::new();
+    };
+    let mut links = entries
+        .flatten()
+        .filter_map(|entry| {
+            let iface = entry.file_name().to_string_lossy().into_owned();
+            let dir = entry.path();
+            if is_virtual_interface_name(&iface) || !dir.join("device").exists() {
+                return None;
+            }
+            let read = |name: &str| {
+                read_first_line(&dir.join(name).to_string_lossy()).filter(|v| !v.is_empty())
+            };
+            Some(NetworkLink {
+                operstate: read("operstate").unwrap_or_else(|| "unknown".to_string()),
+                speed_mbps: read("speed").and_then(|speed| parse_link_speed(&speed)),
+                duplex: read("duplex").filter(|duplex| duplex != "unknown"),
+                iface,
+            })
+        })
+        .collect::<Vec<_>>();
+    links.sort_by(|a, b| a.iface.cmp(&b.iface));
+    links
+}
+
+/// Thermal zone types and hwmon chip names that belong to the CPU package.
+const CPU_SENSOR_NAMES: &[&str] = &[
+    "x86_pkg_temp",
+    "coretemp",
+    "k10temp",
+    "zenpower",
+    "cpu-thermal",
+    "cpu_thermal",
+    "soc_thermal",
+];
+
+/// Reads `thermal/thermal_zone*/temp` and `hwmon/hwmon*/temp1_input` under
+/// `sys_class` and returns the hottest CPU sensor, or the hottest sensor of
+/// any kind when none is recognisably the CPU.
+pub(super) fn read_cpu_temperature(sys_class: &Path) -> Option<f64> {
+    let sources = [
+        ("thermal", "type", "temp"),
+        ("hwmon", "name", "temp1_input"),
+    ];
+    let mut readings = Vec::new();
+    for (class, name_file, temp_file) in sources {
+        let Ok(entries) = fs::read_dir(sys_class.join(class)) else {
+            continue;
+        };
+        for dir in entries.flatten().map(|entry| entry.path()) {
+            let Some(celsius) = fs::read_to_string(dir.join(temp_file))
+                .ok()
+                .and_then(|raw| parse_temperature(&raw))
+            else {
+                continue;
+            };
+            let name = read_first_line(&dir.join(name_file).to_string_lossy()).unwrap_or_default();
+            readings.push((CPU_SENSOR_NAMES.contains(&name.as_str()), celsius));
+        }
+    }
+    let hottest = |cpu_only: bool| {
+        readings
+            .iter()
+            .filter(|(is_cpu, _)| *is_cpu || !cpu_only)
+            .map(|(_, celsius)| *celsius)
+            .reduce(f64::max)
+    };
+    hottest(true).or_else(|| hottest(false))
+}
+
+/// Converts a sysfs reading in millidegrees Celsius; values outside
+/// -50..=150 °C come from absent or broken sensors and are dropped.
+pub(super) fn parse_temperature(raw: &str) -> Option<f64> {
+    let celsius = raw.trim().parse::<i64>().ok()? as f64 / 1000.0;
+    (-50.0..=150.0).contains(&celsius).then_some(celsius)
 }
 
-pub(super) fn parse_lastlog_output(output: &str) -> Option<Option<ParsedLastLoginRecord>> {
-    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
-    let header = lines.next()?;
-    let data = lines.next()?;
+fn is_virtual_interface_name(iface: &str) -> bool {
+    iface == "lo"
+        || [
+            "veth", "docker", "br-", "virbr", "cni", "flannel", "tun", "tap",
+        ]
+        .iter()
+        .any(|prefix| iface.starts_with(prefix))
+}
 
-    if data.contains("**Never logged in**") {
-        return Some(None);
-    }
+/// `speed` reads `-1` or fails outright while the link is down.
+pub(super) fn parse_link_speed(content: &str) -> Option<u32> {
+    content
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|speed| *speed > 0)
+}
 
-    let port_start = header.find("Port")?;
-    let from_start = header.find("From")?;
-    let latest_start = header.find("Latest")?;
-    if !(port_start < from_start && from_start < latest_start) {
-        return None;
+fn read_mac_status() -> MacStatus {
+    let lsm = fs::read_to_string("/sys/kernel/security/lsm").unwrap_or_default();
+    let active = |name: &str| lsm.trim().split(',').any(|entry| entry == name);
+
+    if active("selinux") || Path::new("/sys/fs/selinux").is_dir() {
+        return MacStatus::SeLinux(parse_selinux_enforce(
+            fs::read_to_string("/sys/fs/selinux/enforce")
+                .ok()
+                .as_deref(),
+        ));
+    }
+    if let Some(enabled) = read_first_line("/sys/module/apparmor/parameters/enabled") {
+        return MacStatus::AppArmor {
+            enabled: active("apparmor") || enabled == "Y",
+            profiles: fs::read_to_string("/sys/kernel/security/apparmor/profiles")
+                .ok()
+                .map(|content| count_apparmor_profiles(&content)),
+        };
+    }
+    if Path::new("/etc/selinux/config").is_file() {
+        return MacStatus::SeLinux(SeLinuxMode::Disabled);
     }
+    MacStatus::None
+}
 
-    let via = normalize_detail_field(&slice_column(data, port_start, from_start));
-    let from = normalize_detail_field(&slice_column(data, from_start, latest_start));
-    let latest = data.get(latest_start..)?.trim();
-    if latest.is_empty() {
-        return None;
+/// Maps `/sys/fs/selinux/enforce`; a missing file means selinuxfs is not mounted.
+pub(super) fn parse_selinux_enforce(content: Option<&str>) -> SeLinuxMode {
+    match content.map(str::trim) {
+        Some("1") => SeLinuxMode::Enforcing,
+        Some("0") => SeLinuxMode::Permissive,
+        _ => SeLinuxMode::Disabled,
     }
+}
 
-    Some(Some(ParsedLastLoginRecord {
-        when: latest.to_string(),
-        from,
-        via,
-    }))
+/// Counts `(enforce)` and `(complain)` entries in the AppArmor profile list.
+pub(super) fn count_apparmor_profiles(content: &str) -> (usize, usize) {
+    content
+        .lines()
+        .fold((0, 0), |(enforce, complain), line| match line.trim_end() {
+            line if line.ends_with("(enforce)") => (enforce + 1, complain),
+            line if line.ends_with("(complain)") => (enforce, complain + 1),
+            _ => (enforce, complain),
+        })
 }
 
-pub(super) fn parse_lastb_output(
-    output: &str,
-    now: DateTime<Local>,
-) -> Option<Vec<FailedLoginEvent>> {
-    let entries = output
+/// Maps UIDs to login names from `/etc/passwd` content.
+pub(super) fn parse_passwd_names(content: &str) -> HashMap<u32, String> {
+    content
         .lines()
-        .map(str::trim)
-        .filter(|line| !line.is_empty() && !line.starts_with("btmp begins"))
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?.trim();
+            let uid = fields.nth(1)?.parse::<u32>().ok()?;
+            (!name.is_empty() && !name.starts_with('#')).then(|| (uid, name.to_string()))
+        })
+        .collect()
+}
+
+/// Orders owners by process count, then name; unknown UIDs are shown as numbers.
+pub(super) fn rank_top_users(
+    counts: &HashMap<u32, usize>,
+    names: &HashMap<u32, String>,
+    limit: usize,
+) -> Vec<UserProcessCount> {
+    let mut users = counts
+        .iter()
+        .map(|(uid, processes)| UserProcessCount {
+            user: names.get(uid).cloned().unwrap_or_else(|| uid.to_string()),
+            processes: *processes,
+        })
         .collect::<Vec<_>>();
+    users.sort_by(|a, b| {
+        b.processes
+            .cmp(&a.processes)
+            .then_with(|| a.user.cmp(&b.user))
+    });
+    users.truncate(limit);
+    users
+}
 
-    if entries.is_empty() {
-        return Some(Vec::new());
-    }
+/// `None` when the kernel has no PSI, so the file is missing or unreadable.
+fn read_pressure(resource: &str) -> Option<PressureAverages> {
+    let content = fs::read_to_string(Path::new("/proc/pressure").join(resource)).ok()?;
+    parse_pressure(&content)
+}
 
-    let mut parsed = Vec::new();
-    for line in entries {
-        let mut parts = line.split_whitespace();
-        let _user = parts.next()?;
-        let via = normalize_detail_field(parts.next().unwrap_or_default());
-        let from = normalize_detail_field(parts.next().unwrap_or_default());
-        let when_tokens = parts.take(4).collect::<Vec<_>>();
-        if when_tokens.len() < 4 {
-            return None;
+/// Reads the `some avg10=... avg60=... avg300=... total=...` line of a
+/// `/proc/pressure/*` file; the `full` line is ignored.
+pub(super) fn parse_pressure(content: &str) -> Option<PressureAverages> {
+    let line = content
+        .lines()
+        .find_map(|line| line.strip_prefix("some "))?;
+    let average = |key: &str| {
+        line.split_whitespace()
+            .find_map(|field| field.strip_prefix(key)?.strip_prefix('='))?
+            .parse::<f64>()
+            .ok()
+    };
+    Some(PressureAverages {
+        avg10: average("avg10")?,
+        avg60: average("avg60")?,
+        avg300: average("avg300")?,
+    })
+}
+
+/// Sums the receive and transmit byte counters of `/proc/net/dev` over every
+/// interface except loopback.
+pub(super) fn parse_net_throughput(content: &str) -> Option<(u64, u64)> {
+    let mut totals = None;
+    for line in content.lines() {
+        let Some((iface, counters)) = line.split_once(':') else {
+            continue;
+        };
+        if iface.trim() == "lo" {
+            continue;
         }
+        let fields = counters.split_whitespace().collect::<Vec<_>>();
+        // 8 receive columns come first, so transmit bytes is the ninth.
+        let (Some(rx), Some(tx)) = (
+            fields.first().and_then(|value| value.parse::<u64>().ok()),
+            fields.get(8).and_then(|value| value.parse::<u64>().ok()),
+        ) else {
+            continue;
+        };
+        let (rx_total, tx_total) = totals.get_or_insert((0u64, 0u64));
+        *rx_total = rx_total.saturating_add(rx);
+        *tx_total = tx_total.saturating_add(tx);
+    }
+    totals
+}
 
-        let when_raw = when_tokens.join(" ");
-        parsed.push(FailedLoginEvent {
-            when: parse_lastb_timestamp(&when_raw, now),
-            when_raw,
-            from,
-            via,
+/// Totals since boot, or the rate between two reads `sample_ms` apart.
+fn probe_net_throughput(sample_ms: Option<u64>) -> Result<NetThroughput, String> {
+    let read_counters = || {
+        let content = fs::read_to_string("/proc/net/dev")
+            .map_err(|err| format!("failed to read /proc/net/dev: {}", err))?;
+        parse_net_throughput(&content)
+            .ok_or_else(|| "/proc/net/dev lists no interface besides lo".to_string())
+    };
+    let Some(sample_ms) = sample_ms else {
+        let (rx_bytes, tx_bytes) = read_counters()?;
+        return Ok(NetThroughput {
+            rx_bytes,
+            tx_bytes,
+            per_sec: false,
         });
+    };
+    let started = Instant::now();
+    let before = read_counters()?;
+    thread::sleep(Duration::from_millis(sample_ms));
+    let after = read_counters()?;
+    let elapsed_ms = started.elapsed().as_millis().max(1) as u64;
+    let rate = |from: u64, to: u64| to.saturating_sub(from) * 1000 / elapsed_ms;
+    Ok(NetThroughput {
+        rx_bytes: rate(before.0, after.0),
+        tx_bytes: rate(before.1, after.1),
+        per_sec: true,
+    })
+}
+
+/// Reads the cumulative `pswpin`/`pswpout` page counters from `/proc/vmstat`.
+pub(super) fn parse_vmstat_swap_pages(content: &str) -> Option<(u64, u64)> {
+    let mut pages_in = None;
+    let mut pages_out = None;
+    for line in content.lines() {
+        match line.split_once(' ') {
+            Some(("pswpin", value)) => pages_in = value.trim().parse().ok(),
+            Some(("pswpout", value)) => pages_out = value.trim().parse().ok(),
+            _ => {}
+        }
     }
+    Some((pages_in?, pages_out?))
+}
 
-    Some(parsed)
+pub(super) fn swap_activity_between(
+    before: (u64, u64),
+    after: (u64, u64),
+    elapsed: Duration,
+    page_size: u64,
+) -> SwapActivity {
+    let elapsed_ms = elapsed.as_millis().max(1) as u64;
+    let rate = |from: u64, to: u64| to.saturating_sub(from) * page_size * 1000 / elapsed_ms;
+    SwapActivity {
+        in_bytes_per_sec: rate(before.0, after.0),
+        out_bytes_per_sec: rate(before.1, after.1),
+    }
 }
 
-pub(super) fn parse_apt_upgradable_output(output: &str) -> usize {
-    output
+fn sample_swap_activity() -> Result<SwapActivity, String> {
+    let read_counters = || {
+        let content = fs::read_to_string("/proc/vmstat")
+            .map_err(|err| format!("failed to read /proc/vmstat: {}", err))?;
+        parse_vmstat_swap_pages(&content)
+            .ok_or_else(|| "/proc/vmstat has no pswpin/pswpout counters".to_string())
+    };
+    let started = Instant::now();
+    let before = read_counters()?;
+    thread::sleep(Duration::from_millis(SWAP_ACTIVITY_SAMPLE_MS));
+    let after = read_counters()?;
+    Ok(swap_activity_between(
+        before,
+        after,
+        started.elapsed(),
+        rustix::param::page_size() as u64,
+    ))
+}
+
+/// Reads (idle, total) jiffies from the aggregate `cpu` line of `/proc/stat`.
+/// Idle counts `iowait`; `guest` time is already part of `user`, so only the
+/// first eight columns are summed.
+pub(super) fn parse_proc_stat_cpu_times(content: &str) -> Option<(u64, u64)> {
+    let line = content.lines().find(|line| line.starts_with("cpu "))?;
+    let fields = line
+        .split_whitespace()
+        .skip(1)
+        .take(8)
+        .map(|value| value.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if fields.len() < 4 {
+        return None;
+    }
+    let idle = fields[3] + fields.get(4).copied().unwrap_or(0);
+    Some((idle, fields.iter().sum()))
+}
+
+/// Busy percentage between two samples; `None` when no time elapsed.
+pub(super) fn cpu_busy_percent(before: (u64, u64), after: (u64, u64)) -> Option<f64> {
+    let total = after.1.checked_sub(before.1).filter(|total| *total > 0)?;
+    let idle = after.0.saturating_sub(before.0).min(total);
+    Some((total - idle) as f64 * 100.0 / total as f64)
+}
+
+fn sample_cpu_usage(sample_ms: u64) -> Result<f64, String> {
+    let read_times = || {
+        let content = fs::read_to_string("/proc/stat")
+            .map_err(|err| format!("failed to read /proc/stat: {}", err))?;
+        parse_proc_stat_cpu_times(&content)
+            .ok_or_else(|| "/proc/stat has no aggregate cpu line".to_string())
+    };
+    let before = read_times()?;
+    thread::sleep(Duration::from_millis(sample_ms));
+    let after = read_times()?;
+    cpu_busy_percent(before, after)
+        .ok_or_else(|| "no CPU time elapsed between /proc/stat samples".to_string())
+}
+
+/// Parses `authorized_keys` content, skipping any leading key options.
+pub(super) fn parse_authorized_keys(content: &str) -> Vec<AuthorizedKey> {
+    content
         .lines()
         .map(str::trim)
-        .filter(|line| line.contains("[upgradable from:"))
-        .count()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let tokens = line.split_whitespace().collect::<Vec<_>>();
+            tokens.windows(2).enumerate().find_map(|(idx, pair)| {
+                if !is_ssh_key_type(pair[0]) {
+                    return None;
+                }
+                let blob = STANDARD.decode(pair[1]).ok()?;
+                Some(AuthorizedKey {
+                    key_type: pair[0].to_string(),
+                    fingerprint: format!(
+                        "SHA256:{}",
+                        STANDARD_NO_PAD.encode(Sha256::digest(&blob))
+                    ),
+                    comment: tokens[idx + 2..].join(" "),
+                })
+            })
+        })
+        .collect()
 }
 
-pub(super) fn parse_dnf_check_update_output(output: &str) -> usize {
-    output
+fn is_ssh_key_type(token: &str) -> bool {
+    ["ssh-", "ecdsa-sha2-", "sk-ssh-", "sk-ecdsa-sha2-"]
+        .iter()
+        .any(|prefix| token.starts_with(prefix))
+}
+
+/// Reads the current user's `authorized_keys`, plus root's when it is a
+/// different file and readable. A missing file means no keys.
+fn probe_authorized_keys() -> (Vec<AuthorizedKeysFile>, Vec<(String, String)>) {
+    let own_path = expand_tilde("~/.ssh/authorized_keys");
+    let root_path = Path::new("/root/.ssh/authorized_keys");
+    let mut files = Vec::new();
+    let mut failures = Vec::new();
+
+    match fs::read_to_string(&own_path) {
+        Ok(content) => files.push(AuthorizedKeysFile {
+            owner: None,
+            keys: parse_authorized_keys(&content),
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            files.push(AuthorizedKeysFile {
+                owner: None,
+                keys: Vec::new(),
+            });
+        }
+        Err(err) => failures.push((own_path.display().to_string(), err.to_string())),
+    }
+
+    if own_path != root_path
+        && let Ok(content) = fs::read_to_string(root_path)
+    {
+        files.push(AuthorizedKeysFile {
+            owner: Some("root".to_string()),
+            keys: parse_authorized_keys(&content),
+        });
+    }
+
+    (files, failures)
+}
+
+pub(super) fn parse_recent_logins_content(content: &str) -> Vec<RecentLogin> {
+    content
         .lines()
-        .map(str::trim)
-        .filter(|line| {
-            !line.is_empty()
-                && !line.starts_with("Last metadata expiration check:")
-                && line.split_whitespace().count() >= 3
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(RecentLogin {
+                when_secs: fields.next()?.parse().ok()?,
+                user: fields.next()?.to_string(),
+                from: fields.next()?.to_string(),
+            })
         })
-        .count()
+        .collect()
+}
+
+/// Appends `current` to the state file and returns up to `limit` earlier
+/// sessions, newest first. The file is locked for the read-modify-write.
+pub(super) fn record_recent_login(
+    path: &Path,
+    limit: usize,
+    current: RecentLogin,
+) -> Result<Vec<RecentLogin>, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {}", parent.display(), err))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|err| format!("failed to open {}: {}", path.display(), err))?;
+    file.lock()
+        .map_err(|err| format!("failed to lock {}: {}", path.display(), err))?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    let mut events = parse_recent_logins_content(&content);
+    let previous = events.iter().rev().take(limit).cloned().collect::<Vec<_>>();
+
+    events.push(current);
+    let keep_from = events.len().saturating_sub(limit + 1);
+    let serialized = events[keep_from..]
+        .iter()
+        .map(|event| format!("{}\t{}\t{}\n", event.when_secs, event.user, event.from))
+        .collect::<String>();
+
+    file.set_len(0)
+        .and_then(|_| file.seek(SeekFrom::Start(0)))
+        .and_then(|_| file.write_all(serialized.as_bytes()))
+        .map_err(|err| format!("failed to write {}: {}", path.display(), err))?;
+
+    Ok(previous)
+}
+
+/// The newest `limit` sessions in the state file, leaving it untouched; a
+/// missing file has none.
+pub(super) fn read_recent_logins(path: &Path, limit: usize) -> Result<Vec<RecentLogin>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(format!("failed to read {}: {}", path.display(), err)),
+    };
+    Ok(parse_recent_logins_content(&content)
+        .into_iter()
+        .rev()
+        .take(limit)
+        .collect())
 }
 
 pub(super) fn format_uptime(mut secs: u64) -> String {
@@ -331,6 +2154,26 @@ pub(super) fn format_uptime(mut secs: u64) -> String {
     }
 }
 
+/// `uptime_format = "compact"`: `127d 4h 13m`, or `13m 42s` under an hour.
+/// Zero units are left out, so round values read `2d` or `1h`.
+pub(super) fn format_uptime_compact(secs: u64) -> String {
+    let days = secs / 86400;
+    let hours = secs % 86400 / 3600;
+    let minutes = secs % 3600 / 60;
+    let seconds = if secs < 3600 { secs % 60 } else { 0 };
+    let parts = [(days, "d"), (hours, "h"), (minutes, "m"), (seconds, "s")]
+        .into_iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(any(test, not(target_os = "macos")))]
 pub(super) fn parse_os_release_content(content: &str) -> Option<(String, String)> {
     let mut os_name: Option<String> = None;
     let mut os_version: Option<String> = None;
@@ -348,6 +2191,7 @@ pub(super) fn parse_os_release_content(content: &str) -> Option<(String, String)
     }
 }
 
+#[cfg(any(test, not(target_os = "macos")))]
 pub(super) fn parse_redhat_release_content(content: &str) -> Option<(String, String)> {
     let line = content.trim();
     let needle = " release ";
@@ -357,6 +2201,7 @@ pub(super) fn parse_redhat_release_content(content: &str) -> Option<(String, Str
     Some((os_name.to_string(), ver_str.to_string()))
 }
 
+#[cfg(any(test, not(target_os = "macos")))]
 pub(super) fn parse_meminfo_content(content: &str) -> (u64, u64, u64, u64) {
     let mut mem_total = 0;
     let mut mem_available = 0;
@@ -387,16 +2232,31 @@ pub(super) fn parse_meminfo_content(content: &str) -> (u64, u64, u64, u64) {
     )
 }
 
-pub(super) fn parse_cpuinfo_content(content: &str) -> (String, usize) {
+/// Returns the brand, the logical CPU count and, when every processor lists
+/// `physical id` and `core id`, the number of distinct physical cores.
+#[cfg(any(test, not(target_os = "macos")))]
+pub(super) fn parse_cpuinfo_content(reader: impl BufRead) -> (String, usize, Option<usize>) {
     let mut brand = "Unknown CPU".to_string();
     let mut core_count = 0;
 
     let mut cpu_implementer = String::new();
     let mut cpu_part = String::new();
+    let mut physical_cores = HashSet::new();
+    let mut topology_complete = true;
+    let mut current = (None, None);
 
-    for line in content.lines() {
+    for line in reader.lines().map_while(Result::ok) {
+        let value = || line.split(':').nth(1).map(str::trim);
         if line.starts_with("processor") {
+            if core_count > 0 {
+                topology_complete &= record_physical_core(&mut physical_cores, current);
+            }
+            current = (None, None);
             core_count += 1;
+        } else if line.starts_with("physical id") {
+            current.0 = value().map(str::to_string);
+        } else if line.starts_with("core id") {
+            current.1 = value().map(str::to_string);
         } else if let Some(model_str) = line.strip_prefix("model name") {
             let parts: Vec<_> = model_str.split(':').collect();
             if parts.len() > 1 && brand == "Unknown CPU" {
@@ -429,7 +2289,121 @@ pub(super) fn parse_cpuinfo_content(content: &str) -> (String, usize) {
         }
     }
 
-    (brand, core_count)
+    if core_count > 0 {
+        topology_complete &= record_physical_core(&mut physical_cores, current);
+    }
+    let physical =
+        (topology_complete && !physical_cores.is_empty()).then_some(physical_cores.len());
+    (brand, core_count, physical)
+}
+
+fn record_physical_core(
+    cores: &mut HashSet<(String, String)>,
+    ids: (Option<String>, Option<String>),
+) -> bool {
+    match ids {
+        (Some(package), Some(core)) => {
+            cores.insert((package, core));
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Reads sockets, cores and threads from `lscpu`; `None` when a field is missing or `-`.
+pub(super) fn parse_lscpu_output(output: &str) -> Option<CpuTopology> {
+    let mut sockets = None;
+    let mut cores_per_socket = None;
+    let mut threads_per_core = None;
+    let mut numa_nodes = None;
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().parse::<usize>().ok().filter(|n| *n > 0);
+        match key.trim() {
+            "Socket(s)" => sockets = value,
+            "Core(s) per socket" => cores_per_socket = value,
+            "Thread(s) per core" => threads_per_core = value,
+            "NUMA node(s)" => numa_nodes = value,
+            _ => {}
+        }
+    }
+
+    Some(CpuTopology {
+        sockets: sockets?,
+        cores_per_socket: cores_per_socket?,
+        threads_per_core: threads_per_core?,
+        numa_nodes: numa_nodes.unwrap_or(1),
+    })
+}
+
+/// Counts `Killed process <pid> (<name>)` kernel messages and keeps the last victim.
+pub(super) fn parse_oom_kill_events(log: &str) -> OomSummary {
+    let mut summary = OomSummary {
+        count: 0,
+        last_victim: None,
+    };
+    for line in log.lines() {
+        let Some((_, rest)) = line.split_once("Killed process ") else {
+            continue;
+        };
+        if !line.to_ascii_lowercase().contains("out of memory") {
+            continue;
+        }
+        summary.count += 1;
+        summary.last_victim = rest
+            .split_once('(')
+            .and_then(|(_, name)| name.split_once(')'))
+            .map(|(name, _)| name.to_string())
+            .or(summary.last_victim);
+    }
+    summary
+}
+
+/// Parses a cgroup memory limit in bytes; v2 writes `max` when unlimited.
+pub(super) fn parse_cgroup_memory_bytes(content: &str) -> Option<u64> {
+    content.trim().parse::<u64>().ok()
+}
+
+/// Counts CPUs in a kernel range list such as `0-3,8,10-11`.
+pub(super) fn parse_cpu_range_list(content: &str) -> Option<usize> {
+    let mut count = 0usize;
+    for part in content.trim().split(',').filter(|part| !part.is_empty()) {
+        count += match part.split_once('-') {
+            Some((start, end)) => {
+                let start = start.parse::<usize>().ok()?;
+                let end = end.parse::<usize>().ok()?;
+                end.checked_sub(start)? + 1
+            }
+            None => {
+                part.parse::<usize>().ok()?;
+                1
+            }
+        };
+    }
+    (count > 0).then_some(count)
+}
+
+/// Parses cgroup v2 `cpu.max` (`<quota> <period>` or `max <period>`).
+pub(super) fn parse_cgroup_cpu_max(content: &str) -> Option<f64> {
+    let mut parts = content.split_whitespace();
+    let quota = parts.next()?;
+    let period = parts.next()?.parse::<f64>().ok()?;
+    cpu_quota_ratio(quota.parse::<f64>().ok()?, period)
+}
+
+/// Converts cgroup v1 `cpu.cfs_quota_us`/`cpu.cfs_period_us`; `-1` means no limit.
+pub(super) fn parse_cgroup_cfs_quota(quota: &str, period: &str) -> Option<f64> {
+    cpu_quota_ratio(
+        quota.trim().parse::<f64>().ok()?,
+        period.trim().parse::<f64>().ok()?,
+    )
+}
+
+fn cpu_quota_ratio(quota: f64, period: f64) -> Option<f64> {
+    (quota > 0.0 && period > 0.0).then(|| quota / period)
 }
 
 pub(super) fn detect_virtualization_from_cgroup(content: &str) -> Option<String> {
@@ -442,20 +2416,56 @@ pub(super) fn detect_virtualization_from_cgroup(content: &str) -> Option<String>
     }
 }
 
-pub(super) fn parse_uptime_content(line: &str) -> Option<String> {
+/// Returns whole seconds since boot from `/proc/uptime` content.
+#[cfg(any(test, not(target_os = "macos")))]
+pub(super) fn parse_uptime_content(line: &str) -> Option<u64> {
     let parts: Vec<_> = line.split_whitespace().collect();
-    let total_seconds = parts.first()?.parse::<f64>().ok()? as u64;
-
-    Some(format_uptime(total_seconds))
+    Some(parts.first()?.parse::<f64>().ok()? as u64)
 }
 
+/// The client address from `SSH_CONNECTION`. IPv6 clients are printed in
+/// their short form without brackets or zone, and IPv4-mapped ones as plain
+/// IPv4; anything unparsable is kept as written.
 pub(super) fn parse_ssh_connection_ip(ssh_connection: &str) -> String {
-    ssh_connection
+    let Some(token) = ssh_connection.split_whitespace().next() else {
+        return "unknown".to_string();
+    };
+    let address = token.trim_start_matches('[').trim_end_matches(']');
+    let address = address
+        .split_once('%')
+        .map_or(address, |(address, _)| address);
+    match address.parse::<IpAddr>() {
+        Ok(IpAddr::V6(v6)) => match v6.to_ipv4_mapped() {
+            Some(v4) => v4.to_string(),
+            None => v6.to_string(),
+        },
+        Ok(ip) => ip.to_string(),
+        Err(_) => token.to_string(),
+    }
+}
+
+/// Reverse DNS through `getent hosts`, so NSS settings and `/etc/hosts` are
+/// honored and a slow resolver is cut off after a short timeout.
+fn resolve_client_host(ip: &str) -> Option<String> {
+    let output =
+        run_command_with_timeout("getent", &["hosts", ip], &[], REVERSE_DNS_TIMEOUT_MS).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_getent_hosts_name(&output.stdout, ip)
+}
+
+/// Takes the canonical name from `getent hosts` output such as
+/// `10.0.0.5       build-01.example.com build-01`; a bare echo of the
+/// address is not a name.
+pub(super) fn parse_getent_hosts_name(output: &str, ip: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
         .split_whitespace()
-        .next()
-        .filter(|value| !value.is_empty())
-        .unwrap_or("unknown")
-        .to_string()
+        .nth(1)
+        .filter(|name| *name != ip)
+        .map(str::to_string)
 }
 
 pub(super) fn parse_default_interface_output(stdout: &str) -> Option<String> {
@@ -528,16 +2538,19 @@ fn usage_summary(total_kb: u64, free_kb: u64) -> UsageSummary {
     }
 }
 
-fn parse_uptime() -> Option<String> {
+#[cfg(not(target_os = "macos"))]
+pub(super) fn read_uptime_secs() -> Option<u64> {
     let line = fs::read_to_string("/proc/uptime").ok()?;
     parse_uptime_content(&line)
 }
 
+#[cfg(not(target_os = "macos"))]
 fn parse_load_average() -> Option<String> {
     let content = fs::read_to_string("/proc/loadavg").ok()?;
     parse_loadavg_content(&content)
 }
 
+#[cfg(not(target_os = "macos"))]
 fn get_os_info() -> ((String, String), &'static str) {
     if let Some(result) = parse_redhat_release() {
         return (result, "/etc/redhat-release");
@@ -552,24 +2565,102 @@ fn get_os_info() -> ((String, String), &'static str) {
     )
 }
 
+#[cfg(not(target_os = "macos"))]
 fn parse_redhat_release() -> Option<(String, String)> {
     let content = fs::read_to_string("/etc/redhat-release").ok()?;
     parse_redhat_release_content(&content)
 }
 
+#[cfg(not(target_os = "macos"))]
 fn parse_os_release() -> Option<(String, String)> {
     let content = fs::read_to_string("/etc/os-release").ok()?;
     parse_os_release_content(&content)
 }
 
-fn parse_meminfo() -> (u64, u64, u64, u64) {
+#[cfg(not(target_os = "macos"))]
+pub(super) fn parse_meminfo() -> (u64, u64, u64, u64) {
     let content = fs::read_to_string("/proc/meminfo").unwrap_or_default();
     parse_meminfo_content(&content)
 }
 
-fn parse_cpuinfo() -> (String, usize) {
-    let content = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
-    parse_cpuinfo_content(&content)
+#[cfg(not(target_os = "macos"))]
+pub(super) fn parse_cpuinfo() -> (String, usize, Option<usize>) {
+    match File::open("/proc/cpuinfo") {
+        Ok(file) => parse_cpuinfo_content(BufReader::new(file)),
+        Err(_) => parse_cpuinfo_content(std::io::empty()),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn read_kernel_release() -> Option<String> {
+    read_first_line("/proc/sys/kernel/osrelease")
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(super) fn read_host_name() -> Option<String> {
+    read_first_line("/proc/sys/kernel/hostname")
+}
+
+/// Returns the cgroup memory limit and current usage in kB when a limit is set.
+fn read_cgroup_memory() -> Option<(u64, u64)> {
+    let (limit, current) = [
+        ("/sys/fs/cgroup/memory.max", "/sys/fs/cgroup/memory.current"),
+        (
+            "/sys/fs/cgroup/memory/memory.limit_in_bytes",
+            "/sys/fs/cgroup/memory/memory.usage_in_bytes",
+        ),
+    ]
+    .into_iter()
+    .find_map(|(limit_path, current_path)| {
+        let limit = parse_cgroup_memory_bytes(&read_first_line(limit_path)?)?;
+        let current = parse_cgroup_memory_bytes(&read_first_line(current_path)?)?;
+        Some((limit, current))
+    })?;
+    Some((limit / 1024, current / 1024))
+}
+
+fn read_online_cpu_count() -> Option<usize> {
+    read_first_line("/sys/devices/system/cpu/online").and_then(|line| parse_cpu_range_list(&line))
+}
+
+fn read_cgroup_cpu_quota() -> Option<f64> {
+    if let Some(line) = read_first_line("/sys/fs/cgroup/cpu.max") {
+        return parse_cgroup_cpu_max(&line);
+    }
+    ["/sys/fs/cgroup/cpu", "/sys/fs/cgroup/cpu,cpuacct"]
+        .into_iter()
+        .find_map(|dir| {
+            let quota = read_first_line(&format!("{}/cpu.cfs_quota_us", dir))?;
+            let period = read_first_line(&format!("{}/cpu.cfs_period_us", dir))?;
+            Some(parse_cgroup_cfs_quota(&quota, &period))
+        })
+        .flatten()
+}
+
+fn read_kernel_preemption(kernel_release: &str) -> KernelPreemption {
+    let realtime = is_preempt_rt_kernel(
+        read_first_line("/sys/kernel/realtime").as_deref(),
+        read_first_line("/proc/sys/kernel/version").as_deref(),
+    );
+    let tick_hz = File::open(format!("/boot/config-{}", kernel_release))
+        .ok()
+        .and_then(|file| find_kernel_config_hz(BufReader::new(file).lines().map_while(Result::ok)));
+    KernelPreemption { realtime, tick_hz }
+}
+
+/// `/sys/kernel/realtime` exists only on RT kernels; the `uname -v` banner
+/// (`/proc/sys/kernel/version`) names `PREEMPT_RT` on the rest.
+pub(super) fn is_preempt_rt_kernel(realtime_flag: Option<&str>, version: Option<&str>) -> bool {
+    realtime_flag.is_some_and(|flag| flag.trim() == "1")
+        || version
+            .is_some_and(|version| version.split_whitespace().any(|word| word == "PREEMPT_RT"))
+}
+
+/// Finds `CONFIG_HZ=<n>` in kernel build config lines.
+pub(super) fn find_kernel_config_hz(lines: impl IntoIterator<Item = String>) -> Option<u32> {
+    lines
+        .into_iter()
+        .find_map(|line| line.trim().strip_prefix("CONFIG_HZ=")?.parse().ok())
 }
 
 fn read_first_line(path: &str) -> Option<String> {
@@ -604,26 +2695,22 @@ fn probe_last_login(
         return Err("current user is unknown".to_string());
     }
 
-    let output = run_command_with_timeout(
-        "lastlog",
-        &["-u", user],
-        &[("LC_ALL", "C")],
-        OPTIONAL_PROBE_TIMEOUT_MS,
-    )?;
-
-    if !output.status.success() {
-        let stderr = output.stderr.trim().to_string();
-        let detail = if stderr.is_empty() {
-            output.status.to_string()
-        } else {
-            stderr
+    // wtmp keeps every session, so `last` can skip the one that is running
+    // now; lastlog only keeps the newest login, which usually is this one.
+    let record =
+        match run_login_record_command("last", &["-i", "-w", "-n", "2", user]).and_then(|stdout| {
+            parse_last_output(&stdout).ok_or_else(|| "unexpected 'last' output".to_string())
+        }) {
+            Ok(record) => record,
+            Err(last_err) => run_login_record_command("lastlog", &["-u", user])
+                .and_then(|stdout| {
+                    parse_lastlog_output(&stdout)
+                        .ok_or_else(|| "unexpected 'lastlog' output".to_string())
+                })
+                .map_err(|err| format!("{}; {}", last_err, err))?,
         };
-        return Err(format!("'lastlog -u {}' exited with {}", user, detail));
-    }
 
-    match parse_lastlog_output(&output.stdout)
-        .ok_or_else(|| "unexpected 'lastlog' output".to_string())?
-    {
+    match record {
         Some(record) => Ok(LastLoginInfo::Recorded(enrich_last_login_record(
             record,
             current_source_ip,
@@ -633,6 +2720,26 @@ fn probe_last_login(
     }
 }
 
+fn run_login_record_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let output =
+        run_command_with_timeout(program, args, &[("LC_ALL", "C")], OPTIONAL_PROBE_TIMEOUT_MS)?;
+
+    if !output.status.success() {
+        let stderr = output.stderr.trim().to_string();
+        let detail = if stderr.is_empty() {
+            output.status.to_string()
+        } else {
+            stderr
+        };
+        return Err(format!(
+            "'{}' exited with {}",
+            format_command(program, args),
+            detail
+        ));
+    }
+    Ok(output.stdout)
+}
+
 fn probe_failed_login(
     user: &str,
     current_source_ip: &str,
@@ -674,8 +2781,9 @@ fn enrich_last_login_record(
     now: DateTime<Local>,
 ) -> LastLoginRecord {
     let kind = classify_login_kind(record.via.as_deref(), record.from.as_deref());
-    let age =
-        parse_lastlog_timestamp(&record.when).map(|timestamp| format_relative_age(now, timestamp));
+    let age = parse_lastlog_timestamp(&record.when)
+        .or_else(|| parse_lastb_timestamp(&record.when, now))
+        .map(|timestamp| format_relative_age(now, timestamp));
     let source_relation = compare_sources(record.from.as_deref(), current_source_ip);
 
     LastLoginRecord {
@@ -919,7 +3027,170 @@ fn probe_service_statuses(services: &[String]) -> Result<Vec<RenderedItem>, Stri
     Ok(items)
 }
 
+/// Empty when systemd is not the init system; `/run/systemd/system` only
+/// exists when it is.
+fn probe_failed_units() -> Result<Vec<String>, String> {
+    if !Path::new("/run/systemd/system").is_dir() || !command_exists("systemctl") {
+        return Ok(Vec::new());
+    }
+    let output = run_command_with_timeout(
+        "systemctl",
+        &["--failed", "--no-legend", "--plain", "--full"],
+        &[("LC_ALL", "C")],
+        OPTIONAL_PROBE_TIMEOUT_MS,
+    )?;
+    if !output.status.success() {
+        return Err(format!("systemctl --failed exited with {}", output.status));
+    }
+    Ok(parse_failed_units(&output.stdout))
+}
+
+/// Takes the unit name from each `systemctl --failed --no-legend` row,
+/// dropping the `●` marker older versions print even with `--plain`.
+pub(super) fn parse_failed_units(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .trim_start_matches('●')
+                .split_whitespace()
+                .next()
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Debian flags a pending reboot with a marker file; RHEL answers through
+/// `needs-restarting -r`, which exits 1 when a reboot is needed.
+fn probe_reboot_required() -> (Option<bool>, String) {
+    if Path::new(REBOOT_REQUIRED_PATH).exists() {
+        return (Some(true), REBOOT_REQUIRED_PATH.to_string());
+    }
+    if command_exists("needs-restarting") {
+        let result = run_command_with_timeout(
+            "needs-restarting",
+            &["-r"],
+            &[("LC_ALL", "C")],
+            OPTIONAL_PROBE_TIMEOUT_MS,
+        );
+        return match result.map(|output| output.status.code()) {
+            Ok(Some(0)) => (Some(false), "needs-restarting -r".to_string()),
+            Ok(Some(1)) => (Some(true), "needs-restarting -r".to_string()),
+            Ok(_) => (None, "needs-restarting -r: unexpected exit".to_string()),
+            Err(err) => (None, format!("needs-restarting -r: {}", err)),
+        };
+    }
+    if Path::new("/var/lib/dpkg").is_dir() {
+        return (Some(false), REBOOT_REQUIRED_PATH.to_string());
+    }
+    (None, "not determined".to_string())
+}
+
+/// Debian needs `APT::Periodic::Unattended-Upgrade` and the apt-daily-upgrade
+/// timer; RHEL needs an enabled dnf-automatic timer that actually installs.
+fn probe_auto_updates() -> Option<(bool, String)> {
+    if command_exists("apt") {
+        let mut files = fs::read_dir("/etc/apt/apt.conf.d")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        files.sort();
+        let configured = files
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .filter_map(|content| {
+                parse_apt_periodic_setting(&content, "APT::Periodic::Unattended-Upgrade")
+            })
+            .next_back()
+            .is_some_and(|value| value != "0");
+        let enabled = configured
+            && command_exists("unattended-upgrade")
+            && systemd_unit_enabled("apt-daily-upgrade.timer") != Some(false);
+        return Some((enabled, "unattended-upgrades".to_string()));
+    }
+
+    if command_exists("dnf") {
+        let applies = fs::read_to_string("/etc/dnf/automatic.conf")
+            .map(|content| parse_dnf_automatic_apply_updates(&content))
+            .unwrap_or(false);
+        let enabled = systemd_unit_enabled("dnf-automatic-install.timer") == Some(true)
+            || (applies && systemd_unit_enabled("dnf-automatic.timer") == Some(true));
+        return Some((enabled, "dnf-automatic".to_string()));
+    }
+
+    None
+}
+
+/// `None` when systemctl is missing or cannot answer.
+fn systemd_unit_enabled(unit: &str) -> Option<bool> {
+    let output = run_command_with_timeout(
+        "systemctl",
+        &["is-enabled", unit],
+        &[("LC_ALL", "C")],
+        OPTIONAL_PROBE_TIMEOUT_MS,
+    )
+    .ok()?;
+    match output.stdout.trim() {
+        "enabled" | "static" | "alias" => Some(true),
+        "" => None,
+        _ => Some(false),
+    }
+}
+
+/// Finds `Key "value";` in an apt.conf fragment, ignoring `//` comments.
+pub(super) fn parse_apt_periodic_setting(content: &str, key: &str) -> Option<String> {
+    content
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default().trim())
+        .filter_map(|line| line.strip_prefix(key))
+        .filter_map(|rest| {
+            let value = rest.trim().trim_end_matches(';').trim();
+            value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .map(str::to_string)
+        })
+        .next_back()
+}
+
+/// Reads `apply_updates` from the `[commands]` section of `automatic.conf`.
+pub(super) fn parse_dnf_automatic_apply_updates(content: &str) -> bool {
+    let mut in_commands = false;
+    let mut apply = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_commands = line == "[commands]";
+        } else if in_commands
+            && let Some((key, value)) = line.split_once('=')
+            && key.trim() == "apply_updates"
+        {
+            apply = matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "yes" | "true" | "1" | "on"
+            );
+        }
+    }
+    apply
+}
+
 fn probe_package_updates() -> Result<(String, String), String> {
+    // Ubuntu refreshes this summary from apt hooks, so it is free to read.
+    if let Some(count) = fs::read_to_string(UPDATE_NOTIFIER_PATH)
+        .ok()
+        .and_then(|content| parse_update_notifier_count(&content))
+    {
+        let summary = if count == 0 {
+            "none (apt)".to_string()
+        } else {
+            format!("{} package(s) via apt", count)
+        };
+        return Ok((summary, UPDATE_NOTIFIER_PATH.to_string()));
+    }
+
     if command_exists("apt") {
         let output = run_command_with_timeout(
             "apt",
@@ -947,9 +3218,13 @@ fn probe_package_updates() -> Result<(String, String), String> {
         return Ok((summary, "apt list --upgradable".to_string()));
     }
 
-    if command_exists("dnf") {
+    // yum shares dnf's check-update output and its exit code 100 for "updates available".
+    if let Some(manager) = ["dnf", "yum"]
+        .into_iter()
+        .find(|manager| command_exists(manager))
+    {
         let output = run_command_with_timeout(
-            "dnf",
+            manager,
             &["-q", "check-update", "--cacheonly"],
             &[("LC_ALL", "C")],
             UPDATES_PROBE_TIMEOUT_MS,
@@ -959,11 +3234,11 @@ fn probe_package_updates() -> Result<(String, String), String> {
             Some(0) | Some(100) => {
                 let count = parse_dnf_check_update_output(&output.stdout);
                 let summary = if count == 0 {
-                    "none (dnf)".to_string()
+                    format!("none ({})", manager)
                 } else {
-                    format!("{} package(s) via dnf", count)
+                    format!("{} package(s) via {}", count, manager)
                 };
-                return Ok((summary, "dnf check-update --cacheonly".to_string()));
+                return Ok((summary, format!("{} check-update --cacheonly", manager)));
             }
             _ => {
                 let stderr = output.stderr.trim().to_string();
@@ -973,8 +3248,8 @@ fn probe_package_updates() -> Result<(String, String), String> {
                     stderr
                 };
                 return Err(format!(
-                    "'dnf -q check-update --cacheonly' exited with {}",
-                    detail
+                    "'{} -q check-update --cacheonly' exited with {}",
+                    manager, detail
                 ));
             }
         }
@@ -983,6 +3258,371 @@ fn probe_package_updates() -> Result<(String, String), String> {
     Err("no supported package manager found".to_string())
 }
 
+fn probe_cpu_topology() -> Result<Option<CpuTopology>, String> {
+    if !command_exists("lscpu") {
+        return Ok(None);
+    }
+
+    let output =
+        run_command_with_timeout("lscpu", &[], &[("LC_ALL", "C")], OPTIONAL_PROBE_TIMEOUT_MS)?;
+    if !output.status.success() {
+        return Err(format!("exited with {}", output.status));
+    }
+    parse_lscpu_output(&output.stdout)
+        .map(Some)
+        .ok_or_else(|| "output has no socket/core/thread counts".to_string())
+}
+
+/// Reads `capacity` and `status` of every `BAT*` supply, sorted by name;
+/// desktops and servers have none.
+pub(super) fn parse_battery(power_supply: &Path) -> Vec<BatteryStatus> {
+    let Ok(entries) = fs::read_dir(power_supply) else {
+        return Vec::new();
+    };
+    let mut batteries = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with("BAT") {
+                return None;
+            }
+            let read = |file: &str| fs::read_to_string(entry.path().join(file)).ok();
+            let capacity = read("capacity")?
+                .trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|capacity| *capacity <= 100)?;
+            let status = read("status")
+                .map(|status| status.trim().to_string())
+                .filter(|status| !status.is_empty())
+                .unwrap_or_else(|| "Unknown".to_string());
+            Some(BatteryStatus {
+                name,
+                capacity,
+                status,
+            })
+        })
+        .collect::<Vec<_>>();
+    batteries.sort_by(|left, right| left.name.cmp(&right.name));
+    batteries
+}
+
+/// No devices when `nvidia-smi` is not installed; the timeout keeps a hung
+/// driver from stalling the login.
+fn probe_gpus() -> Result<Vec<GpuInfo>, String> {
+    if !command_exists("nvidia-smi") {
+        return Ok(Vec::new());
+    }
+
+    let output = run_command_with_timeout(
+        "nvidia-smi",
+        &[
+            "--query-gpu=name,memory.used,memory.total",
+            "--format=csv,noheader,nounits",
+        ],
+        &[],
+        GPU_PROBE_TIMEOUT_MS,
+    )?;
+    if !output.status.success() {
+        return Err(format!("exited with {}", output.status));
+    }
+    Ok(parse_nvidia_smi_gpus(&output.stdout))
+}
+
+/// Parses `name, memory.used, memory.total` rows in MiB; the name is split
+/// from the right since model names may contain commas.
+pub(super) fn parse_nvidia_smi_gpus(output: &str) -> Vec<GpuInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.rsplitn(3, ',').map(str::trim);
+            let total = fields.next()?;
+            let used = fields.next()?;
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            Some(GpuInfo {
+                name: name.to_string(),
+                memory_used_mib: used.parse().ok(),
+                memory_total_mib: total.parse().ok(),
+            })
+        })
+        .collect()
+}
+
+fn probe_needrestart() -> Result<Option<(usize, String)>, String> {
+    let cache_path = expand_tilde(NEEDRESTART_CACHE_PATH);
+    match read_probe_cache_result(
+        &cache_path,
+        NEEDRESTART_CACHE_TTL_SECS,
+        NEEDRESTART_FAILURE_CACHE_TTL_SECS,
+    ) {
+        Some(Ok(cached)) => {
+            if let Ok(count) = cached.trim().parse::<usize>() {
+                return Ok(Some((count, format!("cache ({})", cache_path.display()))));
+            }
+        }
+        Some(Err(err)) => return Err(err),
+        None => {}
+    }
+
+    if !command_exists("needrestart") {
+        return Ok(None);
+    }
+
+    let result = run_needrestart();
+    // A failed cache write only costs a slower next login.
+    let _ = match &result {
+        Ok(count) => write_probe_cache(&cache_path, &count.to_string()),
+        Err(err) => write_probe_cache_failure(&cache_path, err),
+    };
+    result.map(|count| Some((count, "needrestart -b".to_string())))
+}
+
+fn run_needrestart() -> Result<usize, String> {
+    let output = run_command_with_timeout(
+        "needrestart",
+        &["-b"],
+        &[("LC_ALL", "C")],
+        NEEDRESTART_PROBE_TIMEOUT_MS,
+    )?;
+    if !output.status.success() {
+        let stderr = output.stderr.trim().to_string();
+        let detail = if stderr.is_empty() {
+            output.status.to_string()
+        } else {
+            stderr
+        };
+        return Err(format!("'needrestart -b' exited with {}", detail));
+    }
+
+    Ok(parse_needrestart_batch_output(&output.stdout))
+}
+
+#[cfg(feature = "remote-welcome")]
+fn probe_update_check() -> Result<(Option<String>, String), String> {
+    let (check, source) = cached_update_check()?;
+    let source = format!("{} (latest {})", source, check.latest);
+    Ok((check.update_available().then_some(check.latest), source))
+}
+
+#[cfg(not(feature = "remote-welcome"))]
+fn probe_update_check() -> Result<(Option<String>, String), String> {
+    Err("built without the remote-welcome feature".to_string())
+}
+
+fn probe_certificates(checks: &[CertCheckConfig]) -> Vec<CertificateStatus> {
+    let now = Utc::now();
+    checks
+        .iter()
+        .map(|check| CertificateStatus {
+            label: check.label.clone(),
+            days_left: fs::read(expand_tilde(&check.path))
+                .map_err(|err| format!("failed to read {}: {}", check.path, err))
+                .and_then(|bytes| parse_certificate_not_after(&bytes))
+                .map(|not_after| not_after.signed_duration_since(now).num_days()),
+        })
+        .collect()
+}
+
+fn probe_fd_usages(checks: &[ServiceFdCheckConfig]) -> Vec<FdUsageStatus> {
+    checks
+        .iter()
+        .map(|check| FdUsageStatus {
+            label: check.label.clone(),
+            usage: probe_fd_usage(check),
+        })
+        .collect()
+}
+
+fn probe_fd_usage(check: &ServiceFdCheckConfig) -> Result<FdUsage, String> {
+    let proc_root = Path::new("/proc");
+    let pid = match (&check.pid_file, &check.process_name) {
+        (Some(pid_file), _) => {
+            let path = expand_tilde(pid_file);
+            fs::read_to_string(&path)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("{} does not contain a pid", path.display()))?
+        }
+        (None, Some(name)) => find_pid_by_comm(proc_root, name)
+            .ok_or_else(|| format!("no running process named '{}'", name))?,
+        (None, None) => return Err("no process configured".to_string()),
+    };
+
+    let proc_dir = proc_root.join(pid.to_string());
+    let fd_dir = proc_dir.join("fd");
+    let mut open = 0;
+    let mut socket_inodes = HashSet::new();
+    for entry in fs::read_dir(&fd_dir)
+        .map_err(|err| format!("failed to read {}: {}", fd_dir.display(), err))?
+        .flatten()
+    {
+        open += 1;
+        if let Ok(target) = fs::read_link(entry.path())
+            && let Some(inode) = target
+                .to_str()
+                .and_then(|target| target.strip_prefix("socket:["))
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|inode| inode.parse::<u64>().ok())
+        {
+            socket_inodes.insert(inode);
+        }
+    }
+    let limit = fs::read_to_string(proc_dir.join("limits"))
+        .ok()
+        .and_then(|content| parse_proc_limits_nofile(&content));
+    // The process's own view of the tables, so a service in another network
+    // namespace is counted against its own sockets.
+    let tables = ["tcp", "tcp6"]
+        .iter()
+        .filter_map(|table| fs::read_to_string(proc_dir.join("net").join(table)).ok())
+        .collect::<Vec<_>>();
+    let connections = (!tables.is_empty()).then(|| {
+        tables
+            .iter()
+            .map(|content| count_established_connections(content, &socket_inodes))
+            .sum()
+    });
+
+    Ok(FdUsage {
+        open,
+        limit,
+        connections,
+    })
+}
+
+pub(super) fn read_probe_cache(path: &Path, ttl_secs: u64) -> Option<String> {
+    let (age_secs, body) = read_probe_cache_entry(path)?;
+    (age_secs <= ttl_secs).then_some(body)
+}
+
+/// Reads a cache that may also hold a failed probe, written by
+/// `write_probe_cache_failure`. A failure is kept for `failure_ttl_secs`, so
+/// a host where the probe keeps timing out only pays the timeout once per
+/// failure TTL instead of at every login.
+pub(super) fn read_probe_cache_result(
+    path: &Path,
+    ttl_secs: u64,
+    failure_ttl_secs: u64,
+) -> Option<Result<String, String>> {
+    let (age_secs, body) = read_probe_cache_entry(path)?;
+    match body.strip_prefix(PROBE_CACHE_FAILURE_PREFIX) {
+        Some(reason) => (age_secs <= failure_ttl_secs).then(|| Err(format!("{} (cached)", reason))),
+        None => (age_secs <= ttl_secs).then_some(Ok(body)),
+    }
+}
+
+pub(super) fn write_probe_cache_failure(path: &Path, reason: &str) -> Result<(), String> {
+    write_probe_cache(
+        path,
+        &format!(
+            "{}{}",
+            PROBE_CACHE_FAILURE_PREFIX,
+            reason.replace('\n', " ")
+        ),
+    )
+}
+
+const PROBE_CACHE_FAILURE_PREFIX: &str = "failed: ";
+
+fn read_probe_cache_entry(path: &Path) -> Option<(u64, String)> {
+    let content = fs::read_to_string(path).ok()?;
+    let (header, body) = content.split_once("\n\n")?;
+    let fetched_at_secs = header
+        .lines()
+        .find_map(|line| line.strip_prefix("fetched_at="))?
+        .parse::<u64>()
+        .ok()?;
+
+    Some((
+        unix_now_secs().saturating_sub(fetched_at_secs),
+        body.to_string(),
+    ))
+}
+
+pub(super) fn write_probe_cache(path: &Path, body: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {}", parent.display(), err))?;
+    }
+
+    // Renaming a finished file into place keeps concurrent readers from
+    // ever seeing a half-written cache.
+    let staging = path.with_extension(format!("tmp{}", std::process::id()));
+    fs::write(
+        &staging,
+        format!("fetched_at={}\n\n{}", unix_now_secs(), body),
+    )
+    .map_err(|err| format!("failed to write {}: {}", staging.display(), err))?;
+    fs::rename(&staging, path).map_err(|err| {
+        let _ = fs::remove_file(&staging);
+        format!("failed to write {}: {}", path.display(), err)
+    })
+}
+
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn read_kernel_log() -> Result<(String, &'static str), String> {
+    let kmsg_err = match read_kmsg() {
+        Ok(log) => return Ok((log, "/dev/kmsg")),
+        Err(err) => err,
+    };
+
+    let output =
+        run_command_with_timeout("dmesg", &[], &[("LC_ALL", "C")], OPTIONAL_PROBE_TIMEOUT_MS)
+            .map_err(|err| format!("{}; {}", kmsg_err, err))?;
+    if !output.status.success() {
+        let stderr = output.stderr.trim().to_string();
+        let detail = if stderr.is_empty() {
+            output.status.to_string()
+        } else {
+            stderr
+        };
+        return Err(format!("{}; 'dmesg' exited with {}", kmsg_err, detail));
+    }
+    Ok((output.stdout, "dmesg"))
+}
+
+#[cfg(target_os = "linux")]
+fn read_kmsg() -> Result<String, String> {
+    use rustix::fs::OFlags;
+    use std::io::ErrorKind;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .custom_flags(OFlags::NONBLOCK.bits() as i32)
+        .open("/dev/kmsg")
+        .map_err(|err| format!("failed to open /dev/kmsg: {}", err))?;
+    let mut log = String::new();
+    let mut record = [0u8; 8192];
+
+    loop {
+        match file.read(&mut record) {
+            Ok(0) => break,
+            Ok(len) => log.push_str(&String::from_utf8_lossy(&record[..len])),
+            // The ring buffer overwrote the record we were about to read.
+            Err(err) if err.kind() == ErrorKind::BrokenPipe => continue,
+            Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+            Err(err) => return Err(format!("failed to read /dev/kmsg: {}", err)),
+        }
+    }
+    Ok(log)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_kmsg() -> Result<String, String> {
+    Err("/dev/kmsg is only available on Linux".to_string())
+}
+
 #[cfg(target_os = "linux")]
 fn get_logged_in_user_count() -> (usize, &'static str) {
     if let Some(count) = count_logged_in_users_from_linux_utmp() {
@@ -1031,11 +3671,19 @@ pub(super) fn run_command_with_timeout(
     loop {
         match child.try_wait() {
             Ok(Some(status)) => {
-                return Ok(TimedCommandOutput {
+                let output = TimedCommandOutput {
                     status,
                     stdout: collect_reader_output(stdout_handle),
                     stderr: collect_reader_output(stderr_handle),
-                });
+                };
+                debug::log(format_args!(
+                    "command='{}' status={} stdout={}B stderr={}B",
+                    command_line,
+                    output.status,
+                    output.stdout.len(),
+                    output.stderr.len()
+                ));
+                return Ok(output);
             }
             Ok(None) if Instant::now() < deadline => {
                 thread::sleep(Duration::from_millis(COMMAND_POLL_INTERVAL_MS));
@@ -1045,6 +3693,10 @@ pub(super) fn run_command_with_timeout(
                 let _ = child.wait();
                 let _ = collect_reader_output(stdout_handle);
                 let _ = collect_reader_output(stderr_handle);
+                debug::log(format_args!(
+                    "command='{}' timed out after {}ms",
+                    command_line, timeout_ms
+                ));
                 return Err(format!(
                     "'{}' timed out after {}ms",
                     command_line, timeout_ms
@@ -1137,61 +3789,193 @@ fn detect_virtualization() -> (Option<String>, String, Option<ProbeIssue>) {
 }
 
 #[cfg(unix)]
-fn collect_disk_usage_items() -> (Option<UsageSummary>, Vec<RenderedItem>) {
+pub(super) fn collect_disk_usage_items(
+    selection: &DiskMountSelection,
+    timeout_ms: u64,
+    diagnostics: &mut SnapshotDiagnostics,
+) -> (Option<UsageSummary>, Vec<DiskUsageItem>) {
     let file = match File::open("/proc/mounts") {
         Ok(file) => file,
         Err(_) => return (None, Vec::new()),
     };
 
-    let mut root_disk = None;
-    let mut items = Vec::new();
+    // Bind mounts repeat the source device, so each source is only queried once.
+    let mut seen_sources = HashSet::new();
+    let mut seen_paths = HashSet::new();
+    let mut mounts = Vec::new();
     for line in BufReader::new(file).lines().map_while(Result::ok) {
         let fields: Vec<&str> = line.split_whitespace().collect();
         if fields.len() < 3 {
             continue;
         }
-        let mount_path = fields[1];
-        let fstype = fields[2];
+        let (source, mount_path, fstype) = (fields[0], fields[1], fields[2]);
+        let Some(label) = disk_mount_label(source, mount_path, fstype, selection) else {
+            continue;
+        };
+        if !seen_sources.insert(source.to_string()) || !seen_paths.insert(mount_path.to_string()) {
+            continue;
+        }
+        mounts.push((mount_path.to_string(), fstype.to_string(), label));
+    }
 
-        if mount_path == "/" {
-            if let Some((summary, item)) = disk_usage_item(mount_path, "Disk usage (root):") {
-                root_disk = Some(summary);
-                items.push(item);
+    // statvfs on a dead NFS server blocks for as long as the server is gone,
+    // so every mount is queried on its own thread against one shared deadline.
+    let jobs = mounts
+        .iter()
+        .map(|(mount_path, _, _)| {
+            let mount_path = mount_path.clone();
+            move || get_mount_usage(&mount_path)
+        })
+        .collect();
+    let usages = run_with_deadline(jobs, Duration::from_millis(timeout_ms));
+    summarize_disk_usage(mounts, usages, timeout_ms, diagnostics)
+}
+
+/// Turns each selected mount and its `(total, used)` bytes into a disk line:
+/// `Some(None)` drops a mount that could not be queried, `None` marks one
+/// that missed the deadline as timed out.
+#[cfg(any(unix, feature = "sysinfo"))]
+pub(super) fn summarize_disk_usage(
+    mounts: Vec<(String, String, &'static str)>,
+    usages: Vec<Option<Option<(u64, u64)>>>,
+    timeout_ms: u64,
+    diagnostics: &mut SnapshotDiagnostics,
+) -> (Option<UsageSummary>, Vec<DiskUsageItem>) {
+    let mut root_disk = None;
+    let mut items = Vec::new();
+    for ((mount_path, fstype, label), usage) in mounts.into_iter().zip(usages) {
+        let item = match usage {
+            Some(Some((total_bytes, used_bytes))) => {
+                disk_usage_item(mount_path, fstype, label, total_bytes, used_bytes)
             }
-        } else if matches!(fstype, "nfs" | "nfs4")
-            && let Some((_, item)) = disk_usage_item(mount_path, "Disk usage (nfs):")
-        {
-            items.push(item);
+            Some(None) => continue,
+            None => {
+                debug::log(format_args!(
+                    "statvfs '{}' timed out after {}ms",
+                    mount_path, timeout_ms
+                ));
+                diagnostics.degrade(
+                    ModuleKind::Disk,
+                    ProbeIssue::DiskUsageTimedOut(mount_path.clone()),
+                );
+                DiskUsageItem {
+                    label: label.to_string(),
+                    mount_path,
+                    fstype,
+                    used_bytes: 0,
+                    total_bytes: 0,
+                    ratio: 0.0,
+                    timed_out: true,
+                }
+            }
+        };
+        if item.mount_path == "/" && !item.timed_out {
+            root_disk = Some(UsageSummary {
+                used_gb: bytes_to_gb(item.used_bytes),
+                total_gb: bytes_to_gb(item.total_bytes),
+                ratio: item.ratio,
+            });
         }
+        items.push(item);
     }
 
     (root_disk, items)
 }
 
 #[cfg(not(unix))]
-fn collect_disk_usage_items() -> (Option<UsageSummary>, Vec<RenderedItem>) {
+pub(super) fn collect_disk_usage_items(
+    _selection: &DiskMountSelection,
+    _timeout_ms: u64,
+    _diagnostics: &mut SnapshotDiagnostics,
+) -> (Option<UsageSummary>, Vec<DiskUsageItem>) {
     (None, Vec::new())
 }
 
-#[cfg(unix)]
-fn disk_usage_item(mount_path: &str, label: &str) -> Option<(UsageSummary, RenderedItem)> {
-    let (total_bytes, used_bytes) = get_mount_usage(mount_path)?;
-    let (used_str, total_str, ratio) = human_readable_usage(used_bytes, total_bytes);
-    let summary = UsageSummary {
-        used_gb: bytes_to_gb(used_bytes),
-        total_gb: bytes_to_gb(total_bytes),
-        ratio,
+/// `disk_mounts` wins over `disk_all_local` when both are set.
+fn disk_mount_selection(cfg: &MotdConfig) -> DiskMountSelection {
+    match &cfg.disk_mounts {
+        Some(paths) => DiskMountSelection::Only(paths.clone()),
+        None if cfg.disk_all_local == Some(true) => DiskMountSelection::AllLocal,
+        None => DiskMountSelection::RootAndNfs,
+    }
+}
+
+/// Picks the disk line label for a `/proc/mounts` entry, or `None` when the
+/// mount is not selected for display.
+pub(super) fn disk_mount_label(
+    source: &str,
+    mount_path: &str,
+    fstype: &str,
+    selection: &DiskMountSelection,
+) -> Option<&'static str> {
+    let label = if mount_path == "/" {
+        "Disk usage (root):"
+    } else if matches!(fstype, "nfs" | "nfs4") {
+        "Disk usage (nfs):"
+    } else {
+        "Disk usage (local):"
     };
-    Some((
-        summary,
-        RenderedItem {
-            label: label.to_string(),
-            value: format!(
-                "{} => {}/{} ({:.2}%)",
-                mount_path, used_str, total_str, ratio
-            ),
-        },
-    ))
+    let selected = match selection {
+        DiskMountSelection::Only(paths) => paths.iter().any(|path| path == mount_path),
+        DiskMountSelection::AllLocal => {
+            label != "Disk usage (local):"
+                || (source.starts_with("/dev/") && !PSEUDO_FILESYSTEMS.contains(&fstype))
+        }
+        DiskMountSelection::RootAndNfs => label != "Disk usage (local):",
+    };
+    selected.then_some(label)
+}
+
+/// Runs each job on its own thread and collects what finished before the
+/// deadline; late jobs are left running detached and come back as `None`.
+pub(super) fn run_with_deadline<T, F>(jobs: Vec<F>, timeout: Duration) -> Vec<Option<T>>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let count = jobs.len();
+    for (index, job) in jobs.into_iter().enumerate() {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            let _ = sender.send((index, job()));
+        });
+    }
+    drop(sender);
+
+    let deadline = Instant::now() + timeout;
+    let mut results = (0..count).map(|_| None).collect::<Vec<_>>();
+    for _ in 0..count {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(remaining) {
+            Ok((index, value)) => results[index] = Some(value),
+            Err(_) => break,
+        }
+    }
+    results
+}
+
+fn disk_usage_item(
+    mount_path: String,
+    fstype: String,
+    label: &str,
+    total_bytes: u64,
+    used_bytes: u64,
+) -> DiskUsageItem {
+    let ratio = if total_bytes > 0 {
+        used_bytes as f64 / total_bytes as f64 * 100.0
+    } else {
+        0.0
+    };
+    DiskUsageItem {
+        label: label.to_string(),
+        mount_path,
+        fstype,
+        used_bytes,
+        total_bytes,
+        ratio,
+        timed_out: false,
+    }
 }
 
 #[cfg(unix)]
@@ -1205,52 +3989,11 @@ fn get_mount_usage(mountpoint: &str) -> Option<(u64, u64)> {
     Some((total_bytes, used_bytes))
 }
 
-#[cfg(unix)]
-fn human_readable_usage(used: u64, total: u64) -> (String, String, f64) {
-    let bigger = std::cmp::max(used, total);
-    let (scale, suffix) = best_unit_scale(bigger as f64);
-
-    let used_f = used as f64 / scale;
-    let total_f = total as f64 / scale;
-    let ratio = if total_f > 0.0 {
-        used_f / total_f * 100.0
-    } else {
-        0.0
-    };
-
-    let used_str = format!("{:.2} {}", used_f, suffix);
-    let total_str = format!("{:.2} {}", total_f, suffix);
-    (used_str, total_str, ratio)
-}
-
-#[cfg(unix)]
+#[cfg(any(unix, feature = "sysinfo"))]
 fn bytes_to_gb(value: u64) -> f64 {
     value as f64 / 1024.0 / 1024.0 / 1024.0
 }
 
-#[cfg(unix)]
-fn best_unit_scale(bytes: f64) -> (f64, &'static str) {
-    const KIB: f64 = 1024.0;
-    const MIB: f64 = 1024.0 * 1024.0;
-    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
-    const TIB: f64 = 1024.0 * 1024.0 * 1024.0 * 1024.0;
-    const PIB: f64 = 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0;
-
-    if bytes >= PIB {
-        (PIB, "PB")
-    } else if bytes >= TIB {
-        (TIB, "TB")
-    } else if bytes >= GIB {
-        (GIB, "GB")
-    } else if bytes >= MIB {
-        (MIB, "MB")
-    } else if bytes >= KIB {
-        (KIB, "KB")
-    } else {
-        (1.0, "B")
-    }
-}
-
 fn kb_to_gb(kb: u64) -> f64 {
     kb as f64 / 1024.0 / 1024.0
 }
@@ -1273,6 +4016,51 @@ fn get_default_interface() -> Result<String, NetworkProbeError> {
     parse_default_interface_output(&output.stdout).ok_or(NetworkProbeError::DefaultRouteParse)
 }
 
+/// Addresses of every non-loopback interface that is up, or of the named
+/// `interfaces` whatever their state, in `ip` order.
+fn get_interfaces(names: Option<&[String]>) -> Result<Vec<InterfaceAddress>, String> {
+    let args: &[&str] = match names {
+        Some(_) => &["-o", "addr", "show"],
+        None => &["-o", "addr", "show", "up"],
+    };
+    let output = run_command_with_timeout("ip", args, &[], CORE_PROBE_TIMEOUT_MS)?;
+    if !output.status.success() {
+        return Err(format!("ip addr exited with {}", output.status));
+    }
+    let mut addresses = parse_ip_addr_output(&output.stdout);
+    if let Some(names) = names {
+        addresses.retain(|address| names.contains(&address.iface));
+    }
+    Ok(addresses)
+}
+
+/// Parses `ip -o addr` lines such as
+/// `2: eth0    inet 10.0.0.5/24 brd 10.0.0.255 scope global eth0`, skipping
+/// loopback and host-scoped addresses.
+pub(super) fn parse_ip_addr_output(output: &str) -> Vec<InterfaceAddress> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let iface = fields.get(1)?.split('@').next()?.trim_end_matches(':');
+            let family = *fields.get(2)?;
+            let address = fields.get(3)?.split('/').next()?;
+            let scope = fields
+                .windows(2)
+                .find(|pair| pair[0] == "scope")
+                .map(|pair| pair[1]);
+            if iface == "lo" || scope == Some("host") || !matches!(family, "inet" | "inet6") {
+                return None;
+            }
+            Some(InterfaceAddress {
+                iface: iface.to_string(),
+                address: address.to_string(),
+                link_local: family == "inet6" && scope == Some("link"),
+            })
+        })
+        .collect()
+}
+
 fn get_interface_ipv4(iface: &str) -> Result<String, NetworkProbeError> {
     let output = run_command_with_timeout(
         "ip",
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use reqx::blocking::Client;
+
+use crate::config::expand_tilde;
+
+use super::probe::{read_probe_cache_result, write_probe_cache, write_probe_cache_failure};
+use super::types::{
+    RELEASE_CHECK_CACHE_PATH, RELEASE_CHECK_CACHE_TTL_SECS, RELEASE_CHECK_FAILURE_CACHE_TTL_SECS,
+    RELEASE_CHECK_TIMEOUT_MS,
+};
+
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const RELEASES_API_ORIGIN: &str = "https://api.github.com";
+const LATEST_RELEASE_PATH: &str = "/repos/lvillis/motdyn/releases/latest";
+pub const RELEASES_PAGE_URL: &str = "https://github.com/lvillis/motdyn/releases/latest";
+
+/// Result of comparing the running build against the latest GitHub release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateCheck {
+    pub current: &'static str,
+    pub latest: String,
+}
+
+impl UpdateCheck {
+    pub fn update_available(&self) -> bool {
+        is_newer_version(&self.latest, self.current)
+    }
+}
+
+/// Asks GitHub for the latest release, bypassing the cache, and refreshes the
+/// cache with the answer. Used by `motdyn check-update`.
+pub fn check_for_update() -> Result<UpdateCheck, String> {
+    let latest = fetch_latest_release_tag()?;
+    // A failed cache write only means the banner asks again next time.
+    let _ = write_probe_cache(&expand_tilde(RELEASE_CHECK_CACHE_PATH), &latest);
+    Ok(UpdateCheck {
+        current: CURRENT_VERSION,
+        latest,
+    })
+}
+
+/// Cached variant for the banner: GitHub is queried at most once a day, and
+/// after a failed lookup at most once an hour, so an offline host does not
+/// wait for the timeout at every login.
+pub(super) fn cached_update_check() -> Result<(UpdateCheck, &'static str), String> {
+    let cache_path = expand_tilde(RELEASE_CHECK_CACHE_PATH);
+    match read_probe_cache_result(
+        &cache_path,
+        RELEASE_CHECK_CACHE_TTL_SECS,
+        RELEASE_CHECK_FAILURE_CACHE_TTL_SECS,
+    ) {
+        Some(Ok(cached)) if !cached.trim().is_empty() => {
+            return Ok((
+                UpdateCheck {
+                    current: CURRENT_VERSION,
+                    latest: cached.trim().to_string(),
+                },
+                "cache",
+            ));
+        }
+        Some(Err(err)) => return Err(err),
+        _ => {}
+    }
+    let result = check_for_update();
+    if let Err(err) = &result {
+        // A failed cache write only means the next login asks again.
+        let _ = write_probe_cache_failure(&cache_path, err);
+    }
+    result.map(|check| (check, "GitHub releases API"))
+}
+
+fn fetch_latest_release_tag() -> Result<String, String> {
+    let timeout = Duration::from_millis(RELEASE_CHECK_TIMEOUT_MS);
+    let client = Client::builder(RELEASES_API_ORIGIN)
+        .request_timeout(timeout)
+        .total_timeout(timeout)
+        .build()
+        .map_err(|err| err.to_string())?;
+    let response = client
+        .get(LATEST_RELEASE_PATH)
+        .try_header("accept", "application/vnd.github+json")
+        .and_then(|request| {
+            request.try_header("user-agent", &format!("motdyn/{}", CURRENT_VERSION))
+        })
+        .map_err(|err| err.to_string())?
+        .send_response()
+        .map_err(|err| err.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("GitHub returned {}", response.status()));
+    }
+    let body = response.text().map_err(|err| err.to_string())?;
+    parse_release_tag(body).ok_or_else(|| "response has no tag_name".to_string())
+}
+
+/// Pulls `tag_name` out of a GitHub release JSON document.
+pub(super) fn parse_release_tag(body: &str) -> Option<String> {
+    let (_, rest) = body.split_once("\"tag_name\"")?;
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let (tag, _) = rest.split_once('"')?;
+    let tag = tag.trim();
+    (!tag.is_empty()).then(|| tag.to_string())
+}
+
+/// Compares dotted numeric versions, ignoring a leading `v` and any
+/// pre-release or build suffix.
+pub(super) fn is_newer_version(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+fn parse_version(raw: &str) -> Option<Vec<u64>> {
+    let core = raw
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    core.split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect()
+}
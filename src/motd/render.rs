@@ -1,18 +1,27 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 
+use chrono::{Local, TimeZone};
+
 #[cfg(feature = "color")]
-use colored::Colorize;
+use colored::{Color, Colorize};
 #[cfg(unix)]
 use rustix::process::geteuid;
 
-use crate::config::MotdConfig;
+use crate::config::{MotdConfig, ThresholdsConfig};
 
+use super::schedule::ScheduledBanners;
 use super::types::{
-    FailedLoginBucket, FailedLoginInfo, FailedLoginSeverity, HiddenField, LastLoginInfo,
-    LastLoginRecord, ModuleKind, ModuleProfile, ModuleSelection, ModuleSource, OutputSettings,
-    PaintKind, RenderContext, RenderedItem, SectionKind, SourceRelation, SystemSnapshot,
-    UsageSummary, ViewerRole, WelcomeResolution, WelcomeSource,
+    AuthorizedKey, BYTES_PER_GB, BatteryStatus, CERT_EXPIRY_WARNING_DAYS, ContainerStorage,
+    CpuTopology, DEFAULT_BATTERY_LOW, DEFAULT_LOAD_THRESHOLD, DEFAULT_PRESSURE_THRESHOLD,
+    DEFAULT_PROGRESS_BAR_WIDTH, DiskUsageItem, EnvironmentStyle, FALLBACK_TERMINAL_WIDTH,
+    FailedLoginBucket, FailedLoginInfo, FailedLoginSeverity, FdUsage, GpuInfo, HiddenField,
+    KernelPreemption, LastLoginInfo, LastLoginRecord, MacStatus, MemoryProcess, ModuleKind,
+    ModuleProfile, ModuleSelection, ModuleSource, NetThroughput, NetworkLink, OutputSettings,
+    PaintKind, PressureAverages, RenderContext, RenderedItem, SeLinuxMode, SectionKind,
+    SourceRelation, SpacingSettings, SwapActivity, SystemSnapshot, TAB_WIDTH, TemperatureUnit,
+    ThemeColor, Threshold, UsageDisplay, UsageSummary, UsageThresholds, ViewerRole,
+    WelcomeResolution, WelcomeSource,
 };
 
 pub(super) fn build_verbose_items(
@@ -20,6 +29,7 @@ pub(super) fn build_verbose_items(
     ctx: &RenderContext,
     selection: &ModuleSelection,
     welcome: &WelcomeResolution,
+    banners: &ScheduledBanners,
     snapshot: &SystemSnapshot,
     output: &OutputSettings,
 ) -> Vec<RenderedItem> {
@@ -32,6 +42,26 @@ pub(super) fn build_verbose_items(
             label: "User config:".to_string(),
             value: format!("{} ({})", ctx.user_config_path, ctx.user_config_status),
         },
+        RenderedItem {
+            label: "Env overrides:".to_string(),
+            value: if ctx.env_overrides.is_empty() {
+                "none".to_string()
+            } else {
+                format!("{} (win over both files)", ctx.env_overrides.join(", "))
+            },
+        },
+        RenderedItem {
+            label: "Config origins:".to_string(),
+            value: if ctx.config_origins.is_empty() {
+                "built-in defaults only".to_string()
+            } else {
+                ctx.config_origins.join(", ")
+            },
+        },
+        RenderedItem {
+            label: "Session overlay:".to_string(),
+            value: ctx.session.clone(),
+        },
         RenderedItem {
             label: "Module source:".to_string(),
             value: match selection.source {
@@ -157,6 +187,98 @@ pub(super) fn build_verbose_items(
             value: snapshot.diagnostics.updates_source.clone(),
         });
     }
+    if selection.modules.contains(&ModuleKind::Kernel)
+        && let Some(preemption) = snapshot.kernel_preemption
+    {
+        items.push(RenderedItem {
+            label: "Kernel:".to_string(),
+            value: format_kernel_preemption(preemption),
+        });
+    }
+    if output.display_hostname.is_some() {
+        items.push(RenderedItem {
+            label: "Real host name:".to_string(),
+            value: snapshot.host_name.clone(),
+        });
+    }
+    if !snapshot.diagnostics.update_check_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Update check:".to_string(),
+            value: snapshot.diagnostics.update_check_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.cpu_topology_source.is_empty() {
+        items.push(RenderedItem {
+            label: "CPU topology source:".to_string(),
+            value: snapshot.diagnostics.cpu_topology_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.storage_pools_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Storage pools source:".to_string(),
+            value: snapshot.diagnostics.storage_pools_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.container_storage_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Container storage source:".to_string(),
+            value: snapshot.diagnostics.container_storage_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.containers_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Containers source:".to_string(),
+            value: snapshot.diagnostics.containers_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.oom_source.is_empty() {
+        items.push(RenderedItem {
+            label: "OOM source:".to_string(),
+            value: snapshot.diagnostics.oom_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.recent_logins_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Recent logins state:".to_string(),
+            value: snapshot.diagnostics.recent_logins_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.needrestart_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Needrestart source:".to_string(),
+            value: snapshot.diagnostics.needrestart_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.reboot_required_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Reboot check source:".to_string(),
+            value: snapshot.diagnostics.reboot_required_source.clone(),
+        });
+    }
+    if !snapshot.diagnostics.auto_updates_source.is_empty() {
+        items.push(RenderedItem {
+            label: "Auto-updates source:".to_string(),
+            value: snapshot.diagnostics.auto_updates_source.clone(),
+        });
+    }
+
+    if selection.modules.contains(&ModuleKind::Addresses) {
+        items.extend(render_interface_address_items(snapshot, output, true));
+    }
+
+    if selection.modules.contains(&ModuleKind::AuthorizedKeys) {
+        for file in &snapshot.authorized_keys {
+            for key in &file.keys {
+                items.push(RenderedItem {
+                    label: match &file.owner {
+                        Some(owner) => format!("Authorized key ({}):", owner),
+                        None => "Authorized key:".to_string(),
+                    },
+                    value: format_authorized_key(key),
+                });
+            }
+        }
+    }
 
     if selection.modules.contains(&ModuleKind::LastLogin) {
         match &snapshot.last_login {
@@ -294,6 +416,19 @@ pub(super) fn build_verbose_items(
         });
     }
 
+    if !banners.messages.is_empty() {
+        items.push(RenderedItem {
+            label: "Scheduled banners:".to_string(),
+            value: format!("{} active", banners.messages.len()),
+        });
+    }
+    if !banners.skipped.is_empty() {
+        items.push(RenderedItem {
+            label: "Skipped banners:".to_string(),
+            value: banners.skipped.join(" | "),
+        });
+    }
+
     if !welcome.warnings.is_empty() {
         items.push(RenderedItem {
             label: "Welcome notes:".to_string(),
@@ -306,6 +441,8 @@ pub(super) fn build_verbose_items(
         });
     }
 
+    items.extend(render_top_memory_items(&snapshot.top_memory));
+
     if !snapshot.diagnostics.issues.is_empty() {
         items.push(RenderedItem {
             label: "Probe notes:".to_string(),
@@ -329,21 +466,83 @@ pub(super) fn build_verbose_items(
     items
 }
 
+/// One row per process under a single `Top memory:` label, with the sizes
+/// right-aligned so the rows read as a small table.
+fn render_top_memory_items(processes: &[MemoryProcess]) -> Vec<RenderedItem> {
+    let rows = processes
+        .iter()
+        .map(|process| {
+            let bytes = process.rss_kb as f64 * 1024.0;
+            let (scale, suffix) = best_unit_scale(bytes);
+            (
+                format!("{:.1} {}", bytes / scale, suffix),
+                &process.name,
+                process.pid,
+            )
+        })
+        .collect::<Vec<_>>();
+    let size_width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0);
+    let name_width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0);
+    rows.iter()
+        .enumerate()
+        .map(|(index, (size, name, pid))| RenderedItem {
+            label: if index == 0 { "Top memory:" } else { "" }.to_string(),
+            value: format!("{:>size_width$}  {:<name_width$}  pid {}", size, name, pid),
+        })
+        .collect()
+}
+
 pub(super) fn render_module_lines(
     modules: &[ModuleKind],
     snapshot: &SystemSnapshot,
     settings: &OutputSettings,
 ) -> Vec<String> {
+    format_module_lines(
+        render_module_item_groups(modules, snapshot, settings),
+        settings,
+    )
+}
+
+/// Each module's items with `[colors]` applied, in `modules` order.
+pub(super) fn render_module_item_groups(
+    modules: &[ModuleKind],
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> Vec<(ModuleKind, Vec<RenderedItem>)> {
     let combine_usage_bar = should_combine_usage_bar(modules, snapshot, settings);
+    modules
+        .iter()
+        .map(|module| {
+            let mut items = render_module_items(*module, snapshot, settings, combine_usage_bar);
+            // The environment highlight wins over `[colors]` for the host name.
+            let highlighted = *module == ModuleKind::Host
+                && settings
+                    .environment
+                    .as_ref()
+                    .is_some_and(|environment| environment.color.is_some());
+            if let Some(color) = settings.colors.get(module).filter(|_| !highlighted) {
+                for item in &mut items {
+                    item.value = paint_theme(strip_ansi(&item.value), *color, settings);
+                }
+            }
+            (*module, items)
+        })
+        .collect()
+}
+
+/// Lays out rendered module items: sections, compact wrapping and clipping.
+pub(super) fn format_module_lines(
+    module_items: Vec<(ModuleKind, Vec<RenderedItem>)>,
+    settings: &OutputSettings,
+) -> Vec<String> {
     let mut groups: Vec<(SectionKind, Vec<RenderedItem>)> = Vec::new();
 
-    for module in modules {
-        let items = render_module_items(*module, snapshot, settings, combine_usage_bar);
+    for (module, items) in module_items {
         if items.is_empty() {
             continue;
         }
 
-        let section = module_section(*module);
+        let section = module_section(module);
         if let Some((current_section, current_items)) = groups.last_mut()
             && *current_section == section
         {
@@ -357,47 +556,91 @@ pub(super) fn render_module_lines(
         return format_compact_groups(groups, settings);
     }
 
-    if !settings.section_headers {
+    let separator = separator_group(&groups, settings);
+    let lines = if !settings.section_headers {
+        // Aligned as one block so the separator does not shift the values.
+        let separator = separator.map(|(index, line)| {
+            let at = groups[..index]
+                .iter()
+                .map(|(_, items)| items.len())
+                .sum::<usize>();
+            (at, line)
+        });
         let items = groups
             .into_iter()
             .flat_map(|(_, items)| items)
             .collect::<Vec<_>>();
-        return format_aligned_items(&items, settings);
-    }
-
-    let mut lines = Vec::new();
-    for (idx, (section, items)) in groups.into_iter().enumerate() {
-        if idx > 0 && !settings.compact {
-            lines.push(String::new());
+        let mut lines = format_aligned_items(&items, settings);
+        if let Some((at, line)) = separator {
+            lines.insert(at, line);
+        }
+        lines
+    } else {
+        let mut lines = Vec::new();
+        for (idx, (section, items)) in groups.into_iter().enumerate() {
+            match &separator {
+                Some((index, line)) if *index == idx => lines.push(line.clone()),
+                _ if idx > 0 => lines.push(String::new()),
+                _ => {}
+            }
+            lines.push(paint(section.title(), PaintKind::Header, settings));
+            lines.extend(format_aligned_items(&items, settings));
         }
-        lines.push(paint(section.title(), PaintKind::Header, settings));
-        lines.extend(format_aligned_items(&items, settings));
+        lines
+    };
+    // Labels are short, so clipping the whole line only ever cuts the value
+    // and leaves the label column where it was.
+    match settings.line_width {
+        Some(width) => lines
+            .iter()
+            .map(|line| clip_line_to_width(line, width))
+            .collect(),
+        None => lines,
     }
-    lines
 }
 
 pub(super) fn format_aligned_items(
     items: &[RenderedItem],
     settings: &OutputSettings,
 ) -> Vec<String> {
-    let width = items.iter().map(|item| item.label.len()).max().unwrap_or(0);
+    let width = items
+        .iter()
+        .map(|item| visible_width(&item.label))
+        .max()
+        .unwrap_or(0);
     items
         .iter()
         .map(|item| {
+            let padding = " ".repeat(width - visible_width(&item.label));
             format!(
-                "{:width$} {}",
+                "{}{} {}",
                 paint(item.label.clone(), PaintKind::Label, settings),
-                item.value,
-                width = width
+                padding,
+                item.value
             )
         })
         .collect()
 }
 
+/// The storage group the configured `separator` goes in front of, unless
+/// storage opens the block and there is nothing to separate it from.
+fn separator_group(
+    groups: &[(SectionKind, Vec<RenderedItem>)],
+    settings: &OutputSettings,
+) -> Option<(usize, String)> {
+    let line = settings.separator.clone()?;
+    groups
+        .iter()
+        .position(|(section, _)| *section == SectionKind::Storage)
+        .filter(|index| *index > 0)
+        .map(|index| (index, line))
+}
+
 fn format_compact_groups(
     groups: Vec<(SectionKind, Vec<RenderedItem>)>,
     settings: &OutputSettings,
 ) -> Vec<String> {
+    let separator = separator_group(&groups, settings);
     let mut lines = Vec::new();
 
     for (idx, (section, items)) in groups.into_iter().enumerate() {
@@ -405,8 +648,15 @@ fn format_compact_groups(
             continue;
         }
 
+        let separated = match &separator {
+            Some((index, line)) if *index == idx => {
+                lines.push(line.clone());
+                true
+            }
+            _ => false,
+        };
         if settings.section_headers {
-            if idx > 0 {
+            if idx > 0 && !separated {
                 lines.push(String::new());
             }
             lines.push(paint(section.title(), PaintKind::Header, settings));
@@ -432,9 +682,9 @@ fn format_compact_groups(
 fn format_compact_section_body(
     prefix: Option<String>,
     clauses: &[String],
-    _settings: &OutputSettings,
+    settings: &OutputSettings,
 ) -> Vec<String> {
-    let width = terminal_width_hint();
+    let width = settings.line_width.unwrap_or_else(terminal_width_hint);
     let separator = "; ";
     let mut lines = Vec::new();
     let prefix_width = prefix
@@ -497,12 +747,144 @@ pub(super) fn resolve_output_settings(cfg: &MotdConfig) -> OutputSettings {
         }
     }
 
+    let mut colors = HashMap::new();
+    let mut ignored_colors = Vec::new();
+    for (module, color) in cfg.colors.iter().flatten() {
+        match (normalize_module_name(module), ThemeColor::parse(color)) {
+            (Some(kind), Some(color)) => {
+                colors.insert(kind, color);
+            }
+            (None, _) => ignored_colors.push(format!("unknown module '{}'", module)),
+            (Some(_), None) => {
+                ignored_colors.push(format!("unknown color '{}' for '{}'", color, module))
+            }
+        }
+    }
+
+    let environment = cfg
+        .environment
+        .as_deref()
+        .and_then(|name| resolve_environment_style(cfg, name, &mut ignored_colors));
+
     OutputSettings {
         compact: cfg.output.compact.unwrap_or(false),
-        plain: cfg.output.plain.unwrap_or(false),
+        plain: cfg.output.plain.unwrap_or(false) || cfg.output.ansi_safe.unwrap_or(false),
         section_headers: cfg.output.section_headers.unwrap_or(false),
         hidden_fields,
         ignored_hidden_fields,
+        max_disk_lines: cfg.output.max_disk_lines.filter(|limit| *limit > 0),
+        disk_display: cfg
+            .output
+            .disk_display
+            .as_deref()
+            .and_then(UsageDisplay::parse)
+            .unwrap_or_default(),
+        display_hostname: cfg.display_hostname.clone(),
+        disk_no_alert: cfg.disk_no_alert.clone().unwrap_or_default(),
+        disk_labels: cfg.disk_labels.clone().unwrap_or_default(),
+        colors,
+        ignored_colors,
+        thresholds: resolve_usage_thresholds(&cfg.thresholds),
+        progress_bar_width: cfg.output.progress_bars.unwrap_or(false).then(|| {
+            cfg.output
+                .progress_bar_width
+                .unwrap_or(DEFAULT_PROGRESS_BAR_WIDTH)
+        }),
+        line_width: None,
+        temperature_unit: cfg
+            .output
+            .temp_unit
+            .as_deref()
+            .and_then(TemperatureUnit::parse)
+            .unwrap_or_default(),
+        separator: cfg.separator.clone(),
+        environment,
+    }
+}
+
+/// The `[environments]` entry for `name` over the built-in colors: red for
+/// prod, yellow for staging, green for dev. `None` when neither knows it.
+fn resolve_environment_style(
+    cfg: &MotdConfig,
+    name: &str,
+    ignored_colors: &mut Vec<String>,
+) -> Option<EnvironmentStyle> {
+    let builtin = match name {
+        "prod" | "production" => Some(ThemeColor::Red),
+        "staging" | "stage" => Some(ThemeColor::Yellow),
+        "dev" | "development" => Some(ThemeColor::Green),
+        _ => None,
+    };
+    let entry = cfg
+        .environments
+        .as_ref()
+        .and_then(|environments| environments.get(name));
+    if entry.is_none() && builtin.is_none() {
+        return None;
+    }
+    let configured = entry
+        .and_then(|entry| entry.color.as_deref())
+        .and_then(|color| {
+            let parsed = ThemeColor::parse(color);
+            if parsed.is_none() {
+                ignored_colors.push(format!(
+                    "unknown color '{}' for environment '{}'",
+                    color, name
+                ));
+            }
+            parsed
+        });
+    Some(EnvironmentStyle {
+        name: name.to_string(),
+        color: configured.or(builtin),
+        banner: entry.and_then(|entry| entry.banner.clone()),
+    })
+}
+
+/// `output.width` (or `--width`) first, then the terminal, then 80 columns
+/// when stdout is not a terminal.
+pub(super) fn resolve_line_width(cfg: &MotdConfig) -> usize {
+    cfg.output
+        .width
+        .or_else(terminal_columns)
+        .unwrap_or(FALLBACK_TERMINAL_WIDTH)
+}
+
+fn resolve_usage_thresholds(cfg: &ThresholdsConfig) -> UsageThresholds {
+    let threshold = |warn: Option<f64>, crit: Option<f64>| {
+        let default = Threshold::default();
+        Threshold {
+            warn: warn.unwrap_or(default.warn),
+            crit: crit.unwrap_or(default.crit),
+        }
+    };
+    UsageThresholds {
+        memory: threshold(cfg.mem_warn, cfg.mem_crit),
+        swap: threshold(cfg.swap_warn, cfg.swap_crit),
+        disk: threshold(cfg.disk_warn, cfg.disk_crit),
+        temperature: threshold(cfg.temp_warn, cfg.temp_crit),
+        load: Threshold {
+            warn: cfg.load_warn.unwrap_or(DEFAULT_LOAD_THRESHOLD.warn),
+            crit: cfg.load_crit.unwrap_or(DEFAULT_LOAD_THRESHOLD.crit),
+        },
+        pressure: Threshold {
+            warn: cfg.pressure_warn.unwrap_or(DEFAULT_PRESSURE_THRESHOLD.warn),
+            crit: cfg.pressure_crit.unwrap_or(DEFAULT_PRESSURE_THRESHOLD.crit),
+        },
+        battery_low: cfg.battery_low.unwrap_or(DEFAULT_BATTERY_LOW),
+    }
+}
+
+pub(super) fn resolve_spacing_settings(
+    cfg: &MotdConfig,
+    output: &OutputSettings,
+) -> SpacingSettings {
+    let default = if output.compact { 0 } else { 1 };
+    SpacingSettings {
+        before_welcome: cfg.spacing.before_welcome.unwrap_or(default),
+        after_welcome: cfg.spacing.after_welcome.unwrap_or(default),
+        before_farewell: cfg.spacing.before_farewell.unwrap_or(default),
+        gutter: cfg.spacing.gutter.unwrap_or(0),
     }
 }
 
@@ -594,6 +976,7 @@ pub(super) fn default_modules() -> Vec<ModuleKind> {
         ModuleKind::Disk,
         ModuleKind::LastLogin,
         ModuleKind::FailedLogin,
+        ModuleKind::FdUsage,
     ]
 }
 
@@ -636,6 +1019,7 @@ pub(super) fn paint(text: impl Into<String>, kind: PaintKind, settings: &OutputS
             PaintKind::Red => text.bright_red().to_string(),
             PaintKind::Green => text.bright_green().to_string(),
             PaintKind::Magenta => text.bright_magenta().to_string(),
+            PaintKind::Alert => text.bold().bright_red().to_string(),
         }
     }
 
@@ -646,6 +1030,159 @@ pub(super) fn paint(text: impl Into<String>, kind: PaintKind, settings: &OutputS
     }
 }
 
+/// Paints text in a user-chosen `[colors]` color.
+pub(super) fn paint_theme(
+    text: impl Into<String>,
+    color: ThemeColor,
+    settings: &OutputSettings,
+) -> String {
+    let text = text.into();
+    if settings.plain || text.is_empty() {
+        return text;
+    }
+
+    #[cfg(feature = "color")]
+    {
+        text.color(terminal_color(color)).to_string()
+    }
+
+    #[cfg(not(feature = "color"))]
+    {
+        let _ = color;
+        text
+    }
+}
+
+/// Paints text bold on a `color` background, in black or white, whichever
+/// reads better on it.
+pub(super) fn paint_highlight(
+    text: impl Into<String>,
+    color: ThemeColor,
+    settings: &OutputSettings,
+) -> String {
+    let text = text.into();
+    if settings.plain || text.is_empty() {
+        return text;
+    }
+
+    #[cfg(feature = "color")]
+    {
+        let foreground = match color {
+            ThemeColor::Black
+            | ThemeColor::Red
+            | ThemeColor::Blue
+            | ThemeColor::Magenta
+            | ThemeColor::BrightBlack => Color::BrightWhite,
+            _ => Color::Black,
+        };
+        text.bold()
+            .color(foreground)
+            .on_color(terminal_color(color))
+            .to_string()
+    }
+
+    #[cfg(not(feature = "color"))]
+    {
+        let _ = color;
+        text
+    }
+}
+
+#[cfg(feature = "color")]
+fn terminal_color(color: ThemeColor) -> Color {
+    match color {
+        ThemeColor::Black => Color::Black,
+        ThemeColor::Red => Color::Red,
+        ThemeColor::Green => Color::Green,
+        ThemeColor::Yellow => Color::Yellow,
+        ThemeColor::Blue => Color::Blue,
+        ThemeColor::Magenta => Color::Magenta,
+        ThemeColor::Cyan => Color::Cyan,
+        ThemeColor::White => Color::White,
+        ThemeColor::BrightBlack => Color::BrightBlack,
+        ThemeColor::BrightRed => Color::BrightRed,
+        ThemeColor::BrightGreen => Color::BrightGreen,
+        ThemeColor::BrightYellow => Color::BrightYellow,
+        ThemeColor::BrightBlue => Color::BrightBlue,
+        ThemeColor::BrightMagenta => Color::BrightMagenta,
+        ThemeColor::BrightCyan => Color::BrightCyan,
+        ThemeColor::BrightWhite => Color::BrightWhite,
+    }
+}
+
+/// The host name, on the environment's background when one is active.
+fn render_host_name(snapshot: &SystemSnapshot, settings: &OutputSettings) -> String {
+    let name = settings
+        .display_hostname
+        .clone()
+        .unwrap_or_else(|| snapshot.host_name.clone());
+    match settings
+        .environment
+        .as_ref()
+        .and_then(|environment| environment.color)
+    {
+        Some(color) => paint_highlight(name, color, settings),
+        None => paint(name, PaintKind::Yellow, settings),
+    }
+}
+
+/// The `--quiet` one-liner, such as `prod-01 | up 3d5h | load 0.42 | mem 40%
+/// | disk 72%`, in module order; modules without a short form are skipped.
+pub(super) fn render_quiet_line(
+    modules: &[ModuleKind],
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> String {
+    let percent = |label: &str, ratio: f64, threshold: Threshold| {
+        let kind = threshold_paint_kind(ratio, 0, threshold).unwrap_or(PaintKind::Green);
+        format!(
+            "{} {}",
+            label,
+            paint(format!("{:.0}%", ratio), kind, settings)
+        )
+    };
+    let mut clauses = Vec::new();
+    for module in modules {
+        let clause = match module {
+            ModuleKind::Host => Some(render_host_name(snapshot, settings)),
+            ModuleKind::Network if snapshot.main_ip != "unknown" => {
+                Some(paint(&snapshot.main_ip, PaintKind::Cyan, settings))
+            }
+            ModuleKind::User => Some(snapshot.current_user.clone()),
+            ModuleKind::Uptime => snapshot
+                .uptime_secs
+                .map(|secs| format!("up {}", format_uptime_short(secs))),
+            ModuleKind::Load => snapshot
+                .load_average
+                .split_whitespace()
+                .next()
+                .map(|one_minute| format!("load {}", one_minute)),
+            ModuleKind::Cpu => snapshot
+                .cpu_usage
+                .map(|usage| percent("cpu", usage, Threshold::default())),
+            ModuleKind::Memory if !is_empty_usage(snapshot.memory) => Some(percent(
+                "mem",
+                snapshot.memory.ratio,
+                settings.thresholds.memory,
+            )),
+            ModuleKind::Swap if !is_empty_usage(snapshot.swap) => Some(percent(
+                "swap",
+                snapshot.swap.ratio,
+                settings.thresholds.swap,
+            )),
+            ModuleKind::Disk => snapshot
+                .root_disk
+                .map(|disk| percent("disk", disk.ratio, settings.thresholds.disk)),
+            ModuleKind::Temperature => snapshot
+                .cpu_temperature
+                .map(|celsius| format!("temp {}", settings.temperature_unit.format(celsius))),
+            _ => None,
+        };
+        clauses.extend(clause);
+    }
+    clauses.join(" | ")
+}
+
 fn render_module_items(
     module: ModuleKind,
     snapshot: &SystemSnapshot,
@@ -655,18 +1192,29 @@ fn render_module_items(
     match module {
         ModuleKind::Host => vec![RenderedItem {
             label: "Host name:".to_string(),
-            value: paint(snapshot.host_name.clone(), PaintKind::Yellow, settings),
+            value: render_host_name(snapshot, settings),
         }],
         ModuleKind::Network => render_network_items(snapshot, settings),
         ModuleKind::User => render_user_items(snapshot, settings),
         ModuleKind::Time => render_time_items(snapshot, settings),
         ModuleKind::Uptime => vec![RenderedItem {
             label: "System uptime:".to_string(),
-            value: paint(snapshot.uptime_str.clone(), PaintKind::Yellow, settings),
+            value: paint(
+                match snapshot.uptime_secs {
+                    Some(secs) if settings.compact => format_uptime_short(secs),
+                    _ => snapshot.uptime_str.clone(),
+                },
+                PaintKind::Yellow,
+                settings,
+            ),
         }],
         ModuleKind::Load => vec![RenderedItem {
             label: "Load average:".to_string(),
-            value: paint(snapshot.load_average.clone(), PaintKind::Yellow, settings),
+            value: paint(
+                snapshot.load_average.clone(),
+                load_average_kind(snapshot),
+                settings,
+            ),
         }],
         ModuleKind::Os => vec![RenderedItem {
             label: "Operating system:".to_string(),
@@ -686,38 +1234,45 @@ fn render_module_items(
                 }]
             }
         }
-        ModuleKind::Virtualization => {
-            if settings.hidden(HiddenField::Virtualization) {
-                Vec::new()
-            } else if let Some(virt) = &snapshot.virt_info {
-                vec![RenderedItem {
-                    label: "Virtualization:".to_string(),
-                    value: paint(virt.clone(), PaintKind::Yellow, settings),
-                }]
-            } else {
-                Vec::new()
-            }
-        }
-        ModuleKind::Cpu => vec![RenderedItem {
-            label: "CPU:".to_string(),
-            value: paint(
-                format!("{} ({} cores)", snapshot.cpu_brand, snapshot.cpu_count),
-                PaintKind::Magenta,
-                settings,
-            ),
-        }],
-        ModuleKind::Memory => render_memory_items(snapshot, settings, combine_usage_bar),
-        ModuleKind::Swap => {
-            if settings.hidden(HiddenField::Swap) || is_empty_usage(snapshot.swap) {
+        ModuleKind::Virtualization => render_virtualization_items(snapshot, settings),
+        ModuleKind::Cpu => with_pressure_item(
+            render_cpu_items(snapshot, settings),
+            "CPU",
+            snapshot.pressure.cpu,
+            settings,
+        ),
+        ModuleKind::Memory => with_pressure_item(
+            render_memory_items(snapshot, settings, combine_usage_bar),
+            "Memory",
+            snapshot.pressure.memory,
+            settings,
+        ),
+        ModuleKind::MemoryAvailable => {
+            if is_empty_usage(snapshot.memory) {
                 Vec::new()
             } else {
                 vec![RenderedItem {
-                    label: "Swap used/total:".to_string(),
-                    value: format_usage(snapshot.swap),
+                    label: "Memory available:".to_string(),
+                    value: {
+                        let (size, suffix) = scaled_gb(snapshot.memory.total_gb);
+                        format!(
+                            "{} {} of {} {}",
+                            size(snapshot.memory.total_gb - snapshot.memory.used_gb),
+                            suffix,
+                            size(snapshot.memory.total_gb),
+                            suffix
+                        )
+                    },
                 }]
             }
         }
-        ModuleKind::Disk => render_disk_items(snapshot, settings, !combine_usage_bar),
+        ModuleKind::Swap => render_swap_items(snapshot, settings),
+        ModuleKind::Disk => with_pressure_item(
+            render_disk_items(snapshot, settings, !combine_usage_bar),
+            "IO",
+            snapshot.pressure.io,
+            settings,
+        ),
         ModuleKind::LastLogin => vec![RenderedItem {
             label: "Last login:".to_string(),
             value: render_last_login_value(&snapshot.last_login, settings),
@@ -731,6 +1286,198 @@ fn render_module_items(
             label: "Pending updates:".to_string(),
             value: paint(snapshot.update_summary.clone(), PaintKind::Yellow, settings),
         }],
+        ModuleKind::NeedRestart => snapshot
+            .needrestart_count
+            .map(|count| RenderedItem {
+                label: "Services to restart:".to_string(),
+                value: if count == 0 {
+                    paint("none", PaintKind::Green, settings)
+                } else {
+                    paint(count.to_string(), PaintKind::Yellow, settings)
+                },
+            })
+            .into_iter()
+            .collect(),
+        ModuleKind::RebootRequired if snapshot.reboot_required == Some(true) => {
+            vec![RenderedItem {
+                label: "Reboot:".to_string(),
+                value: paint(
+                    "*** System restart required ***",
+                    PaintKind::Alert,
+                    settings,
+                ),
+            }]
+        }
+        ModuleKind::RebootRequired => Vec::new(),
+        ModuleKind::Processes => {
+            let mut items = Vec::new();
+            if let Some(count) = snapshot.process_count {
+                items.push(RenderedItem {
+                    label: "Processes:".to_string(),
+                    value: paint(count.to_string(), PaintKind::Cyan, settings),
+                });
+            }
+            if let Some(count) = snapshot.ssh_session_count {
+                items.push(RenderedItem {
+                    label: "SSH sessions:".to_string(),
+                    value: paint(count.to_string(), PaintKind::Cyan, settings),
+                });
+            }
+            items
+        }
+        ModuleKind::Temperature => snapshot
+            .cpu_temperature
+            .map(|celsius| RenderedItem {
+                label: "Temperature:".to_string(),
+                value: paint(
+                    settings.temperature_unit.format(celsius),
+                    threshold_paint_kind(celsius, 1, settings.thresholds.temperature)
+                        .unwrap_or(PaintKind::Green),
+                    settings,
+                ),
+            })
+            .into_iter()
+            .collect(),
+        ModuleKind::AutoUpdates => snapshot
+            .auto_updates_enabled
+            .map(|enabled| RenderedItem {
+                label: "Auto-updates:".to_string(),
+                value: if enabled {
+                    paint("enabled", PaintKind::Green, settings)
+                } else {
+                    paint("disabled", PaintKind::Yellow, settings)
+                },
+            })
+            .into_iter()
+            .collect(),
+        ModuleKind::Certificates => render_certificate_items(snapshot, settings),
+        ModuleKind::FdUsage => render_fd_usage_items(snapshot, settings),
+        ModuleKind::RecentLogins => render_recent_login_items(snapshot, settings),
+        ModuleKind::Oom => snapshot
+            .oom
+            .as_ref()
+            .map(|oom| RenderedItem {
+                label: "OOM events:".to_string(),
+                value: match (oom.count, &oom.last_victim) {
+                    (0, _) => paint("none", PaintKind::Green, settings),
+                    (count, Some(victim)) => paint(
+                        format!("{} (last: {})", count, victim),
+                        PaintKind::Red,
+                        settings,
+                    ),
+                    (count, None) => paint(count.to_string(), PaintKind::Red, settings),
+                },
+            })
+            .into_iter()
+            .collect(),
+        ModuleKind::AuthorizedKeys => snapshot
+            .authorized_keys
+            .iter()
+            .map(|file| RenderedItem {
+                label: match &file.owner {
+                    Some(owner) => format!("Authorized keys ({}):", owner),
+                    None => "Authorized keys:".to_string(),
+                },
+                value: paint(file.keys.len().to_string(), PaintKind::Yellow, settings),
+            })
+            .collect(),
+        ModuleKind::UpdateCheck => snapshot
+            .newer_release
+            .as_ref()
+            .map(|latest| RenderedItem {
+                label: "motdyn update:".to_string(),
+                value: format!(
+                    "{} available (running {})",
+                    paint(latest.clone(), PaintKind::Yellow, settings),
+                    env!("CARGO_PKG_VERSION")
+                ),
+            })
+            .into_iter()
+            .collect(),
+        ModuleKind::TopUsers if !snapshot.top_users.is_empty() => vec![RenderedItem {
+            label: "Top users:".to_string(),
+            value: snapshot
+                .top_users
+                .iter()
+                .map(|entry| format!("{} ({})", entry.user, entry.processes))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }],
+        ModuleKind::TopUsers => Vec::new(),
+        ModuleKind::Addresses => render_interface_address_items(snapshot, settings, false),
+        ModuleKind::FailedUnits if !snapshot.failed_units.is_empty() => vec![RenderedItem {
+            label: "Failed units:".to_string(),
+            value: paint(snapshot.failed_units.join(", "), PaintKind::Red, settings),
+        }],
+        ModuleKind::FailedUnits => Vec::new(),
+        ModuleKind::Pools => snapshot
+            .storage_pools
+            .iter()
+            .map(|pool| {
+                let usage = with_progress_bar(
+                    format_disk_usage(pool, settings.disk_display),
+                    pool.ratio,
+                    settings,
+                );
+                RenderedItem {
+                    label: pool.label.clone(),
+                    value: if settings.disk_alerts_enabled(&pool.mount_path) {
+                        paint_usage(usage, pool.ratio, settings.thresholds.disk, settings)
+                    } else {
+                        usage
+                    },
+                }
+            })
+            .collect(),
+        ModuleKind::Battery => snapshot
+            .batteries
+            .iter()
+            .map(|battery| RenderedItem {
+                label: "Battery:".to_string(),
+                value: format_battery(battery, snapshot.batteries.len() > 1, settings),
+            })
+            .collect(),
+        ModuleKind::Link => snapshot
+            .network_links
+            .iter()
+            .map(|link| RenderedItem {
+                label: "Link:".to_string(),
+                value: format_network_link(link, settings),
+            })
+            .collect(),
+        ModuleKind::ContainerStorage => snapshot
+            .container_storage
+            .map(|storage| RenderedItem {
+                label: "Container storage:".to_string(),
+                value: format_container_storage(storage, settings),
+            })
+            .into_iter()
+            .collect(),
+        ModuleKind::Mac => snapshot
+            .mac_status
+            .map(|status| RenderedItem {
+                label: "MAC:".to_string(),
+                value: render_mac_value(status, settings),
+            })
+            .into_iter()
+            .collect(),
+        ModuleKind::TopCpu => snapshot
+            .top_cpu
+            .as_ref()
+            .map(|top| RenderedItem {
+                label: "Top CPU:".to_string(),
+                value: format!(
+                    "{} ({})",
+                    top.name,
+                    paint(
+                        format!("{:.1}%", top.cpu_percent),
+                        PaintKind::Yellow,
+                        settings
+                    )
+                ),
+            })
+            .into_iter()
+            .collect(),
     }
 }
 
@@ -752,13 +1499,36 @@ fn render_network_items(snapshot: &SystemSnapshot, settings: &OutputSettings) ->
         }
     }
 
-    if parts.is_empty() {
-        Vec::new()
-    } else {
-        vec![RenderedItem {
+    let mut items = Vec::new();
+    if !parts.is_empty() {
+        items.push(RenderedItem {
             label: "Main NIC:".to_string(),
             value: parts.join(" "),
-        }]
+        });
+    }
+    if let Some(throughput) = snapshot.net_throughput {
+        items.push(RenderedItem {
+            label: "Network (rx/tx):".to_string(),
+            value: format_net_throughput(throughput),
+        });
+    }
+    items
+}
+
+/// `12.3 GB / 4.5 GB since boot`, or `1.2 MB/s / 300 B/s` when sampled.
+fn format_net_throughput(throughput: NetThroughput) -> String {
+    if throughput.per_sec {
+        format!(
+            "{} / {}",
+            format_byte_rate(throughput.rx_bytes),
+            format_byte_rate(throughput.tx_bytes)
+        )
+    } else {
+        format!(
+            "{} / {} since boot",
+            format_bytes(throughput.rx_bytes),
+            format_bytes(throughput.tx_bytes)
+        )
     }
 }
 
@@ -766,9 +1536,15 @@ fn render_user_items(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Ve
     let mut value = paint(snapshot.current_user.clone(), PaintKind::Cyan, settings);
 
     if !settings.hidden(HiddenField::SourceIp) {
+        let source = match (&snapshot.from_host, snapshot.from_ip.as_str()) {
+            // Without SSH_CONNECTION the session did not come in over SSH.
+            (_, "unknown") => "local console".to_string(),
+            (Some(host), ip) => format!("{} [{}]", host, ip),
+            (None, ip) => ip.to_string(),
+        };
         value.push_str(&format!(
             " (from {})",
-            paint(snapshot.from_ip.clone(), PaintKind::Cyan, settings)
+            paint(source, PaintKind::Cyan, settings)
         ));
     }
     if !settings.hidden(HiddenField::LoginUserCount) {
@@ -788,13 +1564,26 @@ fn render_user_items(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Ve
     }]
 }
 
+/// Drops a trailing ` +08:00` style offset; a custom `time_format` without
+/// one is returned unchanged.
+pub(super) fn strip_utc_offset(time: &str) -> &str {
+    match time.rsplit_once(' ') {
+        Some((rest, offset))
+            if offset.starts_with(['+', '-'])
+                && offset.len() > 1
+                && offset[1..]
+                    .chars()
+                    .all(|ch| ch.is_ascii_digit() || ch == ':') =>
+        {
+            rest
+        }
+        _ => time,
+    }
+}
+
 fn render_time_items(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Vec<RenderedItem> {
     let value = if settings.hidden(HiddenField::Timezone) {
-        snapshot
-            .now_str_with_tz
-            .rsplit_once(' ')
-            .map(|(time, _)| time.to_string())
-            .unwrap_or_else(|| snapshot.now_str_with_tz.clone())
+        strip_utc_offset(&snapshot.now_str_with_tz).to_string()
     } else {
         snapshot.now_str_with_tz.clone()
     };
@@ -807,7 +1596,7 @@ fn render_time_items(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Ve
 
 fn render_memory_items(
     snapshot: &SystemSnapshot,
-    _settings: &OutputSettings,
+    settings: &OutputSettings,
     combine_usage_bar: bool,
 ) -> Vec<RenderedItem> {
     if combine_usage_bar {
@@ -815,15 +1604,30 @@ fn render_memory_items(
             .root_disk
             .map(|root_disk| RenderedItem {
                 label: "Resource use:".to_string(),
-                value: format_combined_usage_bar(snapshot.memory, root_disk, _settings),
+                value: format_combined_usage_bar(snapshot.memory, root_disk, settings),
             })
             .into_iter()
             .collect();
     }
 
+    let label = match (snapshot.memory_cgroup, settings.disk_display) {
+        (true, _) => "Memory (cgroup):",
+        (false, UsageDisplay::UsedTotal) => "Memory used/total:",
+        (false, UsageDisplay::UsedFree) => "Memory used/free:",
+        (false, UsageDisplay::FreeTotal) => "Memory free/total:",
+    };
     vec![RenderedItem {
-        label: "Memory used/total:".to_string(),
-        value: format_usage(snapshot.memory),
+        label: label.to_string(),
+        value: paint_usage(
+            with_progress_bar(
+                format_usage_as(snapshot.memory, settings.disk_display),
+                snapshot.memory.ratio,
+                settings,
+            ),
+            snapshot.memory.ratio,
+            settings.thresholds.memory,
+            settings,
+        ),
     }]
 }
 
@@ -832,16 +1636,83 @@ fn render_disk_items(
     settings: &OutputSettings,
     include_root: bool,
 ) -> Vec<RenderedItem> {
-    snapshot
+    let mut disks = snapshot
         .disk_items
         .iter()
-        .filter(|item| {
-            (include_root || !item.label.eq_ignore_ascii_case("Disk usage (root):"))
+        .filter(|disk| {
+            (include_root || !disk.label.eq_ignore_ascii_case("Disk usage (root):"))
                 && !(settings.hidden(HiddenField::NfsDisks)
-                    && item.label.eq_ignore_ascii_case("Disk usage (nfs):"))
+                    && disk.label.eq_ignore_ascii_case("Disk usage (nfs):"))
         })
-        .cloned()
-        .collect()
+        .collect::<Vec<_>>();
+
+    let hidden = match settings.max_disk_lines {
+        Some(limit) if disks.len() > limit => {
+            disks.sort_by(|left, right| {
+                right
+                    .ratio
+                    .total_cmp(&left.ratio)
+                    .then_with(|| left.mount_path.cmp(&right.mount_path))
+            });
+            let hidden = disks.len() - limit;
+            disks.truncate(limit);
+            hidden
+        }
+        _ => 0,
+    };
+
+    // Pad mount paths so the usage figures form their own column.
+    let path_width = disks
+        .iter()
+        .map(|disk| visible_width(settings.disk_label(&disk.mount_path)))
+        .max()
+        .unwrap_or(0);
+    let mut items = disks
+        .into_iter()
+        .map(|disk| {
+            let name = settings.disk_label(&disk.mount_path);
+            let usage = if disk.timed_out {
+                paint("(timed out)", PaintKind::Yellow, settings)
+            } else if settings.disk_alerts_enabled(&disk.mount_path) {
+                paint_usage(
+                    with_progress_bar(
+                        format_disk_usage(disk, settings.disk_display),
+                        disk.ratio,
+                        settings,
+                    ),
+                    disk.ratio,
+                    settings.thresholds.disk,
+                    settings,
+                )
+            } else {
+                with_progress_bar(
+                    format_disk_usage(disk, settings.disk_display),
+                    disk.ratio,
+                    settings,
+                )
+            };
+            RenderedItem {
+                label: disk.label.clone(),
+                value: format!(
+                    "{}{}  {}",
+                    name,
+                    " ".repeat(path_width - visible_width(name)),
+                    usage
+                ),
+            }
+        })
+        .collect::<Vec<_>>();
+    if hidden > 0 {
+        items.push(RenderedItem {
+            label: String::new(),
+            value: paint(
+                format!("(+{} more mounts)", hidden),
+                PaintKind::Dim,
+                settings,
+            ),
+        });
+    }
+    items
 }
 
 fn render_service_items(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Vec<RenderedItem> {
@@ -855,21 +1726,308 @@ fn render_service_items(snapshot: &SystemSnapshot, settings: &OutputSettings) ->
         .collect()
 }
 
+fn render_certificate_items(
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> Vec<RenderedItem> {
+    if snapshot.certificates.is_empty() {
+        return vec![RenderedItem {
+            label: "Certificates:".to_string(),
+            value: "no certificates configured".to_string(),
+        }];
+    }
+
+    snapshot
+        .certificates
+        .iter()
+        .map(|status| RenderedItem {
+            label: format!("{} cert:", status.label),
+            value: match &status.days_left {
+                Ok(days) if *days < 0 => paint(
+                    format!("expired {} ago", format_day_count(days.unsigned_abs())),
+                    PaintKind::Red,
+                    settings,
+                ),
+                Ok(days) => paint(
+                    format!("{} left", format_day_count(days.unsigned_abs())),
+                    if *days < CERT_EXPIRY_WARNING_DAYS {
+                        PaintKind::Red
+                    } else {
+                        PaintKind::Green
+                    },
+                    settings,
+                ),
+                Err(_) => paint("unreadable", PaintKind::Yellow, settings),
+            },
+        })
+        .collect()
+}
+
+fn render_swap_items(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Vec<RenderedItem> {
+    // Swapless hosts (containers, many cloud images) report `SwapTotal: 0`;
+    // a `0.00/0.00 GB` line and idle swap activity would only be noise.
+    if settings.hidden(HiddenField::Swap) || is_empty_usage(snapshot.swap) {
+        return Vec::new();
+    }
+    let mut items = vec![RenderedItem {
+        label: "Swap used/total:".to_string(),
+        value: paint_usage(
+            with_progress_bar(format_usage(snapshot.swap), snapshot.swap.ratio, settings),
+            snapshot.swap.ratio,
+            settings.thresholds.swap,
+            settings,
+        ),
+    }];
+    if let Some(activity) = snapshot.swap_activity {
+        items.push(RenderedItem {
+            label: "Swap activity:".to_string(),
+            value: render_swap_activity_value(activity, settings),
+        });
+    }
+    items
+}
+
+fn render_virtualization_items(
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> Vec<RenderedItem> {
+    let mut items = Vec::new();
+    if !settings.hidden(HiddenField::Virtualization)
+        && let Some(virt) = &snapshot.virt_info
+    {
+        items.push(RenderedItem {
+            label: "Virtualization:".to_string(),
+            value: paint(virt.clone(), PaintKind::Yellow, settings),
+        });
+    }
+    if let Some(counts) = snapshot.containers {
+        items.push(RenderedItem {
+            label: "Containers:".to_string(),
+            value: format!(
+                "{} running / {} total",
+                paint(counts.running.to_string(), PaintKind::Green, settings),
+                counts.total
+            ),
+        });
+    }
+    items
+}
+
+fn render_cpu_items(snapshot: &SystemSnapshot, settings: &OutputSettings) -> Vec<RenderedItem> {
+    let summary = match snapshot.cpu_topology {
+        Some(topology) => format_cpu_topology(&snapshot.cpu_brand, topology),
+        None => match snapshot.cpu_physical_cores {
+            Some(cores) => format!(
+                "{} ({} cores / {} threads)",
+                snapshot.cpu_brand, cores, snapshot.cpu_count
+            ),
+            None => format!("{} ({} threads)", snapshot.cpu_brand, snapshot.cpu_count),
+        },
+    };
+    let mut items = vec![RenderedItem {
+        label: "CPU:".to_string(),
+        value: paint(summary, PaintKind::Magenta, settings),
+    }];
+    if let Some(quota) = snapshot.cpu_quota {
+        let online = snapshot.cpu_online.unwrap_or(snapshot.cpu_count);
+        items.push(RenderedItem {
+            label: "CPUs:".to_string(),
+            value: format!(
+                "{} online ({} allowed)",
+                online,
+                paint(format!("{:.1}", quota), PaintKind::Yellow, settings)
+            ),
+        });
+    }
+    if let Some(usage) = snapshot.cpu_usage {
+        items.push(RenderedItem {
+            label: "CPU usage:".to_string(),
+            value: paint(
+                format!("{:.1}%", usage),
+                threshold_paint_kind(usage, 1, Threshold::default()).unwrap_or(PaintKind::Green),
+                settings,
+            ),
+        });
+    }
+    for gpu in &snapshot.gpus {
+        items.push(RenderedItem {
+            label: "GPU:".to_string(),
+            value: format_gpu(gpu, settings),
+        });
+    }
+    items
+}
+
+/// `78% (Discharging)`, prefixed with the supply name when there are
+/// several; red below `battery_low`.
+fn format_battery(battery: &BatteryStatus, named: bool, settings: &OutputSettings) -> String {
+    let kind = if f64::from(battery.capacity) < settings.thresholds.battery_low {
+        PaintKind::Red
+    } else {
+        PaintKind::Green
+    };
+    let value = format!(
+        "{} ({})",
+        paint(format!("{}%", battery.capacity), kind, settings),
+        battery.status
+    );
+    if named {
+        format!("{} {}", battery.name, value)
+    } else {
+        value
+    }
+}
+
+/// Model name followed by VRAM used/total in GB, colored by the memory
+/// thresholds; just the name when the driver does not report memory.
+fn format_gpu(gpu: &GpuInfo, settings: &OutputSettings) -> String {
+    let name = paint(&gpu.name, PaintKind::Magenta, settings);
+    match (gpu.memory_used_mib, gpu.memory_total_mib) {
+        (Some(used), Some(total)) if total > 0 => {
+            let ratio = used as f64 / total as f64 * 100.0;
+            let vram = format!(
+                "{:.2}/{:.2} GB VRAM",
+                used as f64 / 1024.0,
+                total as f64 / 1024.0
+            );
+            format!(
+                "{} ({})",
+                name,
+                paint_usage(vram, ratio, settings.thresholds.memory, settings)
+            )
+        }
+        _ => name,
+    }
+}
+
+fn render_fd_usage_items(
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> Vec<RenderedItem> {
+    snapshot
+        .fd_usages
+        .iter()
+        .map(|status| {
+            let value = match status.usage {
+                Ok(FdUsage {
+                    open,
+                    limit: Some(limit),
+                    ..
+                }) if limit > 0 => {
+                    let ratio = open as f64 / limit as f64 * 100.0;
+                    let kind = match usage_status_label(ratio) {
+                        Some("critical") => PaintKind::Red,
+                        Some(_) => PaintKind::Yellow,
+                        None => PaintKind::Green,
+                    };
+                    paint(format!("{}/{}", open, limit), kind, settings)
+                }
+                Ok(FdUsage { open, .. }) => {
+                    paint(format!("{} (no limit)", open), PaintKind::Green, settings)
+                }
+                Err(_) => paint("unavailable", PaintKind::Yellow, settings),
+            };
+            RenderedItem {
+                label: format!("{} fds:", status.label),
+                value: match status.usage {
+                    Ok(FdUsage {
+                        connections: Some(connections),
+                        ..
+                    }) => format!("{}, {} connections", value, connections),
+                    _ => value,
+                },
+            }
+        })
+        .collect()
+}
+
+fn render_recent_login_items(
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> Vec<RenderedItem> {
+    if snapshot.recent_logins.is_empty() {
+        return vec![RenderedItem {
+            label: "Recent logins:".to_string(),
+            value: paint("none recorded", PaintKind::Dim, settings),
+        }];
+    }
+
+    snapshot
+        .recent_logins
+        .iter()
+        .enumerate()
+        .map(|(idx, login)| {
+            let when = Local
+                .timestamp_opt(login.when_secs as i64, 0)
+                .single()
+                .map(|when| when.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| login.when_secs.to_string());
+            let source = if login.from == "unknown" {
+                "local".to_string()
+            } else {
+                format!(
+                    "from {}",
+                    paint(login.from.clone(), PaintKind::Cyan, settings)
+                )
+            };
+            RenderedItem {
+                label: if idx == 0 {
+                    "Recent logins:".to_string()
+                } else {
+                    String::new()
+                },
+                value: format!("{} {} at {}", login.user, source, when),
+            }
+        })
+        .collect()
+}
+
+fn format_day_count(days: u64) -> String {
+    if days == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", days)
+    }
+}
+
 fn module_section(module: ModuleKind) -> SectionKind {
     match module {
-        ModuleKind::Host | ModuleKind::Network | ModuleKind::User => SectionKind::Identity,
-        ModuleKind::Time | ModuleKind::Uptime | ModuleKind::Load => SectionKind::Runtime,
+        ModuleKind::Host
+        | ModuleKind::Network
+        | ModuleKind::Link
+        | ModuleKind::Addresses
+        | ModuleKind::User => SectionKind::Identity,
+        ModuleKind::Time | ModuleKind::Uptime | ModuleKind::Load | ModuleKind::Processes => {
+            SectionKind::Runtime
+        }
         ModuleKind::Os
         | ModuleKind::Kernel
         | ModuleKind::Virtualization
         | ModuleKind::Cpu
         | ModuleKind::Memory
-        | ModuleKind::Swap => SectionKind::System,
-        ModuleKind::Disk => SectionKind::Storage,
+        | ModuleKind::MemoryAvailable
+        | ModuleKind::Swap
+        | ModuleKind::Temperature
+        | ModuleKind::Battery => SectionKind::System,
+        ModuleKind::Disk | ModuleKind::Pools => SectionKind::Storage,
         ModuleKind::LastLogin
         | ModuleKind::FailedLogin
+        | ModuleKind::AuthorizedKeys
         | ModuleKind::Services
-        | ModuleKind::Updates => SectionKind::Operations,
+        | ModuleKind::FailedUnits
+        | ModuleKind::Updates
+        | ModuleKind::NeedRestart
+        | ModuleKind::AutoUpdates
+        | ModuleKind::RebootRequired
+        | ModuleKind::Certificates
+        | ModuleKind::FdUsage
+        | ModuleKind::RecentLogins
+        | ModuleKind::Oom
+        | ModuleKind::TopCpu
+        | ModuleKind::TopUsers
+        | ModuleKind::Mac
+        | ModuleKind::ContainerStorage
+        | ModuleKind::UpdateCheck => SectionKind::Operations,
     }
 }
 
@@ -907,17 +2065,178 @@ fn normalize_module_name(name: &str) -> Option<ModuleKind> {
         "failed_login" | "failedlogin" | "failed" | "security" => Some(ModuleKind::FailedLogin),
         "services" | "service" | "systemd" => Some(ModuleKind::Services),
         "updates" | "update" | "packages" | "package_updates" => Some(ModuleKind::Updates),
+        "needrestart" | "need_restart" | "restarts" => Some(ModuleKind::NeedRestart),
+        "auto_updates" | "unattended_upgrades" | "dnf_automatic" => Some(ModuleKind::AutoUpdates),
+        "reboot_required" | "reboot" | "restart_required" => Some(ModuleKind::RebootRequired),
+        "certificates" | "certs" | "cert_checks" => Some(ModuleKind::Certificates),
+        "fd_usage" | "fds" | "service_fds" => Some(ModuleKind::FdUsage),
+        "recent_logins" | "recent" | "login_history" => Some(ModuleKind::RecentLogins),
+        "memory_available" | "mem_available" | "available_memory" => {
+            Some(ModuleKind::MemoryAvailable)
+        }
+        "oom" | "oom_events" | "oom_kills" => Some(ModuleKind::Oom),
+        "top_cpu" | "cpu_top" | "top_process" => Some(ModuleKind::TopCpu),
+        "top_users" | "users_top" | "process_owners" => Some(ModuleKind::TopUsers),
+        "mac" | "selinux" | "apparmor" | "lsm" => Some(ModuleKind::Mac),
+        "container_storage" | "docker_df" | "podman_df" => Some(ModuleKind::ContainerStorage),
+        "link" | "links" | "link_speed" => Some(ModuleKind::Link),
+        "addresses" | "ip_addresses" | "ips" | "interfaces" => Some(ModuleKind::Addresses),
+        "failed_units" | "failed_services" | "systemd_failed" => Some(ModuleKind::FailedUnits),
+        "battery" | "batteries" | "power" => Some(ModuleKind::Battery),
+        "pools" | "storage_pools" | "zfs" | "zpool" | "btrfs" => Some(ModuleKind::Pools),
+        "update_check" | "self_update" | "motdyn_update" => Some(ModuleKind::UpdateCheck),
+        "authorized_keys" | "ssh_keys" | "authorized_ssh_keys" => Some(ModuleKind::AuthorizedKeys),
+        "temperature" | "temp" | "thermal" | "sensors" => Some(ModuleKind::Temperature),
+        "processes" | "procs" | "ssh_sessions" | "sessions" => Some(ModuleKind::Processes),
         _ => None,
     }
 }
 
 fn format_usage(summary: UsageSummary) -> String {
-    format!(
-        "{:.2}/{:.2} GB ({:.2}%)",
-        summary.used_gb, summary.total_gb, summary.ratio
+    format_usage_as(summary, UsageDisplay::UsedTotal)
+}
+
+fn format_usage_as(summary: UsageSummary, display: UsageDisplay) -> String {
+    let free_gb = (summary.total_gb - summary.used_gb).max(0.0);
+    let (size, suffix) = scaled_gb(summary.used_gb.max(summary.total_gb));
+    match display {
+        UsageDisplay::UsedTotal => format!(
+            "{}/{} {} ({:.2}%)",
+            size(summary.used_gb),
+            size(summary.total_gb),
+            suffix,
+            summary.ratio
+        ),
+        UsageDisplay::UsedFree => format!(
+            "{} {} used, {} {} free",
+            size(summary.used_gb),
+            suffix,
+            size(free_gb),
+            suffix
+        ),
+        UsageDisplay::FreeTotal => format!(
+            "{} {} free of {} {}",
+            size(free_gb),
+            suffix,
+            size(summary.total_gb),
+            suffix
+        ),
+    }
+}
+
+/// A single memory or swap figure, such as `512.00 MB` or `1.50 TB`.
+pub(super) fn format_gb(gb: f64) -> String {
+    let (size, suffix) = scaled_gb(gb);
+    format!("{} {}", size(gb), suffix)
+}
+
+/// Formats GB figures with two decimals in the unit `best_unit_scale` picks
+/// for `largest_gb`, so figures printed side by side share one unit.
+fn scaled_gb(largest_gb: f64) -> (impl Fn(f64) -> String, &'static str) {
+    let (scale, suffix) = best_unit_scale(largest_gb * BYTES_PER_GB);
+    (
+        move |gb: f64| format!("{:.2}", gb * BYTES_PER_GB / scale),
+        suffix,
+    )
+}
+
+pub(super) fn format_disk_usage(disk: &DiskUsageItem, display: UsageDisplay) -> String {
+    let free_bytes = disk.total_bytes.saturating_sub(disk.used_bytes);
+    let (scale, suffix) = best_unit_scale(disk.used_bytes.max(disk.total_bytes) as f64);
+    let size = |bytes: u64| format!("{:.2} {}", bytes as f64 / scale, suffix);
+    match display {
+        UsageDisplay::UsedTotal => format!(
+            "{}/{} ({:.2}%)",
+            size(disk.used_bytes),
+            size(disk.total_bytes),
+            disk.ratio
+        ),
+        UsageDisplay::UsedFree => {
+            format!("{} used, {} free", size(disk.used_bytes), size(free_bytes))
+        }
+        UsageDisplay::FreeTotal => {
+            format!("{} free of {}", size(free_bytes), size(disk.total_bytes))
+        }
+    }
+}
+
+/// Appends `CPU pressure (10s): 2.3%` when `show_pressure` found the file,
+/// colored once `avg10` reaches the pressure thresholds.
+fn with_pressure_item(
+    mut items: Vec<RenderedItem>,
+    resource: &str,
+    pressure: Option<PressureAverages>,
+    settings: &OutputSettings,
+) -> Vec<RenderedItem> {
+    if let Some(pressure) = pressure {
+        let value = format!("{:.1}%", pressure.avg10);
+        items.push(RenderedItem {
+            label: format!("{} pressure (10s):", resource),
+            value: match threshold_paint_kind(pressure.avg10, 1, settings.thresholds.pressure) {
+                Some(kind) => paint(value, kind, settings),
+                None => value,
+            },
+        });
+    }
+    items
+}
+
+/// Any paging in either direction is painted red: it is the thrashing signal.
+fn render_swap_activity_value(activity: SwapActivity, settings: &OutputSettings) -> String {
+    let kind = if activity.in_bytes_per_sec > 0 || activity.out_bytes_per_sec > 0 {
+        PaintKind::Red
+    } else {
+        PaintKind::Green
+    };
+    paint(
+        format!(
+            "in {}, out {}",
+            format_byte_rate(activity.in_bytes_per_sec),
+            format_byte_rate(activity.out_bytes_per_sec)
+        ),
+        kind,
+        settings,
     )
 }
 
+/// One decimal in the largest unit that keeps the value at or above 1.
+pub(super) fn format_bytes(bytes: u64) -> String {
+    let (scale, suffix) = best_unit_scale(bytes as f64);
+    format!("{:.1} {}", bytes as f64 / scale, suffix)
+}
+
+/// Like [`format_bytes`] per second, but whole bytes below 1 KB.
+fn format_byte_rate(bytes_per_sec: u64) -> String {
+    let (scale, suffix) = best_unit_scale(bytes_per_sec as f64);
+    if scale == 1.0 {
+        format!("{} B/s", bytes_per_sec)
+    } else {
+        format!("{:.1} {}/s", bytes_per_sec as f64 / scale, suffix)
+    }
+}
+
+pub(super) fn best_unit_scale(bytes: f64) -> (f64, &'static str) {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const TIB: f64 = 1024.0 * 1024.0 * 1024.0 * 1024.0;
+    const PIB: f64 = 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0;
+
+    if bytes >= PIB {
+        (PIB, "PB")
+    } else if bytes >= TIB {
+        (TIB, "TB")
+    } else if bytes >= GIB {
+        (GIB, "GB")
+    } else if bytes >= MIB {
+        (MIB, "MB")
+    } else if bytes >= KIB {
+        (KIB, "KB")
+    } else {
+        (1.0, "B")
+    }
+}
+
 fn format_combined_usage_bar(
     memory: UsageSummary,
     root_disk: UsageSummary,
@@ -925,8 +2244,18 @@ fn format_combined_usage_bar(
 ) -> String {
     format!(
         "{}  {}",
-        format_usage_meter("mem", memory.ratio, settings),
-        format_usage_meter("disk", root_disk.ratio, settings)
+        format_usage_meter(
+            "mem",
+            memory.ratio,
+            Some(settings.thresholds.memory),
+            settings
+        ),
+        format_usage_meter(
+            "disk",
+            root_disk.ratio,
+            Some(settings.thresholds.disk).filter(|_| settings.disk_alerts_enabled("/")),
+            settings
+        )
     )
 }
 
@@ -953,9 +2282,25 @@ fn format_bar(value: f64, width: usize, settings: &OutputSettings) -> String {
         .collect()
 }
 
-fn format_usage_meter(label: &str, ratio: f64, settings: &OutputSettings) -> String {
+/// Prefixes a usage figure with `[████░░░░]` when `progress_bars` is on, so
+/// the bar picks up the same threshold color as the figure.
+fn with_progress_bar(text: String, ratio: f64, settings: &OutputSettings) -> String {
+    match settings.progress_bar_width {
+        Some(width) => format!("[{}] {}", format_bar(ratio, width, settings), text),
+        None => text,
+    }
+}
+
+/// `threshold` is `None` for mounts listed in `disk_no_alert`.
+fn format_usage_meter(
+    label: &str,
+    ratio: f64,
+    threshold: Option<Threshold>,
+    settings: &OutputSettings,
+) -> String {
     let label = paint(label, PaintKind::Dim, settings);
-    let meter = match usage_status_label(ratio) {
+    let status = usage_status_label(ratio).filter(|_| threshold.is_some());
+    let meter = match status {
         Some(status) => format!(
             "{} {} {}",
             format_percent(ratio),
@@ -971,13 +2316,245 @@ fn format_usage_meter(label: &str, ratio: f64, settings: &OutputSettings) -> Str
     format!(
         "{} {}",
         label,
-        paint(meter, usage_paint_kind(ratio), settings)
+        paint(
+            meter,
+            threshold
+                .and_then(|threshold| threshold_paint_kind(ratio.clamp(0.0, 100.0), 0, threshold))
+                .unwrap_or(PaintKind::Dim),
+            settings
+        )
+    )
+}
+
+/// Paints a usage line yellow or red once the ratio it prints (with two
+/// decimals) reaches the warn or critical threshold; plain text otherwise.
+fn paint_usage(
+    text: String,
+    ratio: f64,
+    threshold: Threshold,
+    settings: &OutputSettings,
+) -> String {
+    match threshold_paint_kind(ratio, 2, threshold) {
+        Some(kind) => paint(text, kind, settings),
+        None => text,
+    }
+}
+
+/// Judges the ratio as rounded for display, so a line printed as `90.00%`
+/// is red at `crit = 90` even when the raw ratio is 89.996.
+pub(super) fn threshold_paint_kind(
+    ratio: f64,
+    decimals: usize,
+    threshold: Threshold,
+) -> Option<PaintKind> {
+    let shown = format!("{:.*}", decimals, ratio)
+        .parse::<f64>()
+        .unwrap_or(ratio);
+    if shown >= threshold.crit {
+        Some(PaintKind::Red)
+    } else if shown >= threshold.warn {
+        Some(PaintKind::Yellow)
+    } else {
+        None
+    }
+}
+
+/// Red once the 1-minute load exceeds the online cores; yellow otherwise.
+pub(super) fn load_average_kind(snapshot: &SystemSnapshot) -> PaintKind {
+    let cores = snapshot.cpu_online.unwrap_or(snapshot.cpu_count);
+    let one_minute = snapshot
+        .load_average
+        .split_whitespace()
+        .next()
+        .and_then(|value| value.parse::<f64>().ok());
+    match one_minute {
+        Some(load) if cores > 0 && load > cores as f64 => PaintKind::Red,
+        _ => PaintKind::Yellow,
+    }
+}
+
+/// One `IP addresses:` line per interface, such as `eth0: 10.0.0.5,
+/// 2001:db8::5`; link-local IPv6 addresses only when `link_local` is set.
+fn render_interface_address_items(
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+    link_local: bool,
+) -> Vec<RenderedItem> {
+    let mut interfaces: Vec<(&str, Vec<String>)> = Vec::new();
+    for entry in &snapshot.interface_addresses {
+        if entry.link_local != link_local {
+            continue;
+        }
+        let address = paint(&entry.address, PaintKind::Cyan, settings);
+        match interfaces
+            .iter_mut()
+            .find(|(iface, _)| *iface == entry.iface)
+        {
+            Some((_, addresses)) => addresses.push(address),
+            None => interfaces.push((&entry.iface, vec![address])),
+        }
+    }
+    let label = if link_local {
+        "Link-local addresses:"
+    } else {
+        "IP addresses:"
+    };
+    interfaces
+        .into_iter()
+        .map(|(iface, addresses)| RenderedItem {
+            label: label.to_string(),
+            value: format!("{}: {}", iface, addresses.join(", ")),
+        })
+        .collect()
+}
+
+/// `eth0: up 10Gb/s full`; any interface that is not up is painted red.
+fn format_network_link(link: &NetworkLink, settings: &OutputSettings) -> String {
+    let mut state = vec![link.operstate.clone()];
+    if let Some(speed) = link.speed_mbps {
+        state.push(format_link_speed(speed));
+    }
+    if let Some(duplex) = &link.duplex {
+        state.push(duplex.clone());
+    }
+    let kind = if link.operstate == "up" {
+        PaintKind::Green
+    } else {
+        PaintKind::Red
+    };
+    format!("{}: {}", link.iface, paint(state.join(" "), kind, settings))
+}
+
+pub(super) fn format_link_speed(mbps: u32) -> String {
+    if mbps >= 1000 && mbps.is_multiple_of(100) {
+        let gbps = format!("{:.1}", mbps as f64 / 1000.0);
+        format!("{}Gb/s", gbps.trim_end_matches(".0"))
+    } else {
+        format!("{}Mb/s", mbps)
+    }
+}
+
+fn format_container_storage(storage: ContainerStorage, settings: &OutputSettings) -> String {
+    let reclaimable = format!("{} reclaimable", format_bytes(storage.reclaimable_bytes));
+    let kind = if storage.reclaimable_bytes > 0 {
+        PaintKind::Yellow
+    } else {
+        PaintKind::Green
+    };
+    format!(
+        "{} ({})",
+        format_bytes(storage.total_bytes),
+        paint(reclaimable, kind, settings)
     )
 }
 
-fn usage_paint_kind(ratio: f64) -> PaintKind {
-    let _ = ratio;
-    PaintKind::Dim
+/// Anything short of an enforcing policy is painted yellow.
+fn render_mac_value(status: MacStatus, settings: &OutputSettings) -> String {
+    match status {
+        MacStatus::SeLinux(mode) => {
+            let kind = if mode == SeLinuxMode::Enforcing {
+                PaintKind::Green
+            } else {
+                PaintKind::Yellow
+            };
+            format!("SELinux {}", paint(mode.label(), kind, settings))
+        }
+        MacStatus::AppArmor { enabled: false, .. } => {
+            format!(
+                "AppArmor {}",
+                paint("Disabled", PaintKind::Yellow, settings)
+            )
+        }
+        MacStatus::AppArmor {
+            enabled: true,
+            profiles,
+        } => {
+            let counts = profiles
+                .map(|(enforce, complain)| format!(" ({} enforce, {} complain)", enforce, complain))
+                .unwrap_or_default();
+            format!(
+                "AppArmor {}{}",
+                paint("Enabled", PaintKind::Green, settings),
+                counts
+            )
+        }
+        MacStatus::None => paint("none detected", PaintKind::Yellow, settings),
+    }
+}
+
+/// Two-unit uptime for compact output: `3d5h`, `5h17m`, `17m`, `42s`.
+pub(super) fn format_uptime_short(secs: u64) -> String {
+    let days = secs / 86_400;
+    let hours = secs % 86_400 / 3_600;
+    let minutes = secs % 3_600 / 60;
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", secs),
+        (0, 0, _) => format!("{}m", minutes),
+        (0, _, 0) => format!("{}h", hours),
+        (0, _, _) => format!("{}h{}m", hours, minutes),
+        (_, 0, _) => format!("{}d", days),
+        _ => format!("{}d{}h", days, hours),
+    }
+}
+
+fn format_kernel_preemption(preemption: KernelPreemption) -> String {
+    let model = if preemption.realtime {
+        "PREEMPT_RT"
+    } else {
+        "(standard)"
+    };
+    match preemption.tick_hz {
+        Some(hz) => format!("{}, {} Hz tick", model, hz),
+        None => model.to_string(),
+    }
+}
+
+/// Formats `2x AMD EPYC (48c/96t, 2 NUMA nodes)`; single-socket and single-node parts are left out.
+fn format_cpu_topology(brand: &str, topology: CpuTopology) -> String {
+    let cores = topology.sockets * topology.cores_per_socket;
+    let threads = cores * topology.threads_per_core;
+    let sockets = if topology.sockets > 1 {
+        format!("{}x ", topology.sockets)
+    } else {
+        String::new()
+    };
+    let numa = if topology.numa_nodes > 1 {
+        format!(", {} NUMA nodes", topology.numa_nodes)
+    } else {
+        String::new()
+    };
+    format!("{}{} ({}c/{}t{})", sockets, brand, cores, threads, numa)
+}
+
+/// Lists the problems that are in the critical band, for the farewell line.
+pub(super) fn collect_health_alerts(
+    snapshot: &SystemSnapshot,
+    settings: &OutputSettings,
+) -> Vec<String> {
+    let is_critical = |ratio: f64| usage_status_label(ratio) == Some("critical");
+    let mut alerts = snapshot
+        .disk_items
+        .iter()
+        .filter(|disk| settings.disk_alerts_enabled(&disk.mount_path) && is_critical(disk.ratio))
+        .map(|disk| format!("{} is {:.0}% full", disk.mount_path, disk.ratio))
+        .collect::<Vec<_>>();
+
+    if !is_empty_usage(snapshot.memory) && is_critical(snapshot.memory.ratio) {
+        alerts.push(format!("memory is {:.0}% used", snapshot.memory.ratio));
+    }
+    for status in &snapshot.fd_usages {
+        if let Ok(FdUsage {
+            open,
+            limit: Some(limit),
+            ..
+        }) = status.usage
+            && limit > 0
+            && is_critical(open as f64 / limit as f64 * 100.0)
+        {
+            alerts.push(format!("{} is at {}/{} fds", status.label, open, limit));
+        }
+    }
+    alerts
 }
 
 fn usage_status_label(ratio: f64) -> Option<&'static str> {
@@ -1169,17 +2746,178 @@ fn paint_failed_login(
 }
 
 fn terminal_width_hint() -> usize {
-    env::var("COLUMNS")
-        .ok()
-        .and_then(|value| value.parse::<usize>().ok())
+    terminal_columns()
         .map(|value| value.clamp(72, 160))
         .unwrap_or(100)
 }
 
-fn visible_width(text: &str) -> usize {
+/// Reads `COLUMNS` first, then asks the terminal attached to stdout.
+pub(super) fn terminal_columns() -> Option<usize> {
+    let from_env = env::var("COLUMNS")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|value| *value > 0);
+    if from_env.is_some() {
+        return from_env;
+    }
+
+    #[cfg(feature = "system-probes")]
+    {
+        rustix::termios::tcgetwinsize(std::io::stdout())
+            .ok()
+            .map(|size| usize::from(size.ws_col))
+            .filter(|value| *value > 0)
+    }
+
+    #[cfg(not(feature = "system-probes"))]
+    {
+        None
+    }
+}
+
+/// Cuts a line down to `width` visible columns, ending it with `…` and an
+/// SGR reset when it carried escapes.
+pub(super) fn clip_line_to_width(line: &str, width: usize) -> String {
+    if visible_width(line) <= width {
+        return line.to_string();
+    }
+
+    let budget = width.saturating_sub(1);
+    let mut output = String::with_capacity(line.len());
+    let mut column = 0usize;
+    let mut has_escapes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            has_escapes = true;
+            output.push(ch);
+            if let Some(bracket) = chars.next_if_eq(&'[') {
+                output.push(bracket);
+                for code in chars.by_ref() {
+                    output.push(code);
+                    if code.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+
+        let ch_width = char_width(ch);
+        if column + ch_width > budget {
+            break;
+        }
+        output.push(ch);
+        column += ch_width;
+    }
+
+    if width > 0 {
+        output.push('…');
+    }
+    if has_escapes {
+        output.push_str("\u{1b}[0m");
+    }
+    output
+}
+
+/// Drops CSI escape sequences, leaving the visible text.
+pub(super) fn strip_ansi(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            for byte in chars.by_ref() {
+                if byte.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            output.push(ch);
+        }
+    }
+    output
+}
+
+/// Applies [`paste_safe_line`] to each line of a multi-line entry such as the
+/// welcome art, keeping the line breaks it would otherwise drop.
+pub(super) fn paste_safe_text(text: &str) -> String {
+    text.split('\n')
+        .map(paste_safe_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drops ANSI escapes, expands tabs to 8-column stops, swaps motdyn's own
+/// glyphs for ASCII and trims trailing whitespace so a copied line keeps its
+/// alignment. Other non-ASCII text, such as a CJK host name, is kept.
+pub(super) fn paste_safe_line(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut column = 0usize;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\u{1b}' => {
+                if chars.next_if_eq(&'[').is_some() {
+                    for byte in chars.by_ref() {
+                        if byte.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\t' => {
+                let spaces = TAB_WIDTH - column % TAB_WIDTH;
+                output.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+            ch if ch.is_control() => {}
+            ch => match ascii_glyph(ch) {
+                Some(replacement) => {
+                    output.push_str(replacement);
+                    column += replacement.len();
+                }
+                None => {
+                    output.push(ch);
+                    column += char_width(ch);
+                }
+            },
+        }
+    }
+
+    output.truncate(output.trim_end().len());
+    output
+}
+
+/// ASCII stand-ins for the alert sign, truncation mark, bar blocks, box
+/// drawing and bullets used in banners.
+fn ascii_glyph(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        '⚠' => "!",
+        '…' => "...",
+        '°' => "",
+        '█' | '▓' => "#",
+        '▒' | '░' => "-",
+        '●' | '•' | '·' => "*",
+        '→' => "->",
+        '←' => "<-",
+        '│' | '┃' | '║' => "|",
+        '─' | '━' | '═' => "-",
+        '\u{2500}'..='\u{257f}' => "+",
+        '\u{2580}'..='\u{259f}' => "#",
+        '\u{fe0f}' => "",
+        _ => return None,
+    })
+}
+
+/// Terminal columns taken by `text`, skipping ANSI escapes and counting wide
+/// CJK characters and emoji as two columns.
+pub(super) fn visible_width(text: &str) -> usize {
     let bytes = text.as_bytes();
     let mut width = 0usize;
     let mut idx = 0usize;
+    let mut joined = false;
 
     while idx < bytes.len() {
         if bytes[idx] == 0x1b {
@@ -1198,7 +2936,12 @@ fn visible_width(text: &str) -> usize {
         }
 
         if let Some(ch) = text[idx..].chars().next() {
-            width += 1;
+            // A character joined on with U+200D draws inside the emoji
+            // before it, as in a family or profession sequence.
+            if !joined {
+                width += char_width(ch);
+            }
+            joined = ch == '\u{200d}';
             idx += ch.len_utf8();
         } else {
             break;
@@ -1208,6 +2951,97 @@ fn visible_width(text: &str) -> usize {
     width
 }
 
+/// East Asian Width for terminals: `W` and `F` characters take two columns,
+/// combining marks, variation selectors and emoji skin-tone modifiers take
+/// none. The pictograph blocks are counted as wide as a whole, since
+/// terminals draw their text-style emoji two columns wide as well. Ranges
+/// follow Unicode 15's `EastAsianWidth.txt`.
+fn char_width(ch: char) -> usize {
+    match u32::from(ch) {
+        0x0300..=0x036f
+        | 0x1ab0..=0x1aff
+        | 0x1dc0..=0x1dff
+        | 0x200b..=0x200f
+        | 0x20d0..=0x20ff
+        | 0xfe00..=0xfe0f
+        | 0xfe20..=0xfe2f
+        | 0x1f3fb..=0x1f3ff
+        | 0xe0000..=0xe007f
+        | 0xe0100..=0xe01ef => 0,
+        0x1100..=0x115f
+        | 0x231a..=0x231b
+        | 0x2329..=0x232a
+        | 0x23e9..=0x23ec
+        | 0x23f0
+        | 0x23f3
+        | 0x25fd..=0x25fe
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x267f
+        | 0x2693
+        | 0x26a1
+        | 0x26aa..=0x26ab
+        | 0x26bd..=0x26be
+        | 0x26c4..=0x26c5
+        | 0x26ce
+        | 0x26d4
+        | 0x26ea
+        | 0x26f2..=0x26f3
+        | 0x26f5
+        | 0x26fa
+        | 0x26fd
+        | 0x2705
+        | 0x270a..=0x270b
+        | 0x2728
+        | 0x274c
+        | 0x274e
+        | 0x2753..=0x2755
+        | 0x2757
+        | 0x2795..=0x2797
+        | 0x27b0
+        | 0x27bf
+        | 0x2b1b..=0x2b1c
+        | 0x2b50
+        | 0x2b55
+        | 0x2e80..=0x303e
+        | 0x3041..=0x33ff
+        | 0x3400..=0x4dbf
+        | 0x4e00..=0x9fff
+        | 0xa000..=0xa4cf
+        | 0xa960..=0xa97f
+        | 0xac00..=0xd7a3
+        | 0xf900..=0xfaff
+        | 0xfe10..=0xfe19
+        | 0xfe30..=0xfe6b
+        | 0xff00..=0xff60
+        | 0xffe0..=0xffe6
+        | 0x16fe0..=0x16fe4
+        | 0x16ff0..=0x16ff1
+        | 0x17000..=0x18cd5
+        | 0x18d00..=0x18d08
+        | 0x1aff0..=0x1affe
+        | 0x1b000..=0x1b2fb
+        | 0x1f004
+        | 0x1f0cf
+        | 0x1f18e
+        | 0x1f191..=0x1f19a
+        | 0x1f200..=0x1f202
+        | 0x1f210..=0x1f23b
+        | 0x1f240..=0x1f248
+        | 0x1f250..=0x1f251
+        | 0x1f260..=0x1f265
+        | 0x1f300..=0x1f64f
+        | 0x1f680..=0x1f6ff
+        | 0x1f7e0..=0x1f7eb
+        | 0x1f7f0
+        | 0x1f90c..=0x1f9ff
+        | 0x1fa70..=0x1faff
+        | 0x20000..=0x2fffd
+        | 0x30000..=0x3fffd => 2,
+        _ => 1,
+    }
+}
+
 fn compact_clauses(section: SectionKind, items: &[RenderedItem]) -> Vec<String> {
     items
         .iter()
@@ -1227,13 +3061,48 @@ fn compact_clause(section: SectionKind, item: &RenderedItem) -> String {
         "Kernel version:" => format!("kernel {}", item.value),
         "Virtualization:" => format!("virt {}", item.value),
         "CPU:" => item.value.clone(),
-        "Memory used/total:" => format!("mem {}", item.value),
+        "CPUs:" => format!("cpus {}", item.value),
+        "Memory used/total:" | "Memory used/free:" | "Memory free/total:" | "Memory (cgroup):" => {
+            format!("mem {}", item.value)
+        }
+        "Memory available:" => format!("avail {}", item.value),
         "Swap used/total:" => format!("swap {}", item.value),
         "Disk usage (root):" => format!("root {}", normalize_disk_compact_value(&item.value)),
         "Disk usage (nfs):" => format!("nfs {}", normalize_disk_compact_value(&item.value)),
+        "Disk usage (local):" => item.value.split_whitespace().collect::<Vec<_>>().join(" "),
         "Last login:" => format!("last {}", item.value),
         "Failed login:" => format!("failed {}", item.value),
         "Pending updates:" => format!("updates {}", item.value),
+        "Services to restart:" => format!("restart {}", item.value),
+        "Auto-updates:" => format!("auto-updates {}", item.value),
+        "Reboot:" => item.value.clone(),
+        "Failed units:" => format!("failed units {}", item.value),
+        "Battery:" => format!("battery {}", item.value),
+        "Network (rx/tx):" => format!("rx/tx {}", item.value),
+        "CPU pressure (10s):" => format!("cpu psi {}", item.value),
+        "Memory pressure (10s):" => format!("mem psi {}", item.value),
+        "IO pressure (10s):" => format!("io psi {}", item.value),
+        "Recent logins:" => format!("recent {}", item.value),
+        "OOM events:" => format!("oom {}", item.value),
+        "Top CPU:" => format!("top {}", item.value),
+        "Top users:" => format!("procs {}", item.value),
+        "MAC:" => format!("mac {}", item.value),
+        "Container storage:" => format!("containers {}", item.value),
+        "Containers:" => format!("containers {}", item.value),
+        "Link:" => format!("link {}", item.value),
+        "IP addresses:" => format!("ip {}", item.value),
+        "motdyn update:" => format!("motdyn {}", item.value),
+        "Authorized keys:" => format!("keys {}", item.value),
+        "Authorized keys (root):" => format!("root keys {}", item.value),
+        label if label.starts_with("ZFS pool (") || label.starts_with("Btrfs (") => {
+            format!("{} {}", label.trim_end_matches(':'), item.value)
+        }
+        label if label.ends_with(" fds:") => {
+            format!("{} {}", label.trim_end_matches(':'), item.value)
+        }
+        label if label.ends_with(" cert:") => {
+            format!("{} {}", label.trim_end_matches(':'), item.value)
+        }
         label if label.starts_with("Service ") => {
             format!(
                 "{} {}",
@@ -1251,13 +3120,22 @@ fn compact_clause(section: SectionKind, item: &RenderedItem) -> String {
     }
 }
 
+/// Drops the mount path column, keeping only the usage figures.
 fn normalize_disk_compact_value(value: &str) -> String {
     value
-        .split_once("=>")
+        .split_once("  ")
         .map(|(_, rest)| rest.trim().to_string())
         .unwrap_or_else(|| value.to_string())
 }
 
+fn format_authorized_key(key: &AuthorizedKey) -> String {
+    if key.comment.is_empty() {
+        format!("{} {}", key.key_type, key.fingerprint)
+    } else {
+        format!("{} {} {}", key.key_type, key.fingerprint, key.comment)
+    }
+}
+
 fn format_buckets(buckets: &[FailedLoginBucket]) -> String {
     buckets
         .iter()
@@ -0,0 +1,76 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::config::ScheduledBannerConfig;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct ScheduledBanners {
+    pub(super) messages: Vec<String>,
+    pub(super) skipped: Vec<String>,
+}
+
+/// Picks every `[[scheduled_banners]]` entry whose window contains `now`.
+/// Entries with unparsable dates are skipped and reported in `skipped`.
+pub(super) fn active_scheduled_banners(
+    banners: &[ScheduledBannerConfig],
+    now: NaiveDateTime,
+) -> ScheduledBanners {
+    let mut result = ScheduledBanners::default();
+    for (index, banner) in banners.iter().enumerate() {
+        let start = parse_schedule_bound(&banner.start, NaiveTime::MIN);
+        let end = parse_schedule_bound(&banner.end, end_of_day());
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) if start <= end => (start, end),
+            (None, _) => {
+                result.skipped.push(format!(
+                    "scheduled_banners[{}]: invalid start '{}'",
+                    index, banner.start
+                ));
+                continue;
+            }
+            (_, None) => {
+                result.skipped.push(format!(
+                    "scheduled_banners[{}]: invalid end '{}'",
+                    index, banner.end
+                ));
+                continue;
+            }
+            _ => {
+                result
+                    .skipped
+                    .push(format!("scheduled_banners[{}]: end is before start", index));
+                continue;
+            }
+        };
+        if banner.message.is_empty() {
+            result
+                .skipped
+                .push(format!("scheduled_banners[{}]: empty message", index));
+            continue;
+        }
+        if start <= now && now <= end {
+            result.messages.push(banner.message.clone());
+        }
+    }
+    result
+}
+
+/// Accepts `YYYY-MM-DD`, `YYYY-MM-DD HH:MM` or `YYYY-MM-DDTHH:MM:SS` in local time;
+/// a bare date takes `day_time`, so an end date covers the whole day.
+fn parse_schedule_bound(value: &str, day_time: NaiveTime) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.and_time(day_time));
+    }
+    [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+}
+
+fn end_of_day() -> NaiveTime {
+    NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN)
+}
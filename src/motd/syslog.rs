@@ -0,0 +1,38 @@
+use std::os::unix::net::UnixDatagram;
+use std::process;
+
+use chrono::{DateTime, Local, SecondsFormat};
+
+use super::types::SystemSnapshot;
+
+const SYSLOG_SOCKET_PATH: &str = "/dev/log";
+/// `LOG_AUTH` facility (4) with `LOG_INFO` severity (6).
+const SYSLOG_AUTH_INFO: u8 = 4 * 8 + 6;
+
+/// Records a banner display in the system log. Callers treat failures as
+/// non-fatal; the banner still prints.
+pub(super) fn record_login_event(snapshot: &SystemSnapshot) -> Result<(), String> {
+    let message = format_syslog_message(&format_login_event(snapshot, Local::now()), process::id());
+    let socket =
+        UnixDatagram::unbound().map_err(|err| format!("failed to create socket: {}", err))?;
+    socket
+        .send_to(message.as_bytes(), SYSLOG_SOCKET_PATH)
+        .map_err(|err| format!("failed to write {}: {}", SYSLOG_SOCKET_PATH, err))?;
+    Ok(())
+}
+
+pub(super) fn format_login_event(snapshot: &SystemSnapshot, when: DateTime<Local>) -> String {
+    format!(
+        "banner shown user={} from={} host={} at={}",
+        snapshot.current_user,
+        snapshot.from_ip,
+        snapshot.host_name,
+        when.to_rfc3339_opts(SecondsFormat::Secs, false)
+    )
+}
+
+/// Frames a message for the local syslog socket; the daemon adds its own
+/// timestamp and hostname.
+pub(super) fn format_syslog_message(message: &str, pid: u32) -> String {
+    format!("<{}>motdyn[{}]: {}", SYSLOG_AUTH_INFO, pid, message)
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File};
 #[cfg(feature = "remote-welcome")]
 use std::io::Read;
@@ -7,34 +7,80 @@ use std::io::Write;
 use std::net::TcpListener;
 #[cfg(feature = "remote-welcome")]
 use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use chrono::{Local, TimeZone};
+use base64::Engine;
+use chrono::{FixedOffset, Local, TimeZone, Utc};
 use tempfile::tempdir;
 
 #[cfg(feature = "remote-welcome")]
 use crate::config::RemoteWelcomeConfig;
-use crate::config::{MotdConfig, OutputConfig};
+use crate::config::{
+    EnvironmentConfig, MotdConfig, OutputConfig, ScheduledBannerConfig, SpacingConfig,
+};
 
+use super::cache::{CachedOutput, OutputCache, format_cached_output, parse_cached_output};
+use super::cert::parse_certificate_not_after;
+use super::check::{CheckReport, CheckStatus};
+use super::collector::HostCollector;
+use super::debug::rust_log_enables_debug;
+use super::layout::{RenderedLayout, layout_modules, render_layout};
+use super::macos::{
+    parse_boottime, parse_sw_vers_output, parse_swapusage, parse_vm_stat_available_kb,
+};
+use super::metrics::{JsonStyle, SystemMetrics};
 use super::probe::{
-    FailedLoginEvent, ParsedLastLoginRecord, count_logged_in_users_from_linux_utmp_file,
-    detect_virtualization_from_cgroup, format_uptime, parse_apt_upgradable_output,
-    parse_cpuinfo_content, parse_default_interface_output, parse_dnf_check_update_output,
-    parse_interface_ipv4_output, parse_lastb_output, parse_lastlog_output, parse_loadavg_content,
-    parse_meminfo_content, parse_os_release_content, parse_redhat_release_content,
-    parse_ssh_connection_ip, parse_uptime_content, run_command_with_timeout,
-    summarize_failed_login_events, to_gb_and_ratio,
+    FailedLoginEvent, ParsedLastLoginRecord, collect_snapshot_from, count_apparmor_profiles,
+    count_container_states, count_established_connections, count_established_on_port,
+    count_logged_in_users_from_linux_utmp_file, count_processes, cpu_busy_percent,
+    detect_virtualization_from_cgroup, disk_mount_label, find_kernel_config_hz, find_pid_by_comm,
+    format_uptime, format_uptime_compact, is_known_timezone, is_preempt_rt_kernel,
+    parse_apt_periodic_setting, parse_apt_upgradable_output, parse_authorized_keys, parse_battery,
+    parse_btrfs_mounts, parse_btrfs_usage, parse_cgroup_cfs_quota, parse_cgroup_cpu_max,
+    parse_cgroup_memory_bytes, parse_cpu_range_list, parse_cpuinfo_content,
+    parse_default_interface_output, parse_dnf_automatic_apply_updates,
+    parse_dnf_check_update_output, parse_docker_containers_json, parse_docker_system_df_json,
+    parse_failed_units, parse_getent_hosts_name, parse_interface_ipv4_output, parse_ip_addr_output,
+    parse_last_output, parse_lastb_output, parse_lastlog_output, parse_link_speed,
+    parse_loadavg_content, parse_lscpu_output, parse_meminfo_content,
+    parse_needrestart_batch_output, parse_net_throughput, parse_nvidia_smi_gpus,
+    parse_oom_kill_events, parse_os_release_content, parse_passwd_names, parse_pressure,
+    parse_proc_limits_nofile, parse_proc_stat_cpu_ticks, parse_proc_stat_cpu_times,
+    parse_redhat_release_content, parse_selinux_enforce, parse_si_size, parse_ssh_connection_ip,
+    parse_status_vm_rss_kb, parse_system_df_table, parse_temperature, parse_update_notifier_count,
+    parse_uptime_content, parse_utc_offset, parse_vmstat_swap_pages, parse_zfs_pools,
+    pick_top_cpu_process, rank_top_users, read_cpu_temperature, read_network_links,
+    read_probe_cache, read_probe_cache_result, read_recent_logins, read_top_memory_processes,
+    record_recent_login, resolve_time_format, run_command_with_timeout, run_with_deadline,
+    summarize_failed_login_events, swap_activity_between, to_gb_and_ratio, write_probe_cache,
+    write_probe_cache_failure,
 };
+#[cfg(feature = "remote-welcome")]
+use super::release::{is_newer_version, parse_release_tag};
 use super::render::{
-    basic_modules, build_verbose_items, default_modules, render_module_lines, resolve_modules,
-    resolve_output_settings,
+    basic_modules, best_unit_scale, build_verbose_items, clip_line_to_width, collect_health_alerts,
+    default_modules, format_aligned_items, format_gb, format_link_speed, format_uptime_short,
+    load_average_kind, paste_safe_line, render_module_lines, render_quiet_line, resolve_modules,
+    resolve_output_settings, resolve_spacing_settings, strip_ansi, strip_utc_offset,
+    threshold_paint_kind, visible_width,
 };
+use super::schedule::{ScheduledBanners, active_scheduled_banners};
+#[cfg(unix)]
+use super::syslog::{format_login_event, format_syslog_message};
 #[cfg(feature = "remote-welcome")]
 use super::types::WelcomeCacheEntry;
 use super::types::{
-    DEFAULT_WELCOME, FailedLoginBucket, FailedLoginInfo, FailedLoginSeverity, HiddenField,
-    LastLoginInfo, LastLoginRecord, LoginSessionKind, ModuleKind, ModuleProfile, ModuleSelection,
-    ModuleSource, NetworkProbeError, OutputSettings, ProbeIssue, RenderContext, RenderedItem,
-    SnapshotDiagnostics, SourceRelation, SystemSnapshot, UsageSummary, ViewerRole,
+    AuthorizedKey, AuthorizedKeysFile, BYTES_PER_GB, BatteryStatus, CertificateStatus,
+    ContainerCounts, ContainerStorage, CpuTopology, DEFAULT_FAREWELL, DEFAULT_TIME_FORMAT,
+    DEFAULT_TIME_FORMAT_12H, DEFAULT_WELCOME, DiskMountSelection, DiskUsageItem, EnvironmentStyle,
+    FailedLoginBucket, FailedLoginInfo, FailedLoginSeverity, FdUsage, FdUsageStatus, GpuInfo,
+    HiddenField, InterfaceAddress, KernelPreemption, LastLoginInfo, LastLoginRecord,
+    LoginSessionKind, MacStatus, MemoryProcess, ModuleKind, ModuleProfile, ModuleSelection,
+    ModuleSource, NetThroughput, NetworkLink, NetworkProbeError, OomSummary, OutputSettings,
+    PaintKind, PressureAverages, PressureStall, ProbeIssue, RecentLogin, RenderContext, RenderMode,
+    RenderedItem, SeLinuxMode, SnapshotDiagnostics, SnapshotOptions, SourceRelation,
+    SpacingSettings, SwapActivity, SystemSnapshot, TemperatureUnit, ThemeColor, Threshold,
+    TopProcess, UsageDisplay, UsageSummary, UsageThresholds, UserProcessCount, ViewerRole,
     WelcomeResolution, WelcomeSource,
 };
 #[cfg(target_os = "linux")]
@@ -45,6 +91,10 @@ use super::types::{
 #[cfg(feature = "remote-welcome")]
 use super::welcome::{current_unix_secs, read_welcome_cache, write_welcome_cache};
 use super::welcome::{resolve_remote_welcome_settings, resolve_welcome_text};
+use super::{
+    build_output, build_welcome_only, fit_welcome_text, indent_lines, resolve_farewell_text,
+    resolve_header_text, write_static,
+};
 
 #[test]
 fn format_uptime_formats_days() {
@@ -52,16 +102,59 @@ fn format_uptime_formats_days() {
     assert_eq!(value, "2 days, 01:01:01");
 }
 
+#[test]
+fn format_uptime_short_keeps_two_largest_units() {
+    assert_eq!(format_uptime_short(0), "0s");
+    assert_eq!(format_uptime_short(59), "59s");
+    assert_eq!(format_uptime_short(60), "1m");
+    assert_eq!(format_uptime_short(17 * 60 + 59), "17m");
+    assert_eq!(format_uptime_short(3_600), "1h");
+    assert_eq!(format_uptime_short(5 * 3_600 + 17 * 60), "5h17m");
+    assert_eq!(format_uptime_short(86_399), "23h59m");
+    assert_eq!(format_uptime_short(86_400), "1d");
+    assert_eq!(format_uptime_short(86_400 + 59 * 60), "1d");
+    assert_eq!(format_uptime_short(3 * 86_400 + 5 * 3_600 + 42), "3d5h");
+}
+
 #[test]
 fn format_uptime_formats_without_days() {
     let value = format_uptime(3661);
     assert_eq!(value, "01:01:01");
 }
 
+#[test]
+fn format_uptime_keeps_clock_fields_at_edges() {
+    assert_eq!(format_uptime(0), "00:00:00");
+    assert_eq!(format_uptime(42), "00:00:42");
+    assert_eq!(format_uptime(86_400), "1 days, 00:00:00");
+}
+
+#[test]
+fn format_uptime_compact_drops_zero_units() {
+    assert_eq!(format_uptime_compact(0), "0s");
+    assert_eq!(format_uptime_compact(42), "42s");
+    assert_eq!(format_uptime_compact(60), "1m");
+    assert_eq!(format_uptime_compact(13 * 60 + 42), "13m 42s");
+    assert_eq!(format_uptime_compact(3_599), "59m 59s");
+    assert_eq!(format_uptime_compact(3_600), "1h");
+    assert_eq!(format_uptime_compact(3_600 + 42), "1h");
+    assert_eq!(format_uptime_compact(5 * 3_600 + 17 * 60 + 9), "5h 17m");
+    assert_eq!(format_uptime_compact(2 * 86_400), "2d");
+    assert_eq!(format_uptime_compact(86_400 + 13 * 60), "1d 13m");
+    assert_eq!(
+        format_uptime_compact(127 * 86_400 + 4 * 3_600 + 13 * 60 + 59),
+        "127d 4h 13m"
+    );
+}
+
 #[test]
 fn parse_uptime_content_rejects_invalid_input() {
     assert_eq!(parse_uptime_content("not-a-number 0"), None);
     assert_eq!(parse_uptime_content(""), None);
+    assert_eq!(
+        parse_uptime_content("2141331.52 8123.10\n"),
+        Some(2_141_331)
+    );
 }
 
 #[cfg(unix)]
@@ -87,6 +180,15 @@ fn resolve_output_settings_normalizes_hidden_fields_and_ignores_unknowns() {
             compact: Some(true),
             plain: Some(true),
             section_headers: Some(true),
+            ansi_safe: None,
+            clip_ascii_art: None,
+            max_ascii_lines: None,
+            max_disk_lines: None,
+            disk_display: None,
+            progress_bars: None,
+            progress_bar_width: None,
+            width: None,
+            temp_unit: None,
             hidden_fields: Some(vec!["source_ip".into(), "nfs".into(), "bogus".into()]),
         },
         ..MotdConfig::default()
@@ -101,6 +203,148 @@ fn resolve_output_settings_normalizes_hidden_fields_and_ignores_unknowns() {
     assert_eq!(settings.ignored_hidden_fields, vec!["bogus".to_string()]);
 }
 
+#[test]
+fn resolve_output_settings_maps_colors_and_reports_invalid_entries() {
+    let cfg = MotdConfig {
+        colors: Some(BTreeMap::from([
+            ("cpu".into(), "green".into()),
+            ("mem".into(), "bright-red".into()),
+            ("disk".into(), "chartreuse".into()),
+            ("bogus".into(), "red".into()),
+        ])),
+        ..MotdConfig::default()
+    };
+
+    let settings = resolve_output_settings(&cfg);
+    assert_eq!(
+        settings.colors,
+        HashMap::from([
+            (ModuleKind::Cpu, ThemeColor::Green),
+            (ModuleKind::Memory, ThemeColor::BrightRed),
+        ])
+    );
+    assert_eq!(
+        settings.ignored_colors,
+        vec![
+            "unknown module 'bogus'".to_string(),
+            "unknown color 'chartreuse' for 'disk'".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn resolve_output_settings_resolves_environment_styles() {
+    let environment = |name: &str| {
+        let cfg = MotdConfig {
+            environment: Some(name.into()),
+            environments: Some(BTreeMap::from([
+                (
+                    "prod".into(),
+                    EnvironmentConfig {
+                        color: Some("crimson".into()),
+                        banner: Some("=== PRODUCTION ===".into()),
+                    },
+                ),
+                (
+                    "qa".into(),
+                    EnvironmentConfig {
+                        color: Some("blue".into()),
+                        banner: None,
+                    },
+                ),
+            ])),
+            ..MotdConfig::default()
+        };
+        let settings = resolve_output_settings(&cfg);
+        (settings.environment, settings.ignored_colors)
+    };
+
+    assert_eq!(
+        environment("prod"),
+        (
+            Some(EnvironmentStyle {
+                name: "prod".into(),
+                color: Some(ThemeColor::Red),
+                banner: Some("=== PRODUCTION ===".into()),
+            }),
+            vec!["unknown color 'crimson' for environment 'prod'".to_string()],
+        )
+    );
+    assert_eq!(
+        environment("qa").0.and_then(|style| style.color),
+        Some(ThemeColor::Blue)
+    );
+    assert_eq!(
+        environment("staging").0.and_then(|style| style.color),
+        Some(ThemeColor::Yellow)
+    );
+    assert_eq!(environment("lab"), (None, Vec::new()));
+}
+
+#[test]
+fn render_module_lines_keeps_text_when_a_color_is_configured() {
+    let mut settings = resolve_output_settings(&MotdConfig::default());
+    settings.plain = true;
+    settings.colors.insert(ModuleKind::Host, ThemeColor::Blue);
+
+    let lines = render_module_lines(&[ModuleKind::Host], &sample_snapshot(), &settings);
+    assert_eq!(lines, vec!["Host name: prod-hpc-01".to_string()]);
+    assert_eq!(strip_ansi("\u{1b}[1;32mprod\u{1b}[0m-01"), "prod-01");
+}
+
+#[test]
+fn render_module_lines_keeps_host_text_under_an_environment_highlight() {
+    let mut settings = resolve_output_settings(&MotdConfig {
+        environment: Some("dev".into()),
+        ..MotdConfig::default()
+    });
+    settings.plain = true;
+    settings.colors.insert(ModuleKind::Host, ThemeColor::Blue);
+
+    let lines = render_module_lines(&[ModuleKind::Host], &sample_snapshot(), &settings);
+    assert_eq!(lines, vec!["Host name: prod-hpc-01".to_string()]);
+    assert_eq!(
+        render_quiet_line(&[ModuleKind::Host], &sample_snapshot(), &settings),
+        "prod-hpc-01"
+    );
+}
+
+#[test]
+fn resolve_spacing_settings_defaults_follow_compact_and_respect_overrides() {
+    let cfg = MotdConfig::default();
+    assert_eq!(
+        resolve_spacing_settings(&cfg, &resolve_output_settings(&cfg)),
+        SpacingSettings {
+            before_welcome: 1,
+            after_welcome: 1,
+            before_farewell: 1,
+            gutter: 0,
+        }
+    );
+
+    let cfg = MotdConfig {
+        output: OutputConfig {
+            compact: Some(true),
+            ..OutputConfig::default()
+        },
+        spacing: SpacingConfig {
+            after_welcome: Some(2),
+            gutter: Some(3),
+            ..SpacingConfig::default()
+        },
+        ..MotdConfig::default()
+    };
+    assert_eq!(
+        resolve_spacing_settings(&cfg, &resolve_output_settings(&cfg)),
+        SpacingSettings {
+            before_welcome: 0,
+            after_welcome: 2,
+            before_farewell: 0,
+            gutter: 3,
+        }
+    );
+}
+
 #[test]
 fn resolve_modules_uses_full_defaults_for_root_when_unset() {
     let selection = resolve_modules(
@@ -189,6 +433,325 @@ fn fetch_welcome_text_returns_literal_strings() {
     assert_eq!(resolution.text, "Plain text");
 }
 
+#[test]
+fn resolve_farewell_text_picks_from_farewells_unless_farewell_is_set() {
+    let mut cfg = MotdConfig {
+        farewells: Some(vec!["Stay safe!".into(), "See you soon.".into()]),
+        ..MotdConfig::default()
+    };
+    assert_eq!(resolve_farewell_text(&cfg, 0), "Stay safe!");
+    assert_eq!(resolve_farewell_text(&cfg, 3), "See you soon.");
+
+    cfg.farewell = Some("Bye".into());
+    assert_eq!(resolve_farewell_text(&cfg, 3), "Bye");
+
+    let cfg = MotdConfig {
+        farewells: Some(Vec::new()),
+        ..MotdConfig::default()
+    };
+    assert_eq!(resolve_farewell_text(&cfg, 7), DEFAULT_FAREWELL);
+}
+
+#[test]
+fn resolve_header_text_replaces_or_drops_the_default_welcome() {
+    let mut cfg = MotdConfig::default();
+    let header = |cfg: &MotdConfig| resolve_header_text(cfg, &resolve_welcome_text(cfg));
+    assert_eq!(header(&cfg).as_deref(), Some("Welcome!"));
+
+    cfg.header = Some("prod-01".into());
+    assert_eq!(header(&cfg).as_deref(), Some("prod-01"));
+
+    cfg.header = Some(String::new());
+    assert_eq!(header(&cfg), None);
+
+    cfg.welcome = Some("Hello".into());
+    assert_eq!(header(&cfg).as_deref(), Some("Hello"));
+
+    cfg.show_header = Some(false);
+    assert_eq!(header(&cfg), None);
+}
+
+#[test]
+fn resolve_time_format_prefers_time_format_and_rejects_bad_specifiers() {
+    let mut cfg = MotdConfig::default();
+    assert_eq!(resolve_time_format(&cfg), (DEFAULT_TIME_FORMAT, None));
+
+    cfg.time_24h = Some(false);
+    assert_eq!(resolve_time_format(&cfg), (DEFAULT_TIME_FORMAT_12H, None));
+
+    cfg.time_format = Some("%d/%m/%Y %H:%M".into());
+    assert_eq!(resolve_time_format(&cfg), ("%d/%m/%Y %H:%M", None));
+
+    cfg.time_format = Some("%Y-%Q".into());
+    let (format, issue) = resolve_time_format(&cfg);
+    assert_eq!(format, DEFAULT_TIME_FORMAT_12H);
+    assert!(issue.is_some_and(|issue| issue.contains("'%Y-%Q'")));
+}
+
+#[test]
+fn parse_utc_offset_reads_date_offsets() {
+    assert_eq!(
+        parse_utc_offset("+0530"),
+        FixedOffset::east_opt(5 * 3600 + 30 * 60)
+    );
+    assert_eq!(parse_utc_offset("-0800"), FixedOffset::west_opt(8 * 3600));
+    assert_eq!(parse_utc_offset("+0000"), FixedOffset::east_opt(0));
+    assert_eq!(parse_utc_offset("0800"), None);
+    assert_eq!(parse_utc_offset("+08:00"), None);
+    assert_eq!(parse_utc_offset(""), None);
+}
+
+#[test]
+fn is_known_timezone_requires_a_tzdata_file_inside_the_zoneinfo_dir() {
+    let dir = tempdir().expect("tempdir should be created");
+    fs::create_dir(dir.path().join("Asia")).expect("zone dir should be created");
+    fs::write(dir.path().join("Asia/Tokyo"), "TZif").expect("zone file should be written");
+    fs::write(dir.path().join("UTC"), "TZif").expect("zone file should be written");
+
+    assert!(is_known_timezone(dir.path(), "Asia/Tokyo"));
+    assert!(is_known_timezone(dir.path(), "UTC"));
+    assert!(!is_known_timezone(dir.path(), "Asia"));
+    assert!(!is_known_timezone(dir.path(), "Mars/Olympus"));
+    assert!(!is_known_timezone(dir.path(), "Asia/../UTC"));
+    assert!(!is_known_timezone(dir.path(), "/etc/passwd"));
+    assert!(!is_known_timezone(dir.path(), ""));
+}
+
+#[test]
+fn strip_utc_offset_keeps_custom_formats_without_an_offset() {
+    assert_eq!(
+        strip_utc_offset("2026-01-15 09:30:00 +08:00"),
+        "2026-01-15 09:30:00"
+    );
+    assert_eq!(strip_utc_offset("15 Jan 09:30 -0500"), "15 Jan 09:30");
+    assert_eq!(strip_utc_offset("15 Jan 09:30 AM"), "15 Jan 09:30 AM");
+}
+
+#[test]
+fn write_static_drops_per_session_modules_and_colors() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("motd");
+    let cfg = MotdConfig {
+        modules: Some(vec!["host".into(), "user".into(), "time".into()]),
+        output: OutputConfig {
+            ansi_safe: Some(true),
+            ..OutputConfig::default()
+        },
+        ..MotdConfig::default()
+    };
+    let ctx = RenderContext {
+        system_config_path: "/etc/motdyn/config.toml".into(),
+        system_config_status: "missing".into(),
+        user_config_path: "/root/.config/motdyn/config.toml".into(),
+        user_config_status: "missing".into(),
+        env_overrides: Vec::new(),
+        config_origins: Vec::new(),
+        config_notes: Vec::new(),
+        session: "local".into(),
+        mode: RenderMode::Static,
+    };
+
+    write_static(&path, ModuleProfile::Auto, &cfg, &ctx).unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    assert!(text.contains("Host name:"));
+    assert!(!text.contains("User info:"));
+    assert!(!text.contains("Current time"));
+    assert!(!text.contains('\u{1b}'));
+    assert!(text.ends_with("Have a nice day!\n"));
+    assert_eq!(
+        fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+        0o644
+    );
+}
+
+#[test]
+fn build_output_keeps_welcome_art_lines_under_ansi_safe() {
+    let dir = tempdir().unwrap();
+    let art_path = dir.path().join("art.txt");
+    fs::write(&art_path, " /\\_/\\\n( o.o )\n > ^ <").unwrap();
+    let cfg = MotdConfig {
+        ascii_art_file: Some(art_path.display().to_string()),
+        modules: Some(vec!["host".into()]),
+        spacing: SpacingConfig {
+            gutter: Some(2),
+            ..SpacingConfig::default()
+        },
+        output: OutputConfig {
+            ansi_safe: Some(true),
+            ..OutputConfig::default()
+        },
+        ..MotdConfig::default()
+    };
+    let ctx = RenderContext {
+        system_config_path: "/etc/motdyn/config.toml".into(),
+        system_config_status: "missing".into(),
+        user_config_path: "/root/.config/motdyn/config.toml".into(),
+        user_config_status: "missing".into(),
+        env_overrides: Vec::new(),
+        config_origins: Vec::new(),
+        config_notes: Vec::new(),
+        session: "local".into(),
+        mode: RenderMode::Preview,
+    };
+
+    let text = build_output(false, ModuleProfile::Auto, &cfg, &ctx).join("\n");
+    let lines = text.lines().collect::<Vec<_>>();
+    assert!(lines.contains(&"   /\\_/\\"));
+    assert!(lines.contains(&"  ( o.o )"));
+    assert!(lines.contains(&"   > ^ <"));
+}
+
+#[test]
+fn write_static_keeps_multi_line_ascii_art() {
+    let dir = tempdir().unwrap();
+    let art_path = dir.path().join("art.txt");
+    fs::write(&art_path, " /\\_/\\\n( o.o )\n > ^ <").unwrap();
+    let path = dir.path().join("motd");
+    let cfg = MotdConfig {
+        ascii_art_file: Some(art_path.display().to_string()),
+        modules: Some(vec!["host".into()]),
+        output: OutputConfig {
+            ansi_safe: Some(true),
+            ..OutputConfig::default()
+        },
+        ..MotdConfig::default()
+    };
+    let ctx = RenderContext {
+        system_config_path: "/etc/motdyn/config.toml".into(),
+        system_config_status: "missing".into(),
+        user_config_path: "/root/.config/motdyn/config.toml".into(),
+        user_config_status: "missing".into(),
+        env_overrides: Vec::new(),
+        config_origins: Vec::new(),
+        config_notes: Vec::new(),
+        session: "local".into(),
+        mode: RenderMode::Static,
+    };
+
+    write_static(&path, ModuleProfile::Auto, &cfg, &ctx).unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    let lines = text.lines().collect::<Vec<_>>();
+    assert!(
+        lines
+            .windows(3)
+            .any(|window| window == [" /\\_/\\", "( o.o )", " > ^ <"])
+    );
+    assert_eq!(
+        lines.len(),
+        build_output(false, ModuleProfile::Auto, &cfg, &ctx)
+            .iter()
+            .map(|entry| entry.split('\n').count())
+            .sum::<usize>()
+    );
+}
+
+#[test]
+fn write_static_prints_environment_banner_above_welcome() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("motd");
+    let cfg = MotdConfig {
+        modules: Some(vec!["host".into()]),
+        environment: Some("prod".into()),
+        environments: Some(BTreeMap::from([(
+            "prod".into(),
+            EnvironmentConfig {
+                color: None,
+                banner: Some("=== PRODUCTION ===".into()),
+            },
+        )])),
+        ..MotdConfig::default()
+    };
+    let ctx = RenderContext {
+        system_config_path: "/etc/motdyn/config.toml".into(),
+        system_config_status: "missing".into(),
+        user_config_path: "/root/.config/motdyn/config.toml".into(),
+        user_config_status: "missing".into(),
+        env_overrides: Vec::new(),
+        config_origins: Vec::new(),
+        config_notes: Vec::new(),
+        session: "local".into(),
+        mode: RenderMode::Static,
+    };
+
+    write_static(&path, ModuleProfile::Auto, &cfg, &ctx).unwrap();
+    let text = fs::read_to_string(&path).unwrap();
+    let banner = text
+        .find("=== PRODUCTION ===")
+        .expect("banner should print");
+    assert!(banner < text.find(DEFAULT_WELCOME).unwrap());
+}
+
+#[test]
+fn build_welcome_only_skips_default_banner() {
+    assert_eq!(build_welcome_only(&MotdConfig::default()), None);
+
+    let cfg = MotdConfig {
+        welcome: Some("___\n|_|".into()),
+        ..MotdConfig::default()
+    };
+    assert_eq!(build_welcome_only(&cfg).as_deref(), Some("___\n|_|"));
+}
+
+#[test]
+fn clip_line_to_width_truncates_with_ellipsis_and_keeps_escapes() {
+    assert_eq!(clip_line_to_width("short", 10), "short");
+    assert_eq!(clip_line_to_width("0123456789", 6), "01234…");
+    assert_eq!(clip_line_to_width("主机主机", 5), "主机…");
+    assert_eq!(
+        clip_line_to_width("\u{1b}[32m0123456789\u{1b}[0m", 4),
+        "\u{1b}[32m012…\u{1b}[0m"
+    );
+}
+
+#[test]
+fn fit_welcome_text_clips_only_when_enabled_and_width_known() {
+    let art = "#########\n##".to_string();
+    let mut cfg = MotdConfig::default();
+
+    assert_eq!(fit_welcome_text(&cfg, art.clone(), Some(5)), art);
+
+    cfg.output.clip_ascii_art = Some(true);
+    assert_eq!(fit_welcome_text(&cfg, art.clone(), None), art);
+    assert_eq!(fit_welcome_text(&cfg, art, Some(5)), "####…\n##");
+}
+
+#[test]
+fn indent_lines_pads_every_printed_line_but_keeps_blank_ones_empty() {
+    let lines = vec![
+        "  /\\\n /  \\".to_string(),
+        String::new(),
+        "Bye".to_string(),
+    ];
+    assert_eq!(indent_lines(lines.clone(), 0), lines);
+    assert_eq!(
+        indent_lines(lines, 2),
+        vec![
+            "    /\\\n   /  \\".to_string(),
+            String::new(),
+            "  Bye".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn fit_welcome_text_caps_art_height_before_clipping() {
+    let art = "#########\n##\n###\n".to_string();
+    let mut cfg = MotdConfig::default();
+    cfg.output.max_ascii_lines = Some(3);
+    assert_eq!(fit_welcome_text(&cfg, art.clone(), None), art);
+
+    cfg.output.max_ascii_lines = Some(2);
+    assert_eq!(
+        fit_welcome_text(&cfg, art.clone(), None),
+        "#########\n##\n…"
+    );
+
+    cfg.output.clip_ascii_art = Some(true);
+    assert_eq!(fit_welcome_text(&cfg, art, Some(5)), "####…\n##\n…");
+}
+
 #[test]
 fn fetch_welcome_text_reads_local_file_sources() {
     let dir = tempdir().unwrap();
@@ -229,6 +792,39 @@ fn fetch_welcome_text_uses_next_source_after_failure() {
     )));
 }
 
+#[test]
+fn fetch_welcome_text_reads_ascii_art_file_unless_welcome_is_set() {
+    let dir = tempdir().unwrap();
+    let art_path = dir.path().join("art.txt");
+    fs::write(&art_path, " /\\_/\\\n( o.o )\n").unwrap();
+
+    let mut cfg = MotdConfig {
+        ascii_art_file: Some(art_path.display().to_string()),
+        ..MotdConfig::default()
+    };
+    let resolution = resolve_welcome_text(&cfg);
+    assert_eq!(resolution.source, WelcomeSource::LocalFile);
+    assert_eq!(resolution.text, " /\\_/\\\n( o.o )\n");
+
+    cfg.welcome = Some("Inline welcome".into());
+    let resolution = resolve_welcome_text(&cfg);
+    assert_eq!(resolution.text, "Inline welcome");
+    assert!(
+        resolution
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, super::types::WelcomeIssue::AsciiArtFileIgnored(_)))
+    );
+
+    let cfg = MotdConfig {
+        ascii_art_file: Some(dir.path().join("missing.txt").display().to_string()),
+        ..MotdConfig::default()
+    };
+    let resolution = resolve_welcome_text(&cfg);
+    assert_eq!(resolution.source, WelcomeSource::Default);
+    assert_eq!(resolution.warnings.len(), 1);
+}
+
 #[test]
 fn fetch_welcome_text_defaults_for_unsupported_url_scheme() {
     let cfg = MotdConfig {
@@ -293,205 +889,3496 @@ admin            pts/0    10.10.1.15                                 Thu Mar 30
     );
 }
 
+#[test]
+fn parse_last_output_skips_the_current_session() {
+    let output = "\
+admin    pts/1        10.10.1.20       Thu Apr  2 08:15   still logged in\n\
+admin    pts/0        10.10.1.15       Mon Mar 30 09:30 - 11:02  (01:32)\n\
+\n\
+wtmp begins Sun Mar  1 00:00:01 2026\n";
+
+    assert_eq!(
+        parse_last_output(output),
+        Some(Some(ParsedLastLoginRecord {
+            when: "Mon Mar 30 09:30".to_string(),
+            from: Some("10.10.1.15".to_string()),
+            via: Some("pts/0".to_string()),
+        }))
+    );
+}
+
+#[test]
+fn parse_last_output_handles_first_and_local_logins() {
+    let first_login = "\
+admin    pts/1        10.10.1.20       Thu Apr  2 08:15   still logged in\n\
+\n\
+wtmp begins Sun Mar  1 00:00:01 2026\n";
+    assert_eq!(parse_last_output(first_login), Some(None));
+    assert_eq!(
+        parse_last_output("\nwtmp begins Sun Mar  1 00:00:01 2026\n"),
+        Some(None)
+    );
+
+    let local = "admin    tty1         0.0.0.0          Mon Mar 30 09:30 - 09:45  (00:15)\n";
+    assert_eq!(
+        parse_last_output(local),
+        Some(Some(ParsedLastLoginRecord {
+            when: "Mon Mar 30 09:30".to_string(),
+            from: None,
+            via: Some("tty1".to_string()),
+        }))
+    );
+    assert_eq!(parse_last_output("admin pts/0\n"), None);
+}
+
 #[test]
 fn parse_lastlog_output_handles_never_logged_in() {
     let output = "\
 Username         Port     From                                       Latest\n\
 admin                                                         **Never logged in**\n";
 
-    assert_eq!(parse_lastlog_output(output), Some(None));
+    assert_eq!(parse_lastlog_output(output), Some(None));
+}
+
+#[test]
+fn parse_lastb_output_summarizes_recent_failures() {
+    let now = Local.with_ymd_and_hms(2026, 3, 30, 12, 0, 0).unwrap();
+    let output = "\
+admin    ssh:notty   10.10.1.20   Thu Mar 30 09:25 - 09:25  (00:00)\n\
+admin    ssh:notty   10.10.1.21   Thu Mar 30 09:20 - 09:20  (00:00)\n\
+btmp begins Thu Mar 30 00:00:00 2026\n";
+
+    let events = parse_lastb_output(output, now).unwrap();
+    assert_eq!(
+        events,
+        vec![
+            FailedLoginEvent {
+                when: Some(Local.with_ymd_and_hms(2026, 3, 30, 9, 25, 0).unwrap()),
+                when_raw: "Thu Mar 30 09:25".to_string(),
+                from: Some("10.10.1.20".to_string()),
+                via: Some("ssh:notty".to_string()),
+            },
+            FailedLoginEvent {
+                when: Some(Local.with_ymd_and_hms(2026, 3, 30, 9, 20, 0).unwrap()),
+                when_raw: "Thu Mar 30 09:20".to_string(),
+                from: Some("10.10.1.21".to_string()),
+                via: Some("ssh:notty".to_string()),
+            }
+        ]
+    );
+
+    assert_eq!(
+        summarize_failed_login_events(&events, "10.10.1.20", now),
+        FailedLoginInfo::Summary(super::types::FailedLoginSummary {
+            total: 2,
+            count_24h: 2,
+            count_7d: 2,
+            last_when: Some("Thu Mar 30 09:25".to_string()),
+            last_from: Some("10.10.1.20".to_string()),
+            last_via: Some("ssh:notty".to_string()),
+            top_sources: vec![
+                FailedLoginBucket {
+                    value: "10.10.1.20".to_string(),
+                    count: 1,
+                },
+                FailedLoginBucket {
+                    value: "10.10.1.21".to_string(),
+                    count: 1,
+                },
+            ],
+            top_vias: vec![FailedLoginBucket {
+                value: "ssh:notty".to_string(),
+                count: 2,
+            }],
+            unique_sources: 2,
+            severity: FailedLoginSeverity::High,
+            current_source_seen: true,
+        })
+    );
+}
+
+#[test]
+fn parse_package_update_outputs_count_packages() {
+    let apt_output = "\
+Listing...\n\
+bash/stable 5.2 amd64 [upgradable from: 5.1]\n\
+curl/stable 8.7 amd64 [upgradable from: 8.6]\n";
+    let dnf_output = "\
+Last metadata expiration check: 0:42:11 ago on Thu 30 Mar 2026 09:00:00 AM UTC.\n\
+bash.x86_64          5.2-1.el9          baseos\n\
+curl.x86_64          8.7-1.el9          appstream\n";
+
+    assert_eq!(parse_apt_upgradable_output(apt_output), 2);
+    assert_eq!(parse_dnf_check_update_output(dnf_output), 2);
+}
+
+#[test]
+fn parse_update_notifier_count_reads_both_summary_styles() {
+    let current = "\n12 updates can be applied immediately.\n5 of these updates are standard security updates.\n";
+    assert_eq!(parse_update_notifier_count(current), Some(12));
+    assert_eq!(
+        parse_update_notifier_count(
+            "0 packages can be updated.\n0 updates are security updates.\n"
+        ),
+        Some(0)
+    );
+    assert_eq!(parse_update_notifier_count("\n"), None);
+}
+
+#[test]
+fn parse_needrestart_batch_output_counts_services() {
+    let output = "\
+NEEDRESTART-VER: 3.6\n\
+NEEDRESTART-KCUR: 6.1.0-18-amd64\n\
+NEEDRESTART-KEXP: 6.1.0-18-amd64\n\
+NEEDRESTART-KSTA: 1\n\
+NEEDRESTART-SVC: ssh.service\n\
+NEEDRESTART-SVC: cron.service\n\
+NEEDRESTART-SVC: systemd-journald.service\n";
+
+    assert_eq!(parse_needrestart_batch_output(output), 3);
+    assert_eq!(parse_needrestart_batch_output("NEEDRESTART-VER: 3.6\n"), 0);
+}
+
+#[test]
+fn probe_cache_round_trips_and_expires() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("cache").join("needrestart.txt");
+
+    write_probe_cache(&path, "3").unwrap();
+    assert_eq!(read_probe_cache(&path, 60).as_deref(), Some("3"));
+
+    fs::write(&path, "fetched_at=0\n\n3").unwrap();
+    assert_eq!(read_probe_cache(&path, 60), None);
+}
+
+#[test]
+fn probe_cache_remembers_failures_for_the_shorter_ttl() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("needrestart.txt");
+
+    write_probe_cache_failure(&path, "timed out after 1500ms").unwrap();
+    assert_eq!(
+        read_probe_cache_result(&path, 3600, 600),
+        Some(Err("timed out after 1500ms (cached)".to_string()))
+    );
+
+    let stale_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 900;
+    fs::write(
+        &path,
+        format!("fetched_at={}\n\nfailed: timed out", stale_at),
+    )
+    .unwrap();
+    assert_eq!(read_probe_cache_result(&path, 3600, 600), None);
+
+    fs::write(&path, format!("fetched_at={}\n\n3", stale_at)).unwrap();
+    assert_eq!(
+        read_probe_cache_result(&path, 3600, 600),
+        Some(Ok("3".to_string()))
+    );
+}
+
+fn cached_item(label: &str, value: &str) -> RenderedItem {
+    RenderedItem {
+        label: label.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn cached_output_round_trips_escaped_items() {
+    let modules = [ModuleKind::Host, ModuleKind::Disk, ModuleKind::Updates];
+    let output = CachedOutput {
+        modules: vec![
+            (ModuleKind::Host, vec![cached_item("Host name:", "web-01")]),
+            (
+                ModuleKind::Disk,
+                vec![
+                    cached_item("Disk (/):", "\u{1b}[93m40.00/50.00 GB\u{1b}[0m"),
+                    cached_item("Disk (/srv):", "tab\there, back\\slash\nnext"),
+                ],
+            ),
+            (ModuleKind::Updates, Vec::new()),
+        ],
+        alerts: vec!["/ is 96% full".to_string()],
+    };
+    let body = format_cached_output(&output, "abc", "boot-1");
+
+    assert_eq!(
+        parse_cached_output(&body, "abc", "boot-1", &modules),
+        Some(output)
+    );
+    assert_eq!(parse_cached_output(&body, "def", "boot-1", &modules), None);
+    assert_eq!(parse_cached_output(&body, "abc", "boot-2", &modules), None);
+    assert_eq!(
+        parse_cached_output(&body, "abc", "boot-1", &[ModuleKind::Host]),
+        None
+    );
+}
+
+#[test]
+fn cached_output_merge_keeps_module_order_and_fresh_session_items() {
+    let cached = CachedOutput {
+        modules: vec![
+            (ModuleKind::Host, vec![cached_item("Host name:", "web-01")]),
+            (ModuleKind::Disk, vec![cached_item("Disk (/):", "40%")]),
+        ],
+        alerts: Vec::new(),
+    };
+    let fresh = vec![
+        (ModuleKind::Time, vec![cached_item("Current time:", "now")]),
+        (ModuleKind::User, vec![cached_item("User info:", "admin")]),
+    ];
+
+    let merged = cached.merge(
+        &[
+            ModuleKind::Host,
+            ModuleKind::User,
+            ModuleKind::Time,
+            ModuleKind::Disk,
+        ],
+        fresh,
+    );
+    let labels = merged
+        .iter()
+        .flat_map(|(_, items)| items.iter().map(|item| item.label.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec!["Host name:", "User info:", "Current time:", "Disk (/):"]
+    );
+}
+
+#[test]
+fn output_cache_stores_and_loads_until_the_config_changes() {
+    let dir = tempdir().unwrap();
+    let modules = [ModuleKind::Host, ModuleKind::User];
+    let mut cfg = MotdConfig {
+        cache_ttl_secs: Some(60),
+        ..MotdConfig::default()
+    };
+    let at = |cfg: &MotdConfig| OutputCache {
+        path: dir.path().join("motdyn").join("cache"),
+        ..OutputCache::resolve(cfg, ViewerRole::Root, &modules, false).unwrap()
+    };
+    let cache = at(&cfg);
+    assert_eq!(cache.load(&modules), None);
+
+    let output = CachedOutput {
+        modules: vec![(ModuleKind::Host, vec![cached_item("Host name:", "web-01")])],
+        alerts: Vec::new(),
+    };
+    cache.store(&output).unwrap();
+    assert_eq!(cache.load(&modules), Some(output));
+    assert_eq!(cache.load(&[ModuleKind::Host, ModuleKind::Disk]), None);
+
+    cfg.farewell = Some("Bye".to_string());
+    assert_eq!(at(&cfg).load(&modules), None);
+    cfg.cache_ttl_secs = None;
+    assert_eq!(
+        OutputCache::resolve(&cfg, ViewerRole::Root, &modules, false),
+        None
+    );
+}
+
+#[test]
+fn parse_cpu_limits_handle_ranges_and_quotas() {
+    assert_eq!(parse_cpu_range_list("0-15\n"), Some(16));
+    assert_eq!(parse_cpu_range_list("0-3,8,10-11"), Some(7));
+    assert_eq!(parse_cpu_range_list(""), None);
+    assert_eq!(parse_cgroup_cpu_max("400000 100000"), Some(4.0));
+    assert_eq!(parse_cgroup_cpu_max("max 100000"), None);
+    assert_eq!(parse_cgroup_cfs_quota("150000", "100000"), Some(1.5));
+    assert_eq!(parse_cgroup_cfs_quota("-1", "100000"), None);
+}
+
+#[test]
+fn parse_oom_kill_events_counts_kills_and_tracks_last_victim() {
+    let log = "\
+6,1201,88211,-;eth0: link up\n\
+3,1302,99120,-;Out of memory: Killed process 4211 (java) total-vm:8123456kB, anon-rss:4096000kB\n\
+3,1340,99410,-;Memory cgroup out of memory: Killed process 5120 (python3) total-vm:123kB\n\
+[ 8812.120] Out of memory: Killed process 7781 (postgres) total-vm:1kB\n";
+
+    assert_eq!(
+        parse_oom_kill_events(log),
+        OomSummary {
+            count: 3,
+            last_victim: Some("postgres".to_string()),
+        }
+    );
+    assert_eq!(parse_oom_kill_events("6,1,1,-;booted\n").count, 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn syslog_login_event_includes_user_source_and_host() {
+    let when = Local.timestamp_opt(1_768_467_000, 0).unwrap();
+    let event = format_login_event(&sample_snapshot(), when);
+
+    assert!(event.starts_with("banner shown user=admin from=10.10.1.15 host=prod-hpc-01 at="));
+    assert!(event.ends_with(&when.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)));
+    assert_eq!(
+        format_syslog_message("banner shown", 42),
+        "<38>motdyn[42]: banner shown"
+    );
+}
+
+#[cfg(feature = "remote-welcome")]
+#[test]
+fn release_check_parses_tags_and_compares_versions() {
+    let body = r#"{"url":"https://api.github.com/x","tag_name": "1.0.15","name":"1.0.15"}"#;
+    assert_eq!(parse_release_tag(body).as_deref(), Some("1.0.15"));
+    assert_eq!(parse_release_tag(r#"{"message":"Not Found"}"#), None);
+
+    assert!(is_newer_version("1.0.15", "1.0.14"));
+    assert!(is_newer_version("v1.1.0", "1.0.14"));
+    assert!(!is_newer_version("1.0.14", "1.0.14"));
+    assert!(!is_newer_version("1.0.9", "1.0.14"));
+    assert!(!is_newer_version("1.0.15-rc.1", "1.0.15"));
+    assert!(!is_newer_version("nightly", "1.0.14"));
+}
+
+#[test]
+fn render_module_lines_reports_newer_release() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::UpdateCheck], &sample_snapshot(), &settings);
+    assert_eq!(
+        lines,
+        vec![format!(
+            "motdyn update: 1.2.0 available (running {})",
+            env!("CARGO_PKG_VERSION")
+        )]
+    );
+}
+
+#[test]
+fn render_module_lines_draws_progress_bars_before_usage_figures() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: Some(8),
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let snapshot = sample_snapshot();
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Memory], &snapshot, &settings),
+        vec!["Memory used/total: [##------] 0.38/1.50 TB (25.00%)"]
+    );
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Disk], &snapshot, &settings),
+        vec![
+            "Disk usage (root): /     [#-------] 1.20 TB/7.68 TB (15.62%)",
+            "Disk usage (nfs):  /NFS  [#######-] 1.72 TB/1.97 TB (87.31%)",
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_clips_values_to_the_line_width() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: Some(40),
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.host_name = "build-farm-01.example.internal.corp".to_string();
+
+    let lines = render_module_lines(&[ModuleKind::Host, ModuleKind::Load], &snapshot, &settings);
+    assert_eq!(lines[0], "Host name:    build-farm-01.example.int…");
+    assert_eq!(visible_width(&lines[0]), 40);
+    assert!(lines[1].starts_with("Load average: "));
+    assert!(!lines[1].ends_with('…'));
+}
+
+#[test]
+fn render_module_lines_shows_display_hostname_alias() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: Some("build-farm".to_string()),
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::Host], &sample_snapshot(), &settings);
+    assert_eq!(lines, vec!["Host name: build-farm".to_string()]);
+}
+
+#[test]
+fn parse_authorized_keys_fingerprints_keys_and_skips_options() {
+    let content = "\
+# deploy keys
+ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIDOI9RWHP4K5OEy5zfisOb4dbqg1YhGZeXkRsCbaG5u7 alice@laptop
+
+from=\"10.0.0.0/8\",no-pty ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIDOI9RWHP4K5OEy5zfisOb4dbqg1YhGZeXkRsCbaG5u7 ci runner
+ssh-rsa not-base64!
+";
+
+    let keys = parse_authorized_keys(content);
+    assert_eq!(keys.len(), 2);
+    assert_eq!(
+        keys[0],
+        AuthorizedKey {
+            key_type: "ssh-ed25519".to_string(),
+            fingerprint: "SHA256:aw3WNn8FeWIbfgl+D8690Oa0wFWYdRSyamS0G0fWZDQ".to_string(),
+            comment: "alice@laptop".to_string(),
+        }
+    );
+    assert_eq!(keys[1].comment, "ci runner");
+    assert_eq!(keys[1].fingerprint, keys[0].fingerprint);
+}
+
+#[test]
+fn render_module_lines_counts_authorized_keys() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let mut snapshot = sample_snapshot();
+    snapshot.authorized_keys.push(AuthorizedKeysFile {
+        owner: Some("root".to_string()),
+        keys: Vec::new(),
+    });
+    let lines = render_module_lines(&[ModuleKind::AuthorizedKeys], &snapshot, &settings);
+    assert_eq!(
+        lines,
+        vec![
+            "Authorized keys:        1".to_string(),
+            "Authorized keys (root): 0".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_reports_oom_events() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::Oom], &sample_snapshot(), &settings);
+    assert_eq!(lines, vec!["OOM events: 3 (last: java)".to_string()]);
+}
+
+#[test]
+fn parse_proc_stat_cpu_ticks_handles_names_with_parentheses() {
+    assert_eq!(
+        parse_proc_stat_cpu_ticks(
+            "4211 (java) S 1 4211 4211 0 -1 4194560 912 0 0 0 1500 250 0 0 20 0 42 0 3120\n"
+        ),
+        Some(("java".to_string(), 1750))
+    );
+    assert_eq!(
+        parse_proc_stat_cpu_ticks("77 (tmux: (server)) R 1 77 77 0 -1 0 0 0 0 0 7 3 0 0 20 0 1"),
+        Some(("tmux: (server)".to_string(), 10))
+    );
+    assert_eq!(parse_proc_stat_cpu_ticks("12 (short) S 1"), None);
+}
+
+#[test]
+fn read_top_memory_processes_ranks_by_rss_and_skips_unreadable_pids() {
+    let dir = tempdir().expect("tempdir should be created");
+    let add = |pid: &str, comm: Option<&str>, status: Option<&str>| {
+        let pid_dir = dir.path().join(pid);
+        fs::create_dir(&pid_dir).expect("pid dir should be created");
+        if let Some(comm) = comm {
+            fs::write(pid_dir.join("comm"), comm).expect("comm should be written");
+        }
+        if let Some(status) = status {
+            fs::write(pid_dir.join("status"), status).expect("status should be written");
+        }
+    };
+    add(
+        "1",
+        Some("systemd\n"),
+        Some("Name:\tsystemd\nVmRSS:\t   12288 kB\n"),
+    );
+    add("2", Some("kthreadd\n"), Some("Name:\tkthreadd\n"));
+    add("300", Some("postgres\n"), Some("VmRSS:\t 2097152 kB\n"));
+    add("301", Some("exited\n"), None);
+    add("302", Some("nginx\n"), Some("VmRSS:\t   65536 kB\n"));
+    add("303", Some("tiny\n"), Some("VmRSS:\t       4 kB\n"));
+    fs::create_dir(dir.path().join("self")).expect("non-pid dir should be created");
+
+    let top = read_top_memory_processes(dir.path(), 3).expect("proc root should be readable");
+    assert_eq!(
+        top.iter()
+            .map(|process| (process.pid, process.name.as_str(), process.rss_kb))
+            .collect::<Vec<_>>(),
+        vec![
+            (300, "postgres", 2_097_152),
+            (302, "nginx", 65_536),
+            (1, "systemd", 12_288)
+        ]
+    );
+    assert_eq!(parse_status_vm_rss_kb("VmRSS:\t  512 kB\n"), Some(512));
+    assert_eq!(parse_status_vm_rss_kb("Name:\tkthreadd\n"), None);
+    assert!(read_top_memory_processes(&dir.path().join("missing"), 3).is_err());
+}
+
+#[test]
+fn pick_top_cpu_process_skips_exited_and_reused_pids() {
+    let before = HashMap::from([
+        (10, ("nginx".to_string(), 100)),
+        (20, ("postgres".to_string(), 500)),
+        (30, ("exited".to_string(), 10)),
+        (40, ("old".to_string(), 0)),
+    ]);
+    let after = HashMap::from([
+        (10, ("nginx".to_string(), 110)),
+        (20, ("postgres".to_string(), 540)),
+        (40, ("reused".to_string(), 900)),
+        (50, ("new".to_string(), 900)),
+    ]);
+
+    assert_eq!(
+        pick_top_cpu_process(&before, &after, Duration::from_millis(200)),
+        Some(TopProcess {
+            name: "postgres".to_string(),
+            cpu_percent: 200.0,
+        })
+    );
+    assert_eq!(
+        pick_top_cpu_process(&before, &before, Duration::from_millis(200)),
+        None
+    );
+}
+
+#[test]
+fn render_module_lines_reports_top_cpu_process() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::TopCpu], &sample_snapshot(), &settings);
+    assert_eq!(lines, vec!["Top CPU: postgres (42.5%)".to_string()]);
+}
+
+#[test]
+fn kernel_preemption_detects_rt_kernels_and_tick_rate() {
+    assert!(is_preempt_rt_kernel(Some("1"), None));
+    assert!(is_preempt_rt_kernel(
+        None,
+        Some("#1 SMP PREEMPT_RT Debian 6.1.90-1 (2024-05-03)")
+    ));
+    assert!(!is_preempt_rt_kernel(
+        None,
+        Some("#1 SMP PREEMPT_DYNAMIC Debian 6.1.90-1 (2024-05-03)")
+    ));
+    assert!(!is_preempt_rt_kernel(None, None));
+
+    let config = "# CONFIG_HZ_100 is not set\nCONFIG_HZ_1000=y\nCONFIG_HZ=1000\n";
+    assert_eq!(
+        find_kernel_config_hz(config.lines().map(str::to_string)),
+        Some(1000)
+    );
+    assert_eq!(find_kernel_config_hz(Vec::new()), None);
+}
+
+#[test]
+fn mac_status_parsers_map_selinux_modes_and_apparmor_profiles() {
+    assert_eq!(parse_selinux_enforce(Some("1\n")), SeLinuxMode::Enforcing);
+    assert_eq!(parse_selinux_enforce(Some("0")), SeLinuxMode::Permissive);
+    assert_eq!(parse_selinux_enforce(None), SeLinuxMode::Disabled);
+
+    let profiles = "\
+/usr/sbin/cupsd (enforce)
+/usr/bin/man (enforce)
+/snap/firefox (complain)
+lsb_release (unconfined)
+";
+    assert_eq!(count_apparmor_profiles(profiles), (2, 1));
+}
+
+#[test]
+fn render_module_lines_reports_mac_status() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    let render = |snapshot: &SystemSnapshot| {
+        render_module_lines(&[ModuleKind::Mac], snapshot, &settings).join("\n")
+    };
+
+    assert_eq!(render(&snapshot), "MAC: SELinux Permissive");
+    snapshot.mac_status = Some(MacStatus::AppArmor {
+        enabled: true,
+        profiles: Some((42, 3)),
+    });
+    assert_eq!(
+        render(&snapshot),
+        "MAC: AppArmor Enabled (42 enforce, 3 complain)"
+    );
+    snapshot.mac_status = Some(MacStatus::None);
+    assert_eq!(render(&snapshot), "MAC: none detected");
+}
+
+#[test]
+fn container_storage_parsers_sum_api_and_cli_sizes() {
+    let body = r#"{
+        "LayersSize": 3000,
+        "Images": [
+            {"Size": 2000, "SharedSize": 500, "Containers": 1},
+            {"Size": 1000, "SharedSize": 0, "Containers": 0}
+        ],
+        "Containers": [
+            {"SizeRw": 100, "State": "running"},
+            {"SizeRw": 50, "State": "exited"}
+        ],
+        "Volumes": [
+            {"UsageData": {"Size": 400, "RefCount": 1}},
+            {"UsageData": {"Size": 300, "RefCount": 0}}
+        ],
+        "BuildCache": [{"Size": 70, "InUse": false}]
+    }"#;
+    assert_eq!(
+        parse_docker_system_df_json(body),
+        Some(ContainerStorage {
+            total_bytes: 3000 + 150 + 700 + 70,
+            reclaimable_bytes: 1500 + 50 + 300 + 70,
+        })
+    );
+    assert_eq!(parse_docker_system_df_json("not json"), None);
+
+    let table = "\
+Images\t42.1GB\t18.2GB (43%)
+Containers\t512kB\t0B (0%)
+Local Volumes\t1.5MB\t1.5MB (100%)
+Build Cache\t0B\t0B
+";
+    assert_eq!(
+        parse_system_df_table(table),
+        Some(ContainerStorage {
+            total_bytes: 42_100_000_000 + 512_000 + 1_500_000,
+            reclaimable_bytes: 18_200_000_000 + 1_500_000,
+        })
+    );
+    assert_eq!(parse_system_df_table(""), None);
+    assert_eq!(parse_si_size("3.5 GB"), Some(3_500_000_000));
+    assert_eq!(parse_si_size("12XB"), None);
+}
+
+#[test]
+fn parse_zfs_pools_prefers_root_dataset_space() {
+    let zpool = "tank\t4000000000000\t1000000000000\t3000000000000\nscratch\t100\t25\t75\n";
+    let zfs = "tank\t900000000000\t1600000000000\n";
+    let pools = parse_zfs_pools(zpool, zfs);
+    assert_eq!(
+        pools
+            .iter()
+            .map(|pool| (
+                pool.label.as_str(),
+                pool.used_bytes,
+                pool.total_bytes,
+                pool.ratio
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("ZFS pool (tank):", 900_000_000_000, 2_500_000_000_000, 36.0),
+            ("ZFS pool (scratch):", 25, 100, 25.0),
+        ]
+    );
+    assert!(parse_zfs_pools("", "").is_empty());
+    assert!(parse_zfs_pools("tank\t-\t-\t-\n", "").is_empty());
+}
+
+#[test]
+fn btrfs_parsers_read_mounts_and_overall_usage() {
+    let mounts = "\
+/dev/sda2 / btrfs rw,relatime,subvol=/@ 0 0
+/dev/sda2 /home btrfs rw,relatime,subvol=/@home 0 0
+/dev/sdb1 /srv btrfs rw,relatime 0 0
+/dev/sdc1 /boot ext4 rw 0 0
+";
+    assert_eq!(parse_btrfs_mounts(mounts), vec!["/", "/srv"]);
+
+    let usage = "\
+Overall:
+    Device size:\t\t\t  2000000000000
+    Device allocated:\t\t   800000000000
+    Used:\t\t\t   600000000000
+    Free (estimated):\t\t   700000000000\t(min: 700000000000)
+    Data ratio:\t\t\t           2.00
+    Metadata ratio:\t\t           2.00
+    Global reserve:\t\t      536870912\t(used: 0)
+
+Data,RAID1: Size:390000000000, Used:290000000000 (74.36%)
+";
+    assert_eq!(
+        parse_btrfs_usage(usage),
+        Some((300_000_000_000, 1_000_000_000_000))
+    );
+    assert_eq!(parse_btrfs_usage("ERROR: not a btrfs filesystem"), None);
+}
+
+#[test]
+fn parse_failed_units_takes_the_unit_column() {
+    let output = "\
+nginx.service     loaded failed failed A high performance web server
+● backup.timer    loaded failed failed Nightly backup
+";
+    assert_eq!(
+        parse_failed_units(output),
+        vec!["nginx.service".to_string(), "backup.timer".to_string()]
+    );
+    assert!(parse_failed_units("").is_empty());
+}
+
+#[test]
+fn render_module_lines_lists_failed_units_only_when_present() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    assert!(render_module_lines(&[ModuleKind::FailedUnits], &snapshot, &settings).is_empty());
+
+    snapshot.failed_units = vec!["nginx.service".to_string(), "backup.timer".to_string()];
+    assert_eq!(
+        render_module_lines(&[ModuleKind::FailedUnits], &snapshot, &settings),
+        vec!["Failed units: nginx.service, backup.timer".to_string()]
+    );
+}
+
+#[test]
+fn parse_battery_reads_bat_supplies_only() {
+    let dir = tempdir().unwrap();
+    for (name, capacity, status) in [
+        ("BAT1", "55\n", "Charging\n"),
+        ("BAT0", "78\n", "Discharging\n"),
+        ("AC", "", "Online\n"),
+    ] {
+        let supply = dir.path().join(name);
+        fs::create_dir(&supply).unwrap();
+        fs::write(supply.join("capacity"), capacity).unwrap();
+        fs::write(supply.join("status"), status).unwrap();
+    }
+
+    assert_eq!(
+        parse_battery(dir.path()),
+        vec![
+            BatteryStatus {
+                name: "BAT0".to_string(),
+                capacity: 78,
+                status: "Discharging".to_string(),
+            },
+            BatteryStatus {
+                name: "BAT1".to_string(),
+                capacity: 55,
+                status: "Charging".to_string(),
+            },
+        ]
+    );
+    assert!(parse_battery(&dir.path().join("missing")).is_empty());
+}
+
+#[test]
+fn render_module_lines_names_batteries_only_when_several() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    assert!(render_module_lines(&[ModuleKind::Battery], &snapshot, &settings).is_empty());
+
+    snapshot.batteries = vec![BatteryStatus {
+        name: "BAT0".to_string(),
+        capacity: 78,
+        status: "Discharging".to_string(),
+    }];
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Battery], &snapshot, &settings),
+        vec!["Battery: 78% (Discharging)".to_string()]
+    );
+
+    snapshot.batteries.push(BatteryStatus {
+        name: "BAT1".to_string(),
+        capacity: 12,
+        status: "Charging".to_string(),
+    });
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Battery], &snapshot, &settings),
+        vec![
+            "Battery: BAT0 78% (Discharging)".to_string(),
+            "Battery: BAT1 12% (Charging)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn container_count_parsers_split_running_from_total() {
+    let body = r#"[
+        {"Id": "a", "State": "running"},
+        {"Id": "b", "State": "exited"},
+        {"Id": "c", "State": "running"}
+    ]"#;
+    assert_eq!(
+        parse_docker_containers_json(body),
+        Some(ContainerCounts {
+            running: 2,
+            total: 3,
+        })
+    );
+    assert_eq!(parse_docker_containers_json("{}"), None);
+    assert_eq!(
+        count_container_states("running\nexited\ncreated\n\n"),
+        ContainerCounts {
+            running: 1,
+            total: 3,
+        }
+    );
+}
+
+#[test]
+fn render_module_lines_reports_container_counts_under_virtualization() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::from([HiddenField::Virtualization]),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.containers = Some(ContainerCounts {
+        running: 5,
+        total: 8,
+    });
+
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Virtualization], &snapshot, &settings),
+        vec!["Containers: 5 running / 8 total".to_string()]
+    );
+}
+
+#[test]
+fn render_module_lines_lists_storage_pools() {
+    let mut settings = resolve_output_settings(&MotdConfig::default());
+    settings.plain = true;
+    let mut snapshot = sample_snapshot();
+    assert!(render_module_lines(&[ModuleKind::Pools], &snapshot, &settings).is_empty());
+
+    snapshot.storage_pools = vec![
+        DiskUsageItem {
+            label: "ZFS pool (tank):".to_string(),
+            mount_path: "tank".to_string(),
+            fstype: "zfs".to_string(),
+            used_bytes: 900_000_000_000,
+            total_bytes: 2_500_000_000_000,
+            ratio: 36.0,
+            timed_out: false,
+        },
+        DiskUsageItem {
+            label: "Btrfs (/srv):".to_string(),
+            mount_path: "/srv".to_string(),
+            fstype: "btrfs".to_string(),
+            used_bytes: 300_000_000_000,
+            total_bytes: 1_000_000_000_000,
+            ratio: 30.0,
+            timed_out: false,
+        },
+    ];
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Pools], &snapshot, &settings),
+        vec![
+            "ZFS pool (tank): 0.82 TB/2.27 TB (36.00%)".to_string(),
+            "Btrfs (/srv):    279.40 GB/931.32 GB (30.00%)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_reports_container_storage() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let lines = render_module_lines(
+        &[ModuleKind::ContainerStorage],
+        &sample_snapshot(),
+        &settings,
+    );
+
+    assert_eq!(
+        lines,
+        vec!["Container storage: 42.0 GB (18.0 GB reclaimable)".to_string()]
+    );
+}
+
+#[test]
+fn render_layout_fills_placeholders_and_keeps_unknown_ones() {
+    let settings = resolve_output_settings(&MotdConfig::default());
+    let layout = render_layout(
+        "{hostname} | {os} | up {uptime} | mem {mem_percent} of {mem_total} | {{x}} {bogus} {cpu_cores}c",
+        &sample_snapshot(),
+        &settings,
+    );
+    assert_eq!(
+        layout,
+        RenderedLayout {
+            text: "prod-hpc-01 | Rocky Linux 9.5 | up 24 days, 18:42:11 | mem 25.00% of 1.50 TB | {x} {bogus} 192c"
+                .to_string(),
+            unknown: vec!["bogus".to_string()],
+        }
+    );
+    assert_eq!(
+        render_layout("{ not a placeholder }", &sample_snapshot(), &settings).text,
+        "{ not a placeholder }"
+    );
+}
+
+#[test]
+fn layout_modules_lists_each_probed_module_once() {
+    assert_eq!(
+        layout_modules("{hostname} {mem_used}/{mem_total} {disk_percent} {bogus}"),
+        vec![ModuleKind::Host, ModuleKind::Memory, ModuleKind::Disk]
+    );
+}
+
+#[test]
+fn threshold_paint_kind_judges_the_printed_percentage() {
+    let threshold = Threshold {
+        warn: 75.0,
+        crit: 90.0,
+    };
+    assert_eq!(threshold_paint_kind(74.99, 2, threshold), None);
+    assert_eq!(
+        threshold_paint_kind(75.0, 2, threshold),
+        Some(PaintKind::Yellow)
+    );
+    assert_eq!(
+        threshold_paint_kind(89.996, 2, threshold),
+        Some(PaintKind::Red)
+    );
+    assert_eq!(
+        threshold_paint_kind(89.6, 0, threshold),
+        Some(PaintKind::Red)
+    );
+    assert_eq!(
+        threshold_paint_kind(89.4, 0, threshold),
+        Some(PaintKind::Yellow)
+    );
+}
+
+#[test]
+fn load_average_kind_turns_red_above_online_core_count() {
+    let mut snapshot = sample_snapshot();
+    snapshot.cpu_online = Some(4);
+    assert_eq!(load_average_kind(&snapshot), PaintKind::Yellow);
+
+    snapshot.load_average = "6.10 3.20 1.05".to_string();
+    assert_eq!(load_average_kind(&snapshot), PaintKind::Red);
+
+    snapshot.cpu_online = None;
+    snapshot.cpu_count = 8;
+    assert_eq!(load_average_kind(&snapshot), PaintKind::Yellow);
+
+    snapshot.load_average = "unknown".to_string();
+    assert_eq!(load_average_kind(&snapshot), PaintKind::Yellow);
+}
+
+#[test]
+fn read_network_links_skips_virtual_interfaces() {
+    let dir = tempdir().unwrap();
+    let add = |iface: &str, device: bool, files: &[(&str, &str)]| {
+        let path = dir.path().join(iface);
+        fs::create_dir_all(&path).unwrap();
+        if device {
+            fs::create_dir(path.join("device")).unwrap();
+        }
+        for (name, content) in files {
+            fs::write(path.join(name), content).unwrap();
+        }
+    };
+    add(
+        "eth1",
+        true,
+        &[
+            ("operstate", "down\n"),
+            ("speed", "-1\n"),
+            ("duplex", "unknown\n"),
+        ],
+    );
+    add(
+        "eth0",
+        true,
+        &[
+            ("operstate", "up\n"),
+            ("speed", "1000\n"),
+            ("duplex", "full\n"),
+        ],
+    );
+    add("veth12ab", true, &[("operstate", "up\n")]);
+    add("bond0", false, &[("operstate", "up\n")]);
+
+    let links = read_network_links(dir.path());
+    assert_eq!(
+        links,
+        vec![
+            NetworkLink {
+                iface: "eth0".into(),
+                operstate: "up".into(),
+                speed_mbps: Some(1000),
+                duplex: Some("full".into()),
+            },
+            NetworkLink {
+                iface: "eth1".into(),
+                operstate: "down".into(),
+                speed_mbps: None,
+                duplex: None,
+            },
+        ]
+    );
+    assert_eq!(parse_link_speed("0"), None);
+}
+
+#[test]
+fn render_module_lines_reports_link_speed_and_state() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.network_links.push(NetworkLink {
+        iface: "eth1".into(),
+        operstate: "down".into(),
+        speed_mbps: None,
+        duplex: None,
+    });
+
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Link], &snapshot, &settings),
+        vec![
+            "Link: eth0: up 10Gb/s full".to_string(),
+            "Link: eth1: down".to_string(),
+        ]
+    );
+    assert_eq!(format_link_speed(2500), "2.5Gb/s");
+    assert_eq!(format_link_speed(100), "100Mb/s");
+}
+
+#[test]
+fn render_module_lines_flags_pending_reboot_only_when_known() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    let render = |snapshot: &SystemSnapshot| {
+        render_module_lines(&[ModuleKind::RebootRequired], snapshot, &settings)
+    };
+
+    assert_eq!(
+        render(&snapshot),
+        vec!["Reboot: *** System restart required ***".to_string()]
+    );
+    snapshot.reboot_required = Some(false);
+    assert!(render(&snapshot).is_empty());
+    snapshot.reboot_required = None;
+    assert!(render(&snapshot).is_empty());
+}
+
+#[test]
+fn auto_update_parsers_read_apt_and_dnf_settings() {
+    let apt = "\
+// APT::Periodic::Unattended-Upgrade \"0\";
+APT::Periodic::Update-Package-Lists \"1\";
+APT::Periodic::Unattended-Upgrade \"1\";
+";
+    assert_eq!(
+        parse_apt_periodic_setting(apt, "APT::Periodic::Unattended-Upgrade").as_deref(),
+        Some("1")
+    );
+    assert_eq!(
+        parse_apt_periodic_setting(apt, "APT::Periodic::AutocleanInterval"),
+        None
+    );
+
+    let dnf = "\
+[commands]
+upgrade_type = default
+apply_updates = yes
+
+[emitters]
+apply_updates = no
+";
+    assert!(parse_dnf_automatic_apply_updates(dnf));
+    assert!(!parse_dnf_automatic_apply_updates(
+        "[commands]\napply_updates = no\n"
+    ));
+}
+
+#[test]
+fn render_module_lines_reports_auto_updates_state() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    let render = |snapshot: &SystemSnapshot| {
+        render_module_lines(&[ModuleKind::AutoUpdates], snapshot, &settings)
+    };
+
+    assert_eq!(render(&snapshot), vec!["Auto-updates: enabled".to_string()]);
+    snapshot.auto_updates_enabled = Some(false);
+    assert_eq!(
+        render(&snapshot),
+        vec!["Auto-updates: disabled".to_string()]
+    );
+    snapshot.auto_updates_enabled = None;
+    assert!(render(&snapshot).is_empty());
+}
+
+#[test]
+fn rank_top_users_orders_by_process_count_and_names_uids() {
+    let names = parse_passwd_names(
+        "root:x:0:0:root:/root:/bin/bash\n\
+alice:x:1000:1000::/home/alice:/bin/bash\n\
+bob:x:1001:1001::/home/bob:/bin/bash\n\
+broken line\n",
+    );
+    assert_eq!(names.get(&1000).map(String::as_str), Some("alice"));
+    assert_eq!(names.len(), 3);
+
+    let counts = HashMap::from([(0, 88), (1000, 412), (1001, 88), (4242, 7)]);
+    assert_eq!(
+        rank_top_users(&counts, &names, 3),
+        vec![
+            UserProcessCount {
+                user: "alice".to_string(),
+                processes: 412,
+            },
+            UserProcessCount {
+                user: "bob".to_string(),
+                processes: 88,
+            },
+            UserProcessCount {
+                user: "root".to_string(),
+                processes: 88,
+            },
+        ]
+    );
+    assert_eq!(rank_top_users(&counts, &HashMap::new(), 4)[3].user, "4242");
+}
+
+#[test]
+fn count_processes_counts_numeric_proc_entries() {
+    let dir = tempdir().unwrap();
+    for name in ["1", "412", "self", "net", "sys"] {
+        fs::create_dir(dir.path().join(name)).unwrap();
+    }
+    assert_eq!(count_processes(dir.path()), Ok(2));
+    assert!(count_processes(&dir.path().join("missing")).is_err());
+}
+
+#[test]
+fn count_established_on_port_matches_local_ssh_connections() {
+    let table = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1234
+   1: 0F010A0A:0016 1401010A:D431 01 00000000:00000000 02:0009A2A4 00000000     0        0 2345
+   2: 0F010A0A:0016 1501010A:C3A0 01 00000000:00000000 02:0009A2A4 00000000     0        0 3456
+   3: 0F010A0A:D431 1401010A:0016 01 00000000:00000000 02:0009A2A4 00000000  1000        0 4567
+   4: 0F010A0A:0016 1601010A:C3A2 06 00000000:00000000 03:00000F2C 00000000     0        0 0
+";
+    assert_eq!(count_established_on_port(table, 22), 2);
+    assert_eq!(count_established_on_port(table, 443), 0);
+
+    let tcp6 =
+        "  sl  local_address                         remote_address                        st
+   0: 00000000000000000000000001000000:0016 00000000000000000000000001000000:9C40 01
+";
+    assert_eq!(count_established_on_port(tcp6, 22), 1);
+}
+
+#[test]
+fn render_module_lines_reports_process_and_ssh_session_counts() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    assert!(render_module_lines(&[ModuleKind::Processes], &snapshot, &settings).is_empty());
+
+    snapshot.process_count = Some(523);
+    snapshot.ssh_session_count = Some(3);
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Processes], &snapshot, &settings),
+        vec![
+            "Processes:    523".to_string(),
+            "SSH sessions: 3".to_string()
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_reports_top_users() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::TopUsers], &sample_snapshot(), &settings);
+    assert_eq!(lines, vec!["Top users: alice (412), root (88)".to_string()]);
+}
+
+#[test]
+fn parse_cgroup_memory_bytes_treats_max_as_unlimited() {
+    assert_eq!(
+        parse_cgroup_memory_bytes("2147483648\n"),
+        Some(2_147_483_648)
+    );
+    assert_eq!(parse_cgroup_memory_bytes("max\n"), None);
+}
+
+#[test]
+fn render_module_lines_labels_cgroup_memory() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.memory_cgroup = true;
+
+    let lines = render_module_lines(&[ModuleKind::Memory], &snapshot, &settings);
+    assert!(lines[0].starts_with("Memory (cgroup):"));
+}
+
+#[test]
+fn render_module_lines_shows_cpu_quota_only_when_limited() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+
+    let lines = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert_eq!(lines.len(), 1);
+
+    snapshot.cpu_online = Some(16);
+    snapshot.cpu_quota = Some(4.0);
+    let lines = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert_eq!(lines[1], "CPUs: 16 online (4.0 allowed)");
+}
+
+#[test]
+fn parse_meminfo_content_falls_back_to_memfree_when_memavailable_missing() {
+    let content = "\
+MemTotal:       16384 kB\n\
+MemFree:         4096 kB\n\
+SwapTotal:       2048 kB\n\
+SwapFree:        1024 kB\n";
+
+    assert_eq!(parse_meminfo_content(content), (16384, 4096, 2048, 1024));
+}
+
+#[test]
+fn parse_meminfo_content_tolerates_invalid_values() {
+    let content = "\
+MemTotal:       nope kB\n\
+MemAvailable:   2048 kB\n\
+SwapTotal:      broken kB\n\
+SwapFree:       1024 kB\n";
+
+    assert_eq!(parse_meminfo_content(content), (0, 2048, 0, 1024));
+}
+
+#[test]
+fn parse_cpuinfo_content_detects_arm_fallback_brand() {
+    let content = "\
+processor\t: 0\n\
+processor\t: 1\n\
+CPU implementer\t: 0x41\n\
+CPU part\t: 0xd08\n";
+
+    assert_eq!(
+        parse_cpuinfo_content(content.as_bytes()),
+        ("ARM Cortex-A72".to_string(), 2, None)
+    );
+}
+
+#[test]
+fn parse_cpuinfo_content_counts_physical_cores_across_sockets() {
+    let mut content = String::new();
+    for (processor, (package, core)) in [(0, 0), (0, 1), (1, 0), (1, 1)]
+        .into_iter()
+        .cycle()
+        .take(8)
+        .enumerate()
+    {
+        content.push_str(&format!(
+            "processor\t: {}\nmodel name\t: Intel(R) Xeon(R) Gold 6230\nphysical id\t: {}\ncore id\t\t: {}\n\n",
+            processor, package, core
+        ));
+    }
+
+    assert_eq!(
+        parse_cpuinfo_content(content.as_bytes()),
+        ("Intel(R) Xeon(R) Gold 6230".to_string(), 8, Some(4))
+    );
+
+    let partial = "processor\t: 0\nphysical id\t: 0\ncore id\t: 0\nprocessor\t: 1\n";
+    assert_eq!(parse_cpuinfo_content(partial.as_bytes()).2, None);
+}
+
+#[test]
+fn parse_lscpu_output_reads_sockets_cores_and_numa_nodes() {
+    let output = "\
+Architecture:            x86_64
+CPU(s):                  192
+Model name:              AMD EPYC 9654 96-Core Processor
+Thread(s) per core:      2
+Core(s) per socket:      48
+Socket(s):               2
+NUMA node(s):            2
+NUMA node0 CPU(s):       0-47,96-143
+";
+
+    assert_eq!(
+        parse_lscpu_output(output),
+        Some(CpuTopology {
+            sockets: 2,
+            cores_per_socket: 48,
+            threads_per_core: 2,
+            numa_nodes: 2,
+        })
+    );
+    assert_eq!(
+        parse_lscpu_output("Thread(s) per core: 1\nCore(s) per cluster: 4\nSocket(s): -\n"),
+        None
+    );
+}
+
+#[test]
+fn render_module_lines_prefers_cpu_topology_when_present() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.cpu_brand = "AMD EPYC".to_string();
+    snapshot.cpu_topology = Some(CpuTopology {
+        sockets: 2,
+        cores_per_socket: 24,
+        threads_per_core: 2,
+        numa_nodes: 2,
+    });
+    let lines = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert_eq!(
+        lines,
+        vec!["CPU: 2x AMD EPYC (48c/96t, 2 NUMA nodes)".to_string()]
+    );
+
+    snapshot.cpu_topology = Some(CpuTopology {
+        sockets: 1,
+        cores_per_socket: 8,
+        threads_per_core: 1,
+        numa_nodes: 1,
+    });
+    let lines = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert_eq!(lines, vec!["CPU: AMD EPYC (8c/8t)".to_string()]);
+}
+
+#[test]
+fn swap_activity_converts_vmstat_pages_to_bytes_per_second() {
+    let before = parse_vmstat_swap_pages("pgpgin 100\npswpin 10\npswpout 40\n").unwrap();
+    let after = parse_vmstat_swap_pages("pswpin 10\npswpout 52\npgpgout 9\n").unwrap();
+    assert_eq!(parse_vmstat_swap_pages("pgpgin 100\n"), None);
+
+    assert_eq!(
+        swap_activity_between(before, after, Duration::from_millis(200), 4096),
+        SwapActivity {
+            in_bytes_per_sec: 0,
+            out_bytes_per_sec: 245_760,
+        }
+    );
+}
+
+#[test]
+fn best_unit_scale_switches_units_at_each_power_of_1024() {
+    let suffix = |bytes: f64| best_unit_scale(bytes).1;
+    assert_eq!(suffix(0.0), "B");
+    assert_eq!(suffix(1023.0), "B");
+    assert_eq!(suffix(1024.0), "KB");
+    assert_eq!(suffix(1024.0 * 1024.0 - 1.0), "KB");
+    assert_eq!(suffix(1024.0 * 1024.0), "MB");
+    assert_eq!(suffix(BYTES_PER_GB - 1.0), "MB");
+    assert_eq!(suffix(BYTES_PER_GB), "GB");
+    assert_eq!(suffix(BYTES_PER_GB * 1024.0 - 1.0), "GB");
+    assert_eq!(suffix(BYTES_PER_GB * 1024.0), "TB");
+    assert_eq!(suffix(BYTES_PER_GB * 1024.0 * 1024.0), "PB");
+}
+
+#[test]
+fn memory_figures_use_the_unit_of_the_larger_value() {
+    assert_eq!(format_gb(0.5), "512.00 MB");
+    assert_eq!(format_gb(16.0), "16.00 GB");
+    assert_eq!(format_gb(2048.0), "2.00 TB");
+    assert_eq!(format_gb(1.0 / 1024.0 / 1024.0), "1.00 KB");
+
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.memory = UsageSummary {
+        used_gb: 0.125,
+        total_gb: 0.5,
+        ratio: 25.0,
+    };
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Memory], &snapshot, &settings),
+        vec!["Memory used/total: 128.00/512.00 MB (25.00%)".to_string()]
+    );
+}
+
+#[test]
+fn parse_net_throughput_sums_non_loopback_interfaces() {
+    let content = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 9000000     100    0    0    0     0          0         0  9000000     100    0    0    0     0       0          0
+  eth0: 1000000    2000    0    0    0     0          0         0   400000    1500    0    0    0     0       0          0
+wlan0:24000 30 0 0 0 0 0 0 1000 12 0 0 0 0 0 0
+";
+    assert_eq!(parse_net_throughput(content), Some((1_024_000, 401_000)));
+    assert_eq!(
+        parse_net_throughput("    lo: 9000000 100 0 0 0 0 0 0 9000000 100 0 0 0 0 0 0\n"),
+        None
+    );
+}
+
+#[test]
+fn render_module_lines_shows_network_throughput() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.net_throughput = Some(NetThroughput {
+        rx_bytes: 13_207_024_435,
+        tx_bytes: 4_831_838_208,
+        per_sec: false,
+    });
+    let lines = render_module_lines(&[ModuleKind::Network], &snapshot, &settings);
+    assert_eq!(
+        lines.last().map(String::as_str),
+        Some("Network (rx/tx): 12.3 GB / 4.5 GB since boot")
+    );
+
+    snapshot.net_throughput = Some(NetThroughput {
+        rx_bytes: 1_258_291,
+        tx_bytes: 300,
+        per_sec: true,
+    });
+    let lines = render_module_lines(&[ModuleKind::Network], &snapshot, &settings);
+    assert_eq!(
+        lines.last().map(String::as_str),
+        Some("Network (rx/tx): 1.2 MB/s / 300 B/s")
+    );
+}
+
+/// A backend with fixed figures, standing in for an alternative collector.
+struct FixedCollector;
+
+impl HostCollector for FixedCollector {
+    fn uptime_secs(&self) -> Option<u64> {
+        Some(90_061)
+    }
+
+    fn cpu_info(&self) -> (String, usize, Option<usize>) {
+        ("Fixed CPU".to_string(), 8, Some(4))
+    }
+
+    fn memory_kb(&self) -> (u64, u64, u64, u64) {
+        (8_388_608, 2_097_152, 0, 0)
+    }
+
+    fn disks(
+        &self,
+        _selection: &DiskMountSelection,
+        _timeout_ms: u64,
+        _diagnostics: &mut SnapshotDiagnostics,
+    ) -> (Option<UsageSummary>, Vec<DiskUsageItem>) {
+        let root = DiskUsageItem {
+            label: "Disk usage (/):".to_string(),
+            mount_path: "/".to_string(),
+            fstype: "ext4".to_string(),
+            used_bytes: 25,
+            total_bytes: 100,
+            ratio: 25.0,
+            timed_out: false,
+        };
+        (
+            Some(UsageSummary {
+                used_gb: 0.0,
+                total_gb: 0.0,
+                ratio: 25.0,
+            }),
+            vec![root],
+        )
+    }
+}
+
+#[test]
+fn collect_snapshot_from_reads_host_figures_through_the_collector() {
+    let snapshot = collect_snapshot_from(
+        &FixedCollector,
+        &[ModuleKind::Uptime, ModuleKind::Cpu, ModuleKind::Memory],
+        &MotdConfig::default(),
+        SnapshotOptions {
+            probe_disks: true,
+            record_login: false,
+        },
+    );
+
+    assert_eq!(snapshot.uptime_secs, Some(90_061));
+    assert_eq!(snapshot.uptime_str, "1 days, 01:01:01");
+    assert_eq!(snapshot.cpu_brand, "Fixed CPU");
+    assert_eq!(snapshot.cpu_count, 8);
+    assert_eq!(snapshot.cpu_physical_cores, Some(4));
+    assert_eq!(snapshot.swap.total_gb, 0.0);
+    assert_eq!(snapshot.disk_items.len(), 1);
+    assert_eq!(snapshot.disk_items[0].mount_path, "/");
+}
+
+#[cfg(all(feature = "sysinfo", target_os = "linux"))]
+#[test]
+fn sysinfo_collector_agrees_with_the_native_parsers() {
+    use super::collector::{NativeCollector, SysinfoCollector};
+
+    let (_, native_cpus, _) = NativeCollector.cpu_info();
+    let (_, sysinfo_cpus, _) = SysinfoCollector.cpu_info();
+    assert_eq!(sysinfo_cpus, native_cpus);
+
+    let (native_total, ..) = NativeCollector.memory_kb();
+    let (sysinfo_total, ..) = SysinfoCollector.memory_kb();
+    assert_eq!(sysinfo_total, native_total);
+
+    let mut diagnostics = SnapshotDiagnostics::default();
+    let (root, items) =
+        SysinfoCollector.disks(&DiskMountSelection::RootAndNfs, 2_000, &mut diagnostics);
+    assert!(root.is_some());
+    assert!(items.iter().any(|item| item.mount_path == "/"));
+}
+
+#[test]
+fn parse_pressure_reads_the_some_line() {
+    let content = "\
+some avg10=2.31 avg60=1.05 avg300=0.40 total=123456789
+full avg10=0.80 avg60=0.20 avg300=0.05 total=45678901
+";
+    assert_eq!(
+        parse_pressure(content),
+        Some(PressureAverages {
+            avg10: 2.31,
+            avg60: 1.05,
+            avg300: 0.40,
+        })
+    );
+    assert_eq!(
+        parse_pressure("full avg10=0.80 avg60=0.20 avg300=0.05 total=1\n"),
+        None
+    );
+    assert_eq!(
+        parse_pressure("some avg10=x avg60=0.20 avg300=0.05\n"),
+        None
+    );
+}
+
+#[test]
+fn render_module_lines_appends_pressure_items() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    let without = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert!(!without.iter().any(|line| line.contains("pressure")));
+
+    snapshot.pressure = PressureStall {
+        cpu: Some(PressureAverages {
+            avg10: 2.31,
+            avg60: 1.05,
+            avg300: 0.4,
+        }),
+        memory: None,
+        io: Some(PressureAverages {
+            avg10: 31.0,
+            avg60: 12.0,
+            avg300: 4.0,
+        }),
+    };
+    let lines = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert_eq!(
+        lines.last().map(String::as_str),
+        Some("CPU pressure (10s): 2.3%")
+    );
+    let lines = render_module_lines(&[ModuleKind::Memory], &snapshot, &settings);
+    assert!(!lines.iter().any(|line| line.contains("pressure")));
+    let lines = render_module_lines(&[ModuleKind::Disk], &snapshot, &settings);
+    assert!(lines.iter().any(|line| line == "IO pressure (10s): 31.0%"));
+
+    let pressure = UsageThresholds::default().pressure;
+    assert_eq!(threshold_paint_kind(2.31, 1, pressure), None);
+    assert_eq!(
+        threshold_paint_kind(12.0, 1, pressure),
+        Some(PaintKind::Yellow)
+    );
+    assert_eq!(
+        threshold_paint_kind(31.0, 1, pressure),
+        Some(PaintKind::Red)
+    );
+}
+
+#[test]
+fn cpu_busy_percent_uses_the_proc_stat_delta() {
+    let before = parse_proc_stat_cpu_times(
+        "cpu  100 0 50 800 50 0 0 0 10 0\ncpu0 50 0 25 400 25 0 0 0 5 0\n",
+    );
+    assert_eq!(before, Some((850, 1000)));
+    let after = parse_proc_stat_cpu_times("cpu  250 0 100 1000 50 0 0 0 10 0\n");
+    assert_eq!(after, Some((1050, 1400)));
+
+    assert_eq!(
+        cpu_busy_percent(before.unwrap(), after.unwrap()),
+        Some(50.0)
+    );
+    assert_eq!(cpu_busy_percent((10, 100), (10, 100)), None);
+    assert_eq!(parse_proc_stat_cpu_times("intr 12345\n"), None);
+}
+
+#[test]
+fn render_module_lines_shows_swap_activity() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.swap_activity = Some(SwapActivity {
+        in_bytes_per_sec: 0,
+        out_bytes_per_sec: 245_760,
+    });
+
+    let lines = render_module_lines(&[ModuleKind::Swap], &snapshot, &settings);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1], "Swap activity:   in 0 B/s, out 240.0 KB/s");
+}
+
+#[test]
+fn render_quiet_line_joins_short_clauses_in_module_order() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let snapshot = sample_snapshot();
+
+    assert_eq!(
+        render_quiet_line(
+            &[
+                ModuleKind::Host,
+                ModuleKind::Uptime,
+                ModuleKind::Load,
+                ModuleKind::Os,
+                ModuleKind::Memory,
+                ModuleKind::Disk,
+            ],
+            &snapshot,
+            &settings,
+        ),
+        "prod-hpc-01 | up 24d18h | load 0.42 | mem 25% | disk 16%"
+    );
+    assert_eq!(
+        render_quiet_line(&[ModuleKind::Disk, ModuleKind::Host], &snapshot, &settings),
+        "disk 16% | prod-hpc-01"
+    );
+}
+
+#[test]
+fn check_report_names_failed_checks_and_picks_the_worst_status() {
+    let mut settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+
+    let report = CheckReport::from_snapshot(&snapshot, &settings);
+    assert_eq!(report.status(), CheckStatus::Warning);
+    assert_eq!(report.status().exit_code(), 1);
+    assert_eq!(
+        report.summary(),
+        "MOTDYN WARNING - disk /NFS 87% (warn 75%)"
+    );
+
+    settings.disk_no_alert = vec!["/NFS".to_string()];
+    let report = CheckReport::from_snapshot(&snapshot, &settings);
+    assert_eq!(report.status().exit_code(), 0);
+    assert_eq!(report.summary(), "MOTDYN OK - 4 checks within thresholds");
+
+    snapshot.load_average = "400.00 380.00 350.00".to_string();
+    snapshot.cpu_temperature = Some(80.0);
+    let report = CheckReport::from_snapshot(&snapshot, &settings);
+    assert_eq!(report.status().exit_code(), 2);
+    assert_eq!(
+        report.summary(),
+        "MOTDYN CRITICAL - load 400.00 on 192 cores (crit 2.00/core), temp 80.0°C (warn 75.0°C)"
+    );
+}
+
+#[test]
+fn render_module_lines_omits_swap_when_none_is_configured() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let (used_gb, total_gb, ratio) = to_gb_and_ratio(0, 0);
+    let mut snapshot = sample_snapshot();
+    snapshot.swap = UsageSummary {
+        used_gb,
+        total_gb,
+        ratio,
+    };
+    snapshot.swap_activity = Some(SwapActivity {
+        in_bytes_per_sec: 0,
+        out_bytes_per_sec: 0,
+    });
+
+    assert!(render_module_lines(&[ModuleKind::Swap], &snapshot, &settings).is_empty());
+}
+
+#[test]
+fn render_module_lines_shows_cpu_usage() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.cpu_usage = Some(42.25);
+
+    let lines = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert_eq!(lines.last().map(String::as_str), Some("CPU usage: 42.2%"));
+}
+
+#[test]
+fn parse_ip_addr_output_skips_loopback_and_flags_link_local() {
+    let output = "\
+1: lo    inet 127.0.0.1/8 scope host lo\\       valid_lft forever preferred_lft forever
+1: lo    inet6 ::1/128 scope host \\       valid_lft forever preferred_lft forever
+2: eth0    inet 10.0.0.5/24 brd 10.0.0.255 scope global dynamic eth0\\       valid_lft 86000sec
+2: eth0    inet6 2001:db8::5/64 scope global \\       valid_lft forever preferred_lft forever
+2: eth0    inet6 fe80::1/64 scope link \\       valid_lft forever preferred_lft forever
+5: wg0    inet 10.8.0.1/24 scope global wg0\\       valid_lft forever preferred_lft forever
+";
+    let address = |iface: &str, address: &str, link_local| InterfaceAddress {
+        iface: iface.to_string(),
+        address: address.to_string(),
+        link_local,
+    };
+
+    assert_eq!(
+        parse_ip_addr_output(output),
+        vec![
+            address("eth0", "10.0.0.5", false),
+            address("eth0", "2001:db8::5", false),
+            address("eth0", "fe80::1", true),
+            address("wg0", "10.8.0.1", false),
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_lists_addresses_per_interface() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.interface_addresses = parse_ip_addr_output(
+        "2: eth0 inet 10.0.0.5/24 scope global eth0\n\
+         2: eth0 inet6 fe80::1/64 scope link\n\
+         2: eth0 inet6 2001:db8::5/64 scope global\n\
+         3: eth1 inet6 fe80::2/64 scope link\n",
+    );
+
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Addresses], &snapshot, &settings),
+        vec!["IP addresses: eth0: 10.0.0.5, 2001:db8::5".to_string()]
+    );
+}
+
+#[test]
+fn parse_nvidia_smi_gpus_reads_each_device() {
+    let gpus = parse_nvidia_smi_gpus(
+        "NVIDIA A100-SXM4-80GB, 1229, 81920\nNVIDIA GeForce RTX 4090, [N/A], [N/A]\n\n",
+    );
+
+    assert_eq!(
+        gpus,
+        vec![
+            GpuInfo {
+                name: "NVIDIA A100-SXM4-80GB".to_string(),
+                memory_used_mib: Some(1229),
+                memory_total_mib: Some(81920),
+            },
+            GpuInfo {
+                name: "NVIDIA GeForce RTX 4090".to_string(),
+                memory_used_mib: None,
+                memory_total_mib: None,
+            },
+        ]
+    );
+    assert!(parse_nvidia_smi_gpus("No devices were found").is_empty());
+}
+
+#[test]
+fn render_module_lines_lists_one_line_per_gpu() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.gpus = vec![
+        GpuInfo {
+            name: "NVIDIA A100".to_string(),
+            memory_used_mib: Some(1229),
+            memory_total_mib: Some(81920),
+        },
+        GpuInfo {
+            name: "NVIDIA T4".to_string(),
+            memory_used_mib: None,
+            memory_total_mib: None,
+        },
+    ];
+
+    let lines = render_module_lines(&[ModuleKind::Cpu], &snapshot, &settings);
+    assert_eq!(
+        lines[lines.len() - 2..],
+        [
+            "GPU: NVIDIA A100 (1.20/80.00 GB VRAM)".to_string(),
+            "GPU: NVIDIA T4".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn parse_temperature_reads_millidegrees() {
+    assert_eq!(parse_temperature("47500\n"), Some(47.5));
+    assert_eq!(parse_temperature("-5000"), Some(-5.0));
+    assert_eq!(parse_temperature("-273150"), None);
+    assert_eq!(parse_temperature("N/A"), None);
+}
+
+#[test]
+fn read_cpu_temperature_prefers_the_cpu_package_zone() {
+    let dir = tempdir().unwrap();
+    let add = |path: &str, name_file: &str, name: &str, temp_file: &str, temp: &str| {
+        let path = dir.path().join(path);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join(name_file), name).unwrap();
+        fs::write(path.join(temp_file), temp).unwrap();
+    };
+    assert_eq!(read_cpu_temperature(dir.path()), None);
+
+    add(
+        "thermal/thermal_zone0",
+        "type",
+        "acpitz\n",
+        "temp",
+        "61000\n",
+    );
+    assert_eq!(read_cpu_temperature(dir.path()), Some(61.0));
+
+    add(
+        "thermal/thermal_zone1",
+        "type",
+        "x86_pkg_temp\n",
+        "temp",
+        "48000\n",
+    );
+    add("hwmon/hwmon0", "name", "nvme\n", "temp1_input", "70000\n");
+    add(
+        "hwmon/hwmon1",
+        "name",
+        "coretemp\n",
+        "temp1_input",
+        "52000\n",
+    );
+    assert_eq!(read_cpu_temperature(dir.path()), Some(52.0));
+}
+
+#[test]
+fn render_module_lines_shows_temperature_in_the_configured_unit() {
+    let mut settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    assert!(render_module_lines(&[ModuleKind::Temperature], &snapshot, &settings).is_empty());
+
+    snapshot.cpu_temperature = Some(48.25);
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Temperature], &snapshot, &settings),
+        vec!["Temperature: 48.2°C".to_string()]
+    );
+    settings.temperature_unit = TemperatureUnit::Fahrenheit;
+    assert_eq!(
+        render_module_lines(&[ModuleKind::Temperature], &snapshot, &settings),
+        vec!["Temperature: 118.8°F".to_string()]
+    );
+    assert_eq!(
+        threshold_paint_kind(91.0, 1, settings.thresholds.temperature),
+        Some(PaintKind::Red)
+    );
+}
+
+#[test]
+fn parse_ssh_connection_ip_normalizes_ipv6_clients() {
+    assert_eq!(
+        parse_ssh_connection_ip("2001:db8:0:0::1 54321 2001:db8::2 22"),
+        "2001:db8::1"
+    );
+    assert_eq!(
+        parse_ssh_connection_ip("[fe80::1%eth0] 54321 fe80::2 22"),
+        "fe80::1"
+    );
+    assert_eq!(
+        parse_ssh_connection_ip("::ffff:10.10.1.15 54321 ::ffff:10.10.1.1 22"),
+        "10.10.1.15"
+    );
+    assert_eq!(
+        parse_ssh_connection_ip("bastion 54321 10.0.0.1 22"),
+        "bastion"
+    );
+}
+
+#[test]
+fn parse_getent_hosts_name_takes_the_canonical_name() {
+    assert_eq!(
+        parse_getent_hosts_name(
+            "10.10.1.15      build-01.example.com build-01\n",
+            "10.10.1.15"
+        ),
+        Some("build-01.example.com".to_string())
+    );
+    assert_eq!(
+        parse_getent_hosts_name("10.10.1.15 10.10.1.15\n", "10.10.1.15"),
+        None
+    );
+    assert_eq!(parse_getent_hosts_name("", "10.10.1.15"), None);
+}
+
+#[test]
+fn render_module_lines_shows_resolved_host_and_local_console() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::from([HiddenField::LoginUserCount]),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.current_user = "admin".to_string();
+    snapshot.from_host = Some("build-01.example.com".to_string());
+    assert_eq!(
+        render_module_lines(&[ModuleKind::User], &snapshot, &settings),
+        vec!["User info: admin (from build-01.example.com [10.10.1.15])"]
+    );
+
+    snapshot.from_host = None;
+    snapshot.from_ip = "unknown".to_string();
+    assert_eq!(
+        render_module_lines(&[ModuleKind::User], &snapshot, &settings),
+        vec!["User info: admin (from local console)"]
+    );
+}
+
+#[test]
+fn parse_ssh_connection_ip_defaults_to_unknown_when_missing() {
+    assert_eq!(parse_ssh_connection_ip(""), "unknown");
+    assert_eq!(parse_ssh_connection_ip("   "), "unknown");
+    assert_eq!(
+        parse_ssh_connection_ip("192.168.2.2 54012 192.168.88.121 22"),
+        "192.168.2.2"
+    );
+}
+
+#[test]
+fn parse_default_interface_output_skips_malformed_lines() {
+    let output = "\
+default via 192.168.88.1 dev ens192 proto static\n\
+default dev broken\n";
+    assert_eq!(
+        parse_default_interface_output(output),
+        Some("ens192".to_string())
+    );
+    assert_eq!(parse_default_interface_output("default dev broken\n"), None);
+}
+
+#[test]
+fn parse_interface_ipv4_output_returns_none_without_inet_record() {
+    let valid = "2: ens192    inet 192.168.88.121/24 brd 192.168.88.255 scope global ens192\n";
+    let invalid = "2: ens192    mtu 1500 qdisc mq state UP mode DEFAULT group default qlen 1000\n";
+
+    assert_eq!(
+        parse_interface_ipv4_output(valid),
+        Some("192.168.88.121".to_string())
+    );
+    assert_eq!(parse_interface_ipv4_output(invalid), None);
+}
+
+#[test]
+fn disk_mount_label_follows_the_selected_mounts() {
+    let default = DiskMountSelection::RootAndNfs;
+    assert_eq!(
+        disk_mount_label("/dev/sda1", "/", "ext4", &default),
+        Some("Disk usage (root):")
+    );
+    assert_eq!(
+        disk_mount_label("nas:/export", "/NFS", "nfs4", &default),
+        Some("Disk usage (nfs):")
+    );
+    assert_eq!(
+        disk_mount_label("/dev/sdb1", "/home", "xfs", &default),
+        None
+    );
+
+    let all_local = DiskMountSelection::AllLocal;
+    assert_eq!(
+        disk_mount_label("/dev/sdb1", "/home", "xfs", &all_local),
+        Some("Disk usage (local):")
+    );
+    assert_eq!(disk_mount_label("tmpfs", "/run", "tmpfs", &all_local), None);
+    assert_eq!(
+        disk_mount_label("/dev/loop3", "/snap/core/1", "squashfs", &all_local),
+        None
+    );
+    assert_eq!(
+        disk_mount_label(
+            "overlay",
+            "/var/lib/docker/overlay2/x/merged",
+            "overlay",
+            &all_local
+        ),
+        None
+    );
+
+    let only = DiskMountSelection::Only(vec!["/home".to_string()]);
+    assert_eq!(disk_mount_label("/dev/sda1", "/", "ext4", &only), None);
+    assert_eq!(
+        disk_mount_label("/dev/sdb1", "/home", "xfs", &only),
+        Some("Disk usage (local):")
+    );
+}
+
+#[test]
+fn detect_virtualization_from_cgroup_detects_known_runtimes() {
+    assert_eq!(
+        detect_virtualization_from_cgroup("12:cpu:/docker/abcdef"),
+        Some("Docker".to_string())
+    );
+    assert_eq!(
+        detect_virtualization_from_cgroup("1:name=systemd:/lxc.payload/demo"),
+        Some("LXC".to_string())
+    );
+    assert_eq!(detect_virtualization_from_cgroup("0::/"), None);
+}
+
+#[test]
+fn render_module_lines_caps_disk_lines_to_the_fullest_mounts() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: Some(2),
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let disk = |path: &str, ratio: f64| DiskUsageItem {
+        label: "Disk usage (nfs):".to_string(),
+        mount_path: path.to_string(),
+        fstype: "nfs4".to_string(),
+        used_bytes: (ratio * 1024.0 * 1024.0 * 1024.0 / 100.0) as u64,
+        total_bytes: 1024 * 1024 * 1024,
+        ratio,
+        timed_out: false,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.disk_items = vec![
+        disk("/a", 10.0),
+        disk("/d", 90.0),
+        disk("/c", 50.0),
+        disk("/b", 90.0),
+    ];
+
+    let lines = render_module_lines(&[ModuleKind::Disk], &snapshot, &settings);
+    assert_eq!(
+        lines,
+        vec![
+            "Disk usage (nfs): /b  0.90 GB/1.00 GB (90.00%)".to_string(),
+            "Disk usage (nfs): /d  0.90 GB/1.00 GB (90.00%)".to_string(),
+            "                  (+2 more mounts)".to_string(),
+        ]
+    );
+
+    snapshot.disk_items.truncate(2);
+    let lines = render_module_lines(&[ModuleKind::Disk], &snapshot, &settings);
+    assert_eq!(
+        lines,
+        vec![
+            "Disk usage (nfs): /a  0.10 GB/1.00 GB (10.00%)".to_string(),
+            "Disk usage (nfs): /d  0.90 GB/1.00 GB (90.00%)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_marks_timed_out_mounts() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.disk_items[1].used_bytes = 0;
+    snapshot.disk_items[1].total_bytes = 0;
+    snapshot.disk_items[1].ratio = 0.0;
+    snapshot.disk_items[1].timed_out = true;
+
+    let lines = render_module_lines(&[ModuleKind::Disk], &snapshot, &settings);
+    assert_eq!(
+        lines,
+        vec![
+            "Disk usage (root): /     1.20 TB/7.68 TB (15.62%)".to_string(),
+            "Disk usage (nfs):  /NFS  (timed out)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn run_with_deadline_abandons_jobs_that_outlive_the_timeout() {
+    let jobs: Vec<Box<dyn FnOnce() -> u32 + Send>> = vec![
+        Box::new(|| 1),
+        Box::new(|| {
+            thread::sleep(Duration::from_secs(5));
+            2
+        }),
+        Box::new(|| 3),
+    ];
+
+    let results = run_with_deadline(jobs, Duration::from_millis(200));
+    assert_eq!(results, vec![Some(1), None, Some(3)]);
+}
+
+#[test]
+fn render_module_lines_aligns_disk_usage_in_a_column() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::Disk], &sample_snapshot(), &settings);
+    assert_eq!(
+        lines,
+        vec![
+            "Disk usage (root): /     1.20 TB/7.68 TB (15.62%)".to_string(),
+            "Disk usage (nfs):  /NFS  1.72 TB/1.97 TB (87.31%)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_substitutes_disk_labels_for_mount_paths() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::from([("/NFS".to_string(), "Backups".to_string())]),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::Disk], &sample_snapshot(), &settings);
+    assert_eq!(
+        lines,
+        vec![
+            "Disk usage (root): /        1.20 TB/7.68 TB (15.62%)".to_string(),
+            "Disk usage (nfs):  Backups  1.72 TB/1.97 TB (87.31%)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn render_module_lines_frames_usage_per_disk_display() {
+    let mut settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::FreeTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.memory = UsageSummary {
+        used_gb: 3.2,
+        total_gb: 16.0,
+        ratio: 20.0,
+    };
+    snapshot.disk_items.truncate(1);
+
+    let render = |module, settings: &OutputSettings| {
+        render_module_lines(&[module], &snapshot, settings).join("\n")
+    };
+
+    assert_eq!(
+        render(ModuleKind::Memory, &settings),
+        "Memory free/total: 12.80 GB free of 16.00 GB"
+    );
+    assert_eq!(
+        render(ModuleKind::Disk, &settings),
+        "Disk usage (root): /  6.48 TB free of 7.68 TB"
+    );
+
+    settings.disk_display = UsageDisplay::UsedFree;
+    assert_eq!(
+        render(ModuleKind::Memory, &settings),
+        "Memory used/free: 3.20 GB used, 12.80 GB free"
+    );
+    assert_eq!(
+        render(ModuleKind::Disk, &settings),
+        "Disk usage (root): /  1.20 TB used, 6.48 TB free"
+    );
+}
+
+#[test]
+fn render_module_lines_applies_hidden_fields_and_plain_output() {
+    let mut hidden = HashSet::new();
+    hidden.insert(HiddenField::SourceIp);
+    hidden.insert(HiddenField::Timezone);
+    hidden.insert(HiddenField::NfsDisks);
+    let settings = OutputSettings {
+        compact: true,
+        plain: true,
+        section_headers: false,
+        hidden_fields: hidden,
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(
+        &[
+            ModuleKind::User,
+            ModuleKind::Time,
+            ModuleKind::Disk,
+            ModuleKind::Virtualization,
+        ],
+        &sample_snapshot(),
+        &settings,
+    );
+    let rendered = lines.join("\n");
+
+    assert!(rendered.contains("admin, 4 user(s) logged in"));
+    assert!(!rendered.contains("10.10.1.15"));
+    assert!(rendered.contains("2026-01-15 09:30:00"));
+    assert!(!rendered.contains("+00:00"));
+    assert!(rendered.contains("Storage:"));
+    assert!(rendered.contains("root 1.20 TB/7.68 TB (15.62%)"));
+    assert!(!rendered.contains("Disk usage (nfs):"));
+    assert!(!rendered.contains('\u{1b}'));
+    assert!(lines.len() <= 4);
+}
+
+#[test]
+fn render_module_lines_reports_certificate_expiry() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::Certificates], &sample_snapshot(), &settings);
+
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("web cert:") && line.contains("9 days left"))
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("api cert:") && line.contains("120 days left"))
+    );
+}
+
+#[test]
+fn render_module_lines_reports_fd_usage_against_limit() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::FdUsage], &sample_snapshot(), &settings);
+
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("nginx fds:") && line.contains("3900/4096"))
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.ends_with("3900/4096, 212 connections"))
+    );
+}
+
+#[test]
+fn parse_proc_limits_nofile_reads_soft_limit() {
+    let content = "\
+Limit                     Soft Limit           Hard Limit           Units\n\
+Max cpu time              unlimited            unlimited            seconds\n\
+Max open files            1024                 524288               files\n";
+
+    assert_eq!(parse_proc_limits_nofile(content), Some(1024));
+    assert_eq!(
+        parse_proc_limits_nofile(
+            "Max open files            unlimited            unlimited            files\n"
+        ),
+        None
+    );
+}
+
+#[test]
+fn find_pid_by_comm_picks_lowest_matching_pid() {
+    let dir = tempdir().unwrap();
+    for (pid, comm) in [("412", "nginx\n"), ("77", "nginx\n"), ("12", "sshd\n")] {
+        fs::create_dir(dir.path().join(pid)).unwrap();
+        fs::write(dir.path().join(pid).join("comm"), comm).unwrap();
+    }
+    fs::create_dir(dir.path().join("self")).unwrap();
+
+    assert_eq!(find_pid_by_comm(dir.path(), "nginx"), Some(77));
+    assert_eq!(find_pid_by_comm(dir.path(), "postgres"), None);
+}
+
+#[test]
+fn find_pid_by_comm_matches_names_the_kernel_truncates() {
+    let dir = tempdir().unwrap();
+    for (pid, comm) in [("530", "systemd-resolve\n"), ("914", "containerd-shim\n")] {
+        fs::create_dir(dir.path().join(pid)).unwrap();
+        fs::write(dir.path().join(pid).join("comm"), comm).unwrap();
+    }
+
+    assert_eq!(find_pid_by_comm(dir.path(), "systemd-resolved"), Some(530));
+    assert_eq!(
+        find_pid_by_comm(dir.path(), "containerd-shim-runc-v2"),
+        Some(914)
+    );
+    assert_eq!(find_pid_by_comm(dir.path(), "systemd-resolv"), None);
+}
+
+#[test]
+fn count_established_connections_matches_the_process_sockets() {
+    let content = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+   0: 00000000:0050 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1001 1 0\n\
+   1: 0100007F:0050 0100007F:A1B2 01 00000000:00000000 00:00000000 00000000     0        0 1002 1 0\n\
+   2: 0100007F:0050 0100007F:A1B3 01 00000000:00000000 00:00000000 00000000     0        0 1003 1 0\n\
+   3: 0100007F:1F90 0100007F:A1B4 01 00000000:00000000 00:00000000 00000000     0        0 2001 1 0\n";
+    let inodes = HashSet::from([1001, 1002, 1003]);
+
+    assert_eq!(count_established_connections(content, &inodes), 2);
+    assert_eq!(count_established_connections(content, &HashSet::new()), 0);
+}
+
+#[test]
+fn record_recent_login_returns_previous_sessions_and_trims_state() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("state").join("recent_logins");
+    let login = |when_secs: u64, from: &str| RecentLogin {
+        when_secs,
+        user: "admin".to_string(),
+        from: from.to_string(),
+    };
+
+    assert!(
+        record_recent_login(&path, 2, login(1, "10.0.0.1"))
+            .unwrap()
+            .is_empty()
+    );
+    record_recent_login(&path, 2, login(2, "10.0.0.2")).unwrap();
+    record_recent_login(&path, 2, login(3, "unknown")).unwrap();
+    let previous = record_recent_login(&path, 2, login(4, "10.0.0.4")).unwrap();
+
+    assert_eq!(previous, vec![login(3, "unknown"), login(2, "10.0.0.2")]);
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+    let state = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        read_recent_logins(&path, 2).unwrap(),
+        vec![login(4, "10.0.0.4"), login(3, "unknown")]
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), state);
+    assert!(
+        read_recent_logins(&dir.path().join("missing"), 2)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn render_module_lines_lists_recent_logins() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(&[ModuleKind::RecentLogins], &sample_snapshot(), &settings);
+
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("Recent logins: admin from 10.10.1.15 at "));
+}
+
+#[test]
+fn render_module_lines_shows_available_memory() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+
+    let lines = render_module_lines(
+        &[ModuleKind::MemoryAvailable],
+        &sample_snapshot(),
+        &settings,
+    );
+
+    assert_eq!(
+        lines,
+        vec!["Memory available: 1.12 TB of 1.50 TB".to_string()]
+    );
+}
+
+#[test]
+fn rust_log_enables_debug_only_for_motdyn_directives() {
+    assert!(rust_log_enables_debug("motdyn=debug"));
+    assert!(rust_log_enables_debug("warn,motdyn=trace"));
+    assert!(rust_log_enables_debug("motdyn"));
+    assert!(rust_log_enables_debug("motdyn::probe=DEBUG"));
+    assert!(!rust_log_enables_debug("debug"));
+    assert!(!rust_log_enables_debug("trace"));
+    assert!(!rust_log_enables_debug("info"));
+    assert!(!rust_log_enables_debug("hyper=debug"));
+    assert!(!rust_log_enables_debug("motdyn_agent=debug"));
+    assert!(!rust_log_enables_debug("motdyn=info"));
+}
+
+#[test]
+fn parse_certificate_not_after_reads_pem_and_der() {
+    let pem = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIBgDCCASegAwIBAgIUOxWFnPg6BuNG18aIliGJ3ZQL7e4wCgYIKoZIzj0EAwIw\n\
+FjEUMBIGA1UEAwwLbW90ZHluLnRlc3QwHhcNMjUwMTAxMDAwMDAwWhcNMzAwNjAx\n\
+MTIwMDAwWjAWMRQwEgYDVQQDDAttb3RkeW4udGVzdDBZMBMGByqGSM49AgEGCCqG\n\
+SM49AwEHA0IABLROMFujZio4czUyqzNVO8rOEF3kdkoAwlQ/fxCs2xrBu8rhEOcR\n\
+Bq8D3+hXXj0RWytCfvkGmlwPmKmoATlDLbijUzBRMB0GA1UdDgQWBBQ5MZGRPYo+\n\
+Wav20KycmeFbEvZpPTAfBgNVHSMEGDAWgBQ5MZGRPYo+Wav20KycmeFbEvZpPTAP\n\
+BgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0cAMEQCIGiSN2ommjINee2mfOcZ\n\
+XgRBm4FR4jzl11tJuJNw5RwaAiAb6o9sW69PfX/opm4GmVf/FPJqQons1TuZ7BIf\n\
+xGfULg==\n\
+-----END CERTIFICATE-----\n\
+";
+    let expected = Utc.with_ymd_and_hms(2030, 6, 1, 12, 0, 0).unwrap();
+
+    assert_eq!(
+        parse_certificate_not_after(pem.as_bytes()).unwrap(),
+        expected
+    );
+    let der = base64::engine::general_purpose::STANDARD
+        .decode(
+            pem.lines()
+                .filter(|line| !line.starts_with("-----"))
+                .collect::<String>(),
+        )
+        .unwrap();
+    assert_eq!(parse_certificate_not_after(&der).unwrap(), expected);
+    assert!(parse_certificate_not_after(b"not a certificate").is_err());
+}
+
+#[test]
+fn collect_health_alerts_lists_only_critical_items() {
+    let mut settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    assert_eq!(
+        collect_health_alerts(&snapshot, &settings),
+        vec!["nginx is at 3900/4096 fds".to_string()]
+    );
+
+    snapshot.disk_items[0].ratio = 95.4;
+    snapshot.memory.ratio = 97.0;
+    snapshot.fd_usages.clear();
+    assert_eq!(
+        collect_health_alerts(&snapshot, &settings),
+        vec![
+            "/ is 95% full".to_string(),
+            "memory is 97% used".to_string()
+        ]
+    );
+
+    settings.disk_no_alert = vec!["/".to_string()];
+    assert_eq!(
+        collect_health_alerts(&snapshot, &settings),
+        vec!["memory is 97% used".to_string()]
+    );
+
+    snapshot.disk_items[0].ratio = 15.62;
+    snapshot.memory.ratio = 25.0;
+    assert!(collect_health_alerts(&snapshot, &settings).is_empty());
+}
+
+#[test]
+fn combined_usage_bar_skips_status_for_no_alert_root() {
+    let mut settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
+    };
+    let mut snapshot = sample_snapshot();
+    snapshot.root_disk = Some(UsageSummary {
+        used_gb: 96.0,
+        total_gb: 100.0,
+        ratio: 96.0,
+    });
+    let modules = [ModuleKind::Memory, ModuleKind::Disk];
+
+    let lines = render_module_lines(&modules, &snapshot, &settings);
+    assert!(lines[0].contains("disk  96% critical"), "{:?}", lines);
+
+    settings.disk_no_alert = vec!["/".to_string()];
+    let lines = render_module_lines(&modules, &snapshot, &settings);
+    assert!(lines[0].contains("disk  96% #"), "{:?}", lines);
 }
 
 #[test]
-fn parse_lastb_output_summarizes_recent_failures() {
-    let now = Local.with_ymd_and_hms(2026, 3, 30, 12, 0, 0).unwrap();
-    let output = "\
-admin    ssh:notty   10.10.1.20   Thu Mar 30 09:25 - 09:25  (00:00)\n\
-admin    ssh:notty   10.10.1.21   Thu Mar 30 09:20 - 09:20  (00:00)\n\
-btmp begins Thu Mar 30 00:00:00 2026\n";
+fn active_scheduled_banners_shows_overlapping_windows_and_skips_malformed_entries() {
+    let banner = |start: &str, end: &str, message: &str| ScheduledBannerConfig {
+        start: start.to_string(),
+        end: end.to_string(),
+        message: message.to_string(),
+    };
+    let banners = vec![
+        banner("2026-05-01", "2026-05-03", "Storage maintenance this week"),
+        banner(
+            "2026-05-02 08:00",
+            "2026-05-02T18:00",
+            "Network cutover today",
+        ),
+        banner("2026-05-03", "2026-05-04", "Not yet"),
+        banner("05/01/2026", "2026-05-04", "Bad start"),
+        banner("2026-05-04", "2026-05-01", "Backwards"),
+    ];
+    let now = Local
+        .with_ymd_and_hms(2026, 5, 2, 12, 0, 0)
+        .unwrap()
+        .naive_local();
 
-    let events = parse_lastb_output(output, now).unwrap();
+    let active = active_scheduled_banners(&banners, now);
     assert_eq!(
-        events,
+        active.messages,
         vec![
-            FailedLoginEvent {
-                when: Some(Local.with_ymd_and_hms(2026, 3, 30, 9, 25, 0).unwrap()),
-                when_raw: "Thu Mar 30 09:25".to_string(),
-                from: Some("10.10.1.20".to_string()),
-                via: Some("ssh:notty".to_string()),
-            },
-            FailedLoginEvent {
-                when: Some(Local.with_ymd_and_hms(2026, 3, 30, 9, 20, 0).unwrap()),
-                when_raw: "Thu Mar 30 09:20".to_string(),
-                from: Some("10.10.1.21".to_string()),
-                via: Some("ssh:notty".to_string()),
-            }
+            "Storage maintenance this week".to_string(),
+            "Network cutover today".to_string(),
         ]
     );
-
     assert_eq!(
-        summarize_failed_login_events(&events, "10.10.1.20", now),
-        FailedLoginInfo::Summary(super::types::FailedLoginSummary {
-            total: 2,
-            count_24h: 2,
-            count_7d: 2,
-            last_when: Some("Thu Mar 30 09:25".to_string()),
-            last_from: Some("10.10.1.20".to_string()),
-            last_via: Some("ssh:notty".to_string()),
-            top_sources: vec![
-                FailedLoginBucket {
-                    value: "10.10.1.20".to_string(),
-                    count: 1,
-                },
-                FailedLoginBucket {
-                    value: "10.10.1.21".to_string(),
-                    count: 1,
-                },
-            ],
-            top_vias: vec![FailedLoginBucket {
-                value: "ssh:notty".to_string(),
-                count: 2,
-            }],
-            unique_sources: 2,
-            severity: FailedLoginSeverity::High,
-            current_source_seen: true,
-        })
+        active.skipped,
+        vec![
+            "scheduled_banners[3]: invalid start '05/01/2026'".to_string(),
+            "scheduled_banners[4]: end is before start".to_string(),
+        ]
     );
-}
-
-#[test]
-fn parse_package_update_outputs_count_packages() {
-    let apt_output = "\
-Listing...\n\
-bash/stable 5.2 amd64 [upgradable from: 5.1]\n\
-curl/stable 8.7 amd64 [upgradable from: 8.6]\n";
-    let dnf_output = "\
-Last metadata expiration check: 0:42:11 ago on Thu 30 Mar 2026 09:00:00 AM UTC.\n\
-bash.x86_64          5.2-1.el9          baseos\n\
-curl.x86_64          8.7-1.el9          appstream\n";
 
-    assert_eq!(parse_apt_upgradable_output(apt_output), 2);
-    assert_eq!(parse_dnf_check_update_output(dnf_output), 2);
+    let last_second = Local
+        .with_ymd_and_hms(2026, 5, 3, 23, 59, 59)
+        .unwrap()
+        .naive_local();
+    assert_eq!(
+        active_scheduled_banners(&banners[..1], last_second)
+            .messages
+            .len(),
+        1
+    );
 }
 
 #[test]
-fn parse_meminfo_content_falls_back_to_memfree_when_memavailable_missing() {
-    let content = "\
-MemTotal:       16384 kB\n\
-MemFree:         4096 kB\n\
-SwapTotal:       2048 kB\n\
-SwapFree:        1024 kB\n";
+fn metrics_report_serializes_to_toml_with_disk_tables() {
+    let mut snapshot = sample_snapshot();
+    snapshot.host_name = "prod \"hpc\" 01".to_string();
+    snapshot.virt_info = None;
+    let toml = SystemMetrics::from_snapshot(&snapshot).to_toml().unwrap();
 
-    assert_eq!(parse_meminfo_content(content), (16384, 4096, 2048, 1024));
+    assert!(toml.starts_with("host_name = \"prod \\\"hpc\\\" 01\"\n"));
+    assert!(toml.contains("uptime_secs = 2141331\n"));
+    assert!(toml.contains("load_average = [0.42, 0.38, 0.35]\n"));
+    assert!(toml.contains("\n[cpu]\nbrand = \"2x AMD EPYC 9654\"\ncores = 192\nonline = 192\n"));
+    assert!(toml.contains("\n[memory]\nfree_bytes = 1236950581248\npercent = 25.0\n"));
+    assert!(toml.contains(
+        "\n[[disks]]\nfstype = \"nfs4\"\nmount = \"/NFS\"\npercent = 87.31\ntotal_bytes = 2166026912645\nused_bytes = 1891136999915\n"
+    ));
+    assert!(!toml.contains("virtualization"));
+    assert!(toml::from_str::<toml::Table>(&toml).is_ok());
 }
 
 #[test]
-fn parse_meminfo_content_tolerates_invalid_values() {
-    let content = "\
-MemTotal:       nope kB\n\
-MemAvailable:   2048 kB\n\
-SwapTotal:      broken kB\n\
-SwapFree:       1024 kB\n";
+fn metrics_report_serializes_to_json_with_raw_numbers() {
+    let json = SystemMetrics::from_snapshot(&sample_snapshot())
+        .to_json(JsonStyle::Raw)
+        .unwrap();
+    let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
 
-    assert_eq!(parse_meminfo_content(content), (0, 2048, 0, 1024));
+    assert_eq!(value["uptime_secs"], 2_141_331);
+    assert_eq!(value["cpu"]["cores"], 192);
+    assert_eq!(
+        value["memory"]["free_bytes"].as_u64().unwrap()
+            + value["memory"]["used_bytes"].as_u64().unwrap(),
+        value["memory"]["total_bytes"].as_u64().unwrap()
+    );
+    assert_eq!(
+        value["disks"][1],
+        serde_json::json!({
+            "mount": "/NFS",
+            "fstype": "nfs4",
+            "used_bytes": 1_891_136_999_915_u64,
+            "total_bytes": 2_166_026_912_645_u64,
+            "percent": 87.31,
+        })
+    );
 }
 
 #[test]
-fn parse_cpuinfo_content_detects_arm_fallback_brand() {
-    let content = "\
-processor\t: 0\n\
-processor\t: 1\n\
-CPU implementer\t: 0x41\n\
-CPU part\t: 0xd08\n";
+fn metrics_report_serializes_human_json_as_display_strings() {
+    let json = SystemMetrics::from_snapshot(&sample_snapshot())
+        .to_json(JsonStyle::Human)
+        .unwrap();
+    let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
 
+    assert_eq!(value["uptime"], "24 days, 18:48:51");
+    assert!(value.get("uptime_secs").is_none());
+    assert_eq!(value["load_average"], "0.42 0.38 0.35");
+    assert_eq!(value["cpu"]["cores"], 192);
+    assert_eq!(value["memory"]["percent"], "25.00%");
+    assert!(value["memory"].get("used_bytes").is_none());
     assert_eq!(
-        parse_cpuinfo_content(content),
-        ("ARM Cortex-A72".to_string(), 2)
+        value["disks"][1],
+        serde_json::json!({
+            "mount": "/NFS",
+            "fstype": "nfs4",
+            "used": "1.7 TB",
+            "total": "2.0 TB",
+            "percent": "87.31%",
+        })
     );
 }
 
 #[test]
-fn parse_ssh_connection_ip_defaults_to_unknown_when_missing() {
-    assert_eq!(parse_ssh_connection_ip(""), "unknown");
-    assert_eq!(parse_ssh_connection_ip("   "), "unknown");
+fn paste_safe_line_expands_tabs_and_strips_escapes() {
     assert_eq!(
-        parse_ssh_connection_ip("192.168.2.2 54012 192.168.88.121 22"),
-        "192.168.2.2"
+        paste_safe_line("\u{1b}[1;32mHost:\u{1b}[0m\tprod-01 \t "),
+        "Host:   prod-01"
     );
+    assert_eq!(paste_safe_line("a\tb"), "a       b");
 }
 
 #[test]
-fn parse_default_interface_output_skips_malformed_lines() {
-    let output = "\
-default via 192.168.88.1 dev ens192 proto static\n\
-default dev broken\n";
-    assert_eq!(
-        parse_default_interface_output(output),
-        Some("ens192".to_string())
+fn paste_safe_line_turns_alerts_and_bars_into_ascii() {
+    let mut settings = resolve_output_settings(&MotdConfig::default());
+    settings.plain = false;
+    settings.progress_bar_width = Some(10);
+    settings.line_width = Some(40);
+    let mut snapshot = sample_snapshot();
+    snapshot.disk_items[0].ratio = 95.4;
+    snapshot.memory.ratio = 97.0;
+
+    let mut lines = render_module_lines(
+        &[ModuleKind::Host, ModuleKind::Memory, ModuleKind::Disk],
+        &snapshot,
+        &settings,
     );
-    assert_eq!(parse_default_interface_output("default dev broken\n"), None);
+    lines.push(super::format_alert_farewell(&collect_health_alerts(
+        &snapshot, &settings,
+    )));
+    let raw = lines.join("\n");
+    assert!(raw.contains('⚠'));
+    assert!(raw.contains('█'));
+    assert!(raw.contains('…'));
+
+    let safe = lines
+        .iter()
+        .map(|line| paste_safe_line(line))
+        .collect::<Vec<_>>();
+    assert!(safe.iter().all(|line| line.is_ascii()), "{safe:#?}");
+    assert!(
+        safe.iter()
+            .any(|line| line.starts_with("! Attention needed:"))
+    );
+    assert!(safe.iter().any(|line| line.contains("[#########-]")));
+    assert!(safe.iter().any(|line| line.ends_with("...")));
+    assert_eq!(paste_safe_line("─── 23.5°C ●"), "--- 23.5C *");
 }
 
 #[test]
-fn parse_interface_ipv4_output_returns_none_without_inet_record() {
-    let valid = "2: ens192    inet 192.168.88.121/24 brd 192.168.88.255 scope global ens192\n";
-    let invalid = "2: ens192    mtu 1500 qdisc mq state UP mode DEFAULT group default qlen 1000\n";
+fn visible_width_counts_wide_characters_and_skips_escapes() {
+    assert_eq!(visible_width("Host:"), 5);
+    assert_eq!(visible_width("主机名:"), 7);
+    assert_eq!(visible_width("🖥 Host:"), 8);
+    assert_eq!(visible_width("\u{1b}[1;32m内存:\u{1b}[0m"), 5);
+    assert_eq!(visible_width("e\u{301}"), 1);
+}
 
-    assert_eq!(
-        parse_interface_ipv4_output(valid),
-        Some("192.168.88.121".to_string())
-    );
-    assert_eq!(parse_interface_ipv4_output(invalid), None);
+#[test]
+fn visible_width_counts_fullwidth_latin_labels() {
+    assert_eq!(visible_width("ＣＰＵ:"), 7);
+    assert_eq!(visible_width("ＨＯＳＴ: up"), 12);
+    assert_eq!(visible_width("ｶﾅ"), 2);
 }
 
 #[test]
-fn detect_virtualization_from_cgroup_detects_known_runtimes() {
-    assert_eq!(
-        detect_virtualization_from_cgroup("12:cpu:/docker/abcdef"),
-        Some("Docker".to_string())
-    );
-    assert_eq!(
-        detect_virtualization_from_cgroup("1:name=systemd:/lxc.payload/demo"),
-        Some("LXC".to_string())
-    );
-    assert_eq!(detect_virtualization_from_cgroup("0::/"), None);
+fn visible_width_keeps_emoji_sequences_two_columns_wide() {
+    assert_eq!(visible_width("👍🏽 OK"), 5);
+    assert_eq!(visible_width("🖥\u{fe0f}"), 2);
+    assert_eq!(visible_width("👩\u{200d}💻 Dev"), 6);
+    assert_eq!(visible_width("🇯🇵"), 2);
 }
 
 #[test]
-fn render_module_lines_applies_hidden_fields_and_plain_output() {
-    let mut hidden = HashSet::new();
-    hidden.insert(HiddenField::SourceIp);
-    hidden.insert(HiddenField::Timezone);
-    hidden.insert(HiddenField::NfsDisks);
+fn format_aligned_items_aligns_cjk_and_emoji_labels() {
     let settings = OutputSettings {
-        compact: true,
+        compact: false,
         plain: true,
         section_headers: false,
-        hidden_fields: hidden,
+        hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
+    let items = [
+        RenderedItem {
+            label: "主机名:".to_string(),
+            value: "prod-01".to_string(),
+        },
+        RenderedItem {
+            label: "🧠 Memory:".to_string(),
+            value: "4 GB".to_string(),
+        },
+        RenderedItem {
+            label: "OS:".to_string(),
+            value: "Debian".to_string(),
+        },
+    ];
 
-    let lines = render_module_lines(
-        &[
-            ModuleKind::User,
-            ModuleKind::Time,
-            ModuleKind::Disk,
-            ModuleKind::Virtualization,
-        ],
-        &sample_snapshot(),
-        &settings,
-    );
-    let rendered = lines.join("\n");
+    let lines = format_aligned_items(&items, &settings);
+    let value_columns = lines
+        .iter()
+        .zip(["prod-01", "4 GB", "Debian"])
+        .map(|(line, value)| visible_width(&line[..line.find(value).unwrap()]))
+        .collect::<Vec<_>>();
 
-    assert!(rendered.contains("admin, 4 user(s) logged in"));
-    assert!(!rendered.contains("10.10.1.15"));
-    assert!(rendered.contains("2026-01-15 09:30:00"));
-    assert!(!rendered.contains("+00:00"));
-    assert!(rendered.contains("Storage:"));
-    assert!(rendered.contains("root 1.20 TB/7.68 TB (15.62%)"));
-    assert!(!rendered.contains("Disk usage (nfs):"));
-    assert!(!rendered.contains('\u{1b}'));
-    assert!(lines.len() <= 4);
+    assert_eq!(value_columns, vec![11, 11, 11]);
 }
 
 #[test]
@@ -502,6 +4389,19 @@ fn render_module_lines_inserts_section_headers_when_enabled() {
         section_headers: true,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
 
     let lines = render_module_lines(
@@ -516,6 +4416,45 @@ fn render_module_lines_inserts_section_headers_when_enabled() {
     assert!(lines.iter().any(|line| line.is_empty()));
 }
 
+#[test]
+fn render_module_lines_puts_separator_before_storage_and_keeps_alignment() {
+    let settings = OutputSettings {
+        compact: false,
+        plain: true,
+        section_headers: false,
+        hidden_fields: HashSet::new(),
+        ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: Some("----".to_string()),
+        environment: None,
+    };
+    let snapshot = sample_snapshot();
+
+    let lines = render_module_lines(&[ModuleKind::Host, ModuleKind::Disk], &snapshot, &settings);
+    assert_eq!(
+        lines,
+        vec![
+            "Host name:         prod-hpc-01",
+            "----",
+            "Disk usage (root): /     1.20 TB/7.68 TB (15.62%)",
+            "Disk usage (nfs):  /NFS  1.72 TB/1.97 TB (87.31%)",
+        ]
+    );
+
+    let lines = render_module_lines(&[ModuleKind::Disk, ModuleKind::Host], &snapshot, &settings);
+    assert!(!lines.iter().any(|line| line == "----"));
+}
+
 #[test]
 fn render_module_lines_compact_layout_packs_multiple_items_per_line() {
     let settings = OutputSettings {
@@ -524,6 +4463,19 @@ fn render_module_lines_compact_layout_packs_multiple_items_per_line() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
 
     let lines = render_module_lines(
@@ -556,6 +4508,19 @@ fn render_module_lines_compact_summarizes_login_security_modules() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
 
     let lines = render_module_lines(
@@ -578,6 +4543,19 @@ fn render_module_lines_full_keeps_failed_login_summary_concise() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
 
     let lines = render_module_lines(&[ModuleKind::FailedLogin], &sample_snapshot(), &settings);
@@ -596,6 +4574,19 @@ fn render_module_lines_full_combines_memory_and_root_disk_usage() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
 
     let lines = render_module_lines(
@@ -620,6 +4611,19 @@ fn render_module_lines_full_shortens_last_login_and_hides_empty_swap() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
 
     let mut snapshot = sample_snapshot();
@@ -650,6 +4654,19 @@ fn render_module_lines_marks_critical_root_disk_usage() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
     let mut snapshot = sample_snapshot();
     snapshot.root_disk = Some(UsageSummary {
@@ -685,6 +4702,19 @@ fn build_verbose_items_reports_degraded_modules_and_ignored_fields() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: vec!["bogus".to_string()],
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
     let items = build_verbose_items(
         &MotdConfig::default(),
@@ -693,7 +4723,11 @@ fn build_verbose_items_reports_degraded_modules_and_ignored_fields() {
             system_config_status: "missing".into(),
             user_config_path: "/root/.config/motdyn/config.toml".into(),
             user_config_status: "missing".into(),
+            env_overrides: Vec::new(),
+            config_origins: Vec::new(),
             config_notes: Vec::new(),
+            session: "ssh".into(),
+            mode: RenderMode::Login,
         },
         &ModuleSelection {
             modules: default_modules(),
@@ -708,6 +4742,7 @@ fn build_verbose_items_reports_degraded_modules_and_ignored_fields() {
             settings: resolve_remote_welcome_settings(&MotdConfig::default()),
             warnings: Vec::new(),
         },
+        &ScheduledBanners::default(),
         &snapshot,
         &output,
     );
@@ -729,6 +4764,58 @@ fn build_verbose_items_reports_degraded_modules_and_ignored_fields() {
     );
 }
 
+/// Verbose items for `snapshot` under the default config and plain output.
+fn default_verbose_items(snapshot: &SystemSnapshot) -> Vec<RenderedItem> {
+    let mut output = resolve_output_settings(&MotdConfig::default());
+    output.plain = true;
+    build_verbose_items(
+        &MotdConfig::default(),
+        &RenderContext {
+            system_config_path: "/etc/motdyn/config.toml".into(),
+            system_config_status: "missing".into(),
+            user_config_path: "/root/.config/motdyn/config.toml".into(),
+            user_config_status: "missing".into(),
+            env_overrides: Vec::new(),
+            config_origins: Vec::new(),
+            config_notes: Vec::new(),
+            session: "ssh".into(),
+            mode: RenderMode::Login,
+        },
+        &ModuleSelection {
+            modules: default_modules(),
+            ignored: Vec::new(),
+            source: ModuleSource::Default,
+        },
+        &WelcomeResolution {
+            text: DEFAULT_WELCOME.to_string(),
+            source: WelcomeSource::Default,
+            source_detail: "default welcome".to_string(),
+            url: None,
+            settings: resolve_remote_welcome_settings(&MotdConfig::default()),
+            warnings: Vec::new(),
+        },
+        &ScheduledBanners::default(),
+        snapshot,
+        &output,
+    )
+}
+
+#[test]
+fn build_verbose_items_reports_kernel_preemption() {
+    let mut snapshot = sample_snapshot();
+    snapshot.kernel_preemption = Some(KernelPreemption {
+        realtime: true,
+        tick_hz: Some(1000),
+    });
+
+    let items = default_verbose_items(&snapshot);
+    assert!(
+        items
+            .iter()
+            .any(|item| item.label == "Kernel:" && item.value == "PREEMPT_RT, 1000 Hz tick")
+    );
+}
+
 #[test]
 fn build_verbose_items_includes_login_security_details() {
     let output = OutputSettings {
@@ -737,6 +4824,19 @@ fn build_verbose_items_includes_login_security_details() {
         section_headers: false,
         hidden_fields: HashSet::new(),
         ignored_hidden_fields: Vec::new(),
+        max_disk_lines: None,
+        disk_display: UsageDisplay::UsedTotal,
+        display_hostname: None,
+        disk_no_alert: Vec::new(),
+        disk_labels: BTreeMap::new(),
+        colors: HashMap::new(),
+        ignored_colors: Vec::new(),
+        thresholds: UsageThresholds::default(),
+        progress_bar_width: None,
+        line_width: None,
+        temperature_unit: TemperatureUnit::Celsius,
+        separator: None,
+        environment: None,
     };
     let snapshot = sample_snapshot();
     let items = build_verbose_items(
@@ -749,7 +4849,11 @@ fn build_verbose_items_includes_login_security_details() {
             system_config_status: "missing".into(),
             user_config_path: "/root/.config/motdyn/config.toml".into(),
             user_config_status: "missing".into(),
+            env_overrides: Vec::new(),
+            config_origins: Vec::new(),
             config_notes: Vec::new(),
+            session: "ssh".into(),
+            mode: RenderMode::Login,
         },
         &ModuleSelection {
             modules: vec![ModuleKind::LastLogin, ModuleKind::FailedLogin],
@@ -764,6 +4868,7 @@ fn build_verbose_items_includes_login_security_details() {
             settings: resolve_remote_welcome_settings(&MotdConfig::default()),
             warnings: Vec::new(),
         },
+        &ScheduledBanners::default(),
         &snapshot,
         &output,
     );
@@ -788,6 +4893,38 @@ fn build_verbose_items_includes_login_security_details() {
     );
 }
 
+#[test]
+fn build_verbose_items_lists_top_memory_processes() {
+    let mut snapshot = sample_snapshot();
+    snapshot.top_memory = vec![
+        MemoryProcess {
+            pid: 812,
+            name: "postgres".into(),
+            rss_kb: 2_097_152,
+        },
+        MemoryProcess {
+            pid: 1,
+            name: "systemd".into(),
+            rss_kb: 12_288,
+        },
+    ];
+
+    let items = default_verbose_items(&snapshot);
+    let top_memory = items
+        .iter()
+        .skip_while(|item| item.label != "Top memory:")
+        .take(2)
+        .map(|item| (item.label.as_str(), item.value.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        top_memory,
+        vec![
+            ("Top memory:", " 2.0 GB  postgres  pid 812"),
+            ("", "12.0 MB  systemd   pid 1"),
+        ]
+    );
+}
+
 #[cfg(feature = "remote-welcome")]
 #[test]
 fn write_and_read_welcome_cache_round_trip() {
@@ -1056,6 +5193,35 @@ fn remote_welcome_revalidates_stale_cache_on_http_304() {
     assert!(restored.fetched_at_secs > 1);
 }
 
+#[test]
+fn macos_parsers_read_sysctl_sw_vers_and_vm_stat_output() {
+    assert_eq!(
+        parse_sw_vers_output(
+            "ProductName:\t\tmacOS\nProductVersion:\t\t14.4.1\nBuildVersion:\t\t23E224\n"
+        ),
+        Some(("macOS".to_string(), "14.4.1".to_string()))
+    );
+    assert_eq!(
+        parse_boottime("{ sec = 1712345678, usec = 123456 } Fri Apr  5 19:34:38 2024"),
+        Some(1_712_345_678)
+    );
+    assert_eq!(parse_boottime("garbage"), None);
+    assert_eq!(
+        parse_swapusage("total = 2048.00M  used = 1536.00M  free = 512.00M  (encrypted)"),
+        Some((2_097_152, 524_288))
+    );
+    assert_eq!(
+        parse_vm_stat_available_kb(
+            "Mach Virtual Memory Statistics: (page size of 16384 bytes)\n\
+             Pages free:                               10.\n\
+             Pages active:                            500.\n\
+             Pages inactive:                           20.\n\
+             Pages speculative:                         2.\n"
+        ),
+        Some(32 * 16)
+    );
+}
+
 #[cfg(target_os = "linux")]
 #[test]
 fn count_logged_in_users_from_linux_utmp_file_counts_user_process_records() {
@@ -1099,21 +5265,38 @@ fn sample_snapshot() -> SystemSnapshot {
         main_ip: "10.10.8.24".to_string(),
         current_user: "admin".to_string(),
         from_ip: "10.10.1.15".to_string(),
+        from_host: None,
         login_user_count: 4,
         now_str_with_tz: "2026-01-15 09:30:00 +00:00".to_string(),
         uptime_str: "24 days, 18:42:11".to_string(),
+        uptime_secs: Some(2_141_331),
         load_average: "0.42 0.38 0.35".to_string(),
         os_name: "Rocky Linux".to_string(),
         os_version: "9.5".to_string(),
         kernel_version: "5.14.0-503.15.1.el9_5.x86_64".to_string(),
+        kernel_preemption: None,
         virt_info: Some("kvm".to_string()),
         cpu_brand: "2x AMD EPYC 9654".to_string(),
         cpu_count: 192,
+        cpu_physical_cores: Some(192),
+        cpu_online: Some(192),
+        cpu_quota: None,
+        cpu_topology: None,
+        memory_cgroup: false,
         memory: UsageSummary {
             used_gb: 384.0,
             total_gb: 1536.0,
             ratio: 25.0,
         },
+        swap_activity: None,
+        cpu_usage: None,
+        gpus: Vec::new(),
+        cpu_temperature: None,
+        batteries: Vec::new(),
+        net_throughput: None,
+        pressure: PressureStall::default(),
+        storage_pools: Vec::new(),
+        top_memory: Vec::new(),
         swap: UsageSummary {
             used_gb: 0.0,
             total_gb: 64.0,
@@ -1125,13 +5308,23 @@ fn sample_snapshot() -> SystemSnapshot {
             ratio: 15.62,
         }),
         disk_items: vec![
-            RenderedItem {
+            DiskUsageItem {
                 label: "Disk usage (root):".to_string(),
-                value: "/ => 1.20 TB/7.68 TB (15.62%)".to_string(),
+                mount_path: "/".to_string(),
+                fstype: "ext4".to_string(),
+                used_bytes: 1_319_413_953_331,
+                total_bytes: 8_444_249_301_319,
+                ratio: 15.62,
+                timed_out: false,
             },
-            RenderedItem {
+            DiskUsageItem {
                 label: "Disk usage (nfs):".to_string(),
-                value: "/NFS => 1.72 TB/1.97 TB (87.47%)".to_string(),
+                mount_path: "/NFS".to_string(),
+                fstype: "nfs4".to_string(),
+                used_bytes: 1_891_136_999_915,
+                total_bytes: 2_166_026_912_645,
+                ratio: 87.31,
+                timed_out: false,
             },
         ],
         last_login: LastLoginInfo::Recorded(LastLoginRecord {
@@ -1178,6 +5371,75 @@ fn sample_snapshot() -> SystemSnapshot {
             },
         ],
         update_summary: "2 package(s) via dnf".to_string(),
+        needrestart_count: Some(3),
+        auto_updates_enabled: Some(true),
+        reboot_required: Some(true),
+        failed_units: Vec::new(),
+        oom: Some(OomSummary {
+            count: 3,
+            last_victim: Some("java".to_string()),
+        }),
+        newer_release: Some("1.2.0".to_string()),
+        authorized_keys: vec![AuthorizedKeysFile {
+            owner: None,
+            keys: vec![AuthorizedKey {
+                key_type: "ssh-ed25519".to_string(),
+                fingerprint: "SHA256:aw3WNn8FeWIbfgl+D8690Oa0wFWYdRSyamS0G0fWZDQ".to_string(),
+                comment: "alice@laptop".to_string(),
+            }],
+        }],
+        top_cpu: Some(TopProcess {
+            name: "postgres".to_string(),
+            cpu_percent: 42.5,
+        }),
+        interface_addresses: Vec::new(),
+        process_count: None,
+        ssh_session_count: None,
+        mac_status: Some(MacStatus::SeLinux(SeLinuxMode::Permissive)),
+        container_storage: Some(ContainerStorage {
+            total_bytes: 42 * 1024 * 1024 * 1024,
+            reclaimable_bytes: 18 * 1024 * 1024 * 1024,
+        }),
+        containers: None,
+        network_links: vec![NetworkLink {
+            iface: "eth0".into(),
+            operstate: "up".into(),
+            speed_mbps: Some(10_000),
+            duplex: Some("full".into()),
+        }],
+        top_users: vec![
+            UserProcessCount {
+                user: "alice".to_string(),
+                processes: 412,
+            },
+            UserProcessCount {
+                user: "root".to_string(),
+                processes: 88,
+            },
+        ],
+        recent_logins: vec![RecentLogin {
+            when_secs: 1_768_467_000,
+            user: "admin".to_string(),
+            from: "10.10.1.15".to_string(),
+        }],
+        fd_usages: vec![FdUsageStatus {
+            label: "nginx".to_string(),
+            usage: Ok(FdUsage {
+                open: 3900,
+                limit: Some(4096),
+                connections: Some(212),
+            }),
+        }],
+        certificates: vec![
+            CertificateStatus {
+                label: "web".to_string(),
+                days_left: Ok(9),
+            },
+            CertificateStatus {
+                label: "api".to_string(),
+                days_left: Ok(120),
+            },
+        ],
         diagnostics: SnapshotDiagnostics {
             degraded_modules: Vec::new(),
             issues: Vec::new(),
@@ -1190,6 +5452,16 @@ fn sample_snapshot() -> SystemSnapshot {
             failed_login_source: "lastb".to_string(),
             service_status_source: "systemctl is-active".to_string(),
             updates_source: "dnf check-update --cacheonly".to_string(),
+            needrestart_source: "needrestart -b".to_string(),
+            auto_updates_source: String::new(),
+            reboot_required_source: String::new(),
+            recent_logins_source: "/root/.local/state/motdyn/recent_logins".to_string(),
+            oom_source: "/dev/kmsg".to_string(),
+            cpu_topology_source: String::new(),
+            container_storage_source: String::new(),
+            storage_pools_source: String::new(),
+            containers_source: String::new(),
+            update_check_source: "cache (latest 1.2.0)".to_string(),
         },
     }
 }
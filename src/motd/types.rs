@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::path::PathBuf;
 
@@ -15,9 +15,84 @@ pub(super) const LINUX_USER_PROCESS: i16 = 7;
 
 pub(super) const DEFAULT_WELCOME: &str = "Welcome!";
 pub(super) const DEFAULT_FAREWELL: &str = "Have a nice day!";
+pub(super) const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+pub(super) const DEFAULT_TIME_FORMAT_12H: &str = "%Y-%m-%d %I:%M:%S %p %:z";
+pub(super) const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+pub(super) const TIMEZONE_COMMAND_TIMEOUT_MS: u64 = 250;
 pub(super) const DEFAULT_WELCOME_TIMEOUT_MS: u64 = 250;
 pub(super) const DEFAULT_WELCOME_CACHE_TTL_SECS: u64 = 300;
 pub(super) const DEFAULT_WELCOME_CACHE_PATH: &str = "~/.cache/motdyn/welcome.txt";
+pub(super) const NEEDRESTART_CACHE_PATH: &str = "~/.cache/motdyn/needrestart.txt";
+pub(super) const NEEDRESTART_CACHE_TTL_SECS: u64 = 3600;
+pub(super) const NEEDRESTART_FAILURE_CACHE_TTL_SECS: u64 = 600;
+pub(super) const RELEASE_CHECK_CACHE_PATH: &str = "~/.cache/motdyn/latest_release.txt";
+pub(super) const RELEASE_CHECK_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+pub(super) const RELEASE_CHECK_FAILURE_CACHE_TTL_SECS: u64 = 60 * 60;
+pub(super) const RELEASE_CHECK_TIMEOUT_MS: u64 = 1000;
+pub(super) const CERT_EXPIRY_WARNING_DAYS: i64 = 14;
+pub(super) const TAB_WIDTH: usize = 8;
+/// `TASK_COMM_LEN` less its terminating NUL.
+pub(super) const PROC_COMM_MAX_BYTES: usize = 15;
+/// `UsageSummary` figures are in GB of this many bytes.
+pub(super) const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+pub(super) const TOP_CPU_SAMPLE_MS: u64 = 200;
+pub(super) const SWAP_ACTIVITY_SAMPLE_MS: u64 = 200;
+pub(super) const DEFAULT_CPU_USAGE_SAMPLE_MS: u64 = 200;
+pub(super) const GPU_PROBE_TIMEOUT_MS: u64 = 500;
+pub(super) const DEFAULT_BATTERY_LOW: f64 = 20.0;
+/// Share of the last 10 seconds some task stalled on a resource, in percent.
+pub(super) const DEFAULT_PRESSURE_THRESHOLD: Threshold = Threshold {
+    warn: 10.0,
+    crit: 25.0,
+};
+pub(super) const DEFAULT_LOAD_THRESHOLD: Threshold = Threshold {
+    warn: 1.0,
+    crit: 2.0,
+};
+pub(super) const TOP_MEMORY_PROCESS_COUNT: usize = 3;
+pub(super) const REVERSE_DNS_TIMEOUT_MS: u64 = 300;
+pub(super) const MAX_PROC_SCAN_ENTRIES: usize = 4096;
+/// Tick rate of the `/proc/<pid>/stat` CPU counters; fixed at 100 on Linux.
+pub(super) const PROC_STAT_TICKS_PER_SEC: u64 = 100;
+pub(super) const TOP_USERS_LIMIT: usize = 3;
+pub(super) const SSH_PORT: u16 = 22;
+pub(super) const DEFAULT_DISK_TIMEOUT_MS: u64 = 2000;
+pub(super) const DEFAULT_PROGRESS_BAR_WIDTH: usize = 20;
+pub(super) const FALLBACK_TERMINAL_WIDTH: usize = 80;
+pub(super) const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "overlay",
+    "proc",
+    "pstore",
+    "ramfs",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "squashfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+];
+pub(super) const REBOOT_REQUIRED_PATH: &str = "/var/run/reboot-required";
+pub(super) const UPDATE_NOTIFIER_PATH: &str = "/var/lib/update-notifier/updates-available";
+pub(super) const CONTAINER_STORAGE_TIMEOUT_MS: u64 = 2000;
+pub(super) const STORAGE_POOL_TIMEOUT_MS: u64 = 2000;
+pub(super) const DOCKER_SOCKET_PATH: &str = "/var/run/docker.sock";
+pub(super) const DEFAULT_RECENT_LOGINS_LIMIT: usize = 5;
+pub(super) const DEFAULT_RECENT_LOGINS_STATE_PATH: &str = "~/.local/state/motdyn/recent_logins";
 #[cfg(feature = "remote-welcome")]
 pub(super) const MAX_WELCOME_BODY_BYTES: usize = 8 * 1024;
 
@@ -27,7 +102,36 @@ pub struct RenderContext {
     pub system_config_status: String,
     pub user_config_path: String,
     pub user_config_status: String,
+    /// `MOTDYN_*` variables applied on top of both config files.
+    pub env_overrides: Vec<String>,
+    /// Each key set by a config layer, with the layer it was taken from:
+    /// `farewell (env)`.
+    pub config_origins: Vec<String>,
     pub config_notes: Vec<String>,
+    pub session: String,
+    pub mode: RenderMode,
+}
+
+/// Why the banner is being rendered, which decides what may be recorded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderMode {
+    /// A real login: recent logins, syslog and the output cache are updated.
+    #[default]
+    Login,
+    /// `motdyn preview`: rendered as a login would be, but nothing is recorded.
+    Preview,
+    /// `motdyn generate`: like a preview, minus the per-session modules,
+    /// since the text is written once and shown to every later login.
+    Static,
+}
+
+/// What `collect_snapshot` may do besides reading the system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SnapshotOptions {
+    /// Off when the output cache already holds the disk lines and alerts.
+    pub(super) probe_disks: bool,
+    /// Off outside real logins, so `recent_logins` only reads its state file.
+    pub(super) record_login: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -49,6 +153,27 @@ pub(super) enum ModuleKind {
     FailedLogin,
     Services,
     Updates,
+    NeedRestart,
+    Certificates,
+    FdUsage,
+    RecentLogins,
+    MemoryAvailable,
+    Oom,
+    TopCpu,
+    TopUsers,
+    Mac,
+    ContainerStorage,
+    Link,
+    AutoUpdates,
+    RebootRequired,
+    UpdateCheck,
+    AuthorizedKeys,
+    Temperature,
+    Processes,
+    Addresses,
+    FailedUnits,
+    Battery,
+    Pools,
 }
 
 impl ModuleKind {
@@ -71,8 +196,45 @@ impl ModuleKind {
             Self::FailedLogin => "failed_login",
             Self::Services => "services",
             Self::Updates => "updates",
+            Self::NeedRestart => "needrestart",
+            Self::Certificates => "certificates",
+            Self::FdUsage => "fd_usage",
+            Self::RecentLogins => "recent_logins",
+            Self::MemoryAvailable => "memory_available",
+            Self::Oom => "oom",
+            Self::TopCpu => "top_cpu",
+            Self::TopUsers => "top_users",
+            Self::Mac => "mac",
+            Self::ContainerStorage => "container_storage",
+            Self::Link => "link",
+            Self::AutoUpdates => "auto_updates",
+            Self::RebootRequired => "reboot_required",
+            Self::UpdateCheck => "update_check",
+            Self::AuthorizedKeys => "authorized_keys",
+            Self::Temperature => "temperature",
+            Self::Processes => "processes",
+            Self::Addresses => "addresses",
+            Self::FailedUnits => "failed_units",
+            Self::Battery => "battery",
+            Self::Pools => "pools",
         }
     }
+
+    /// Modules whose value differs per login, so the output cache never
+    /// holds them.
+    pub(super) fn is_per_session(self) -> bool {
+        matches!(
+            self,
+            Self::User
+                | Self::Time
+                | Self::Uptime
+                | Self::LastLogin
+                | Self::FailedLogin
+                | Self::RecentLogins
+                | Self::AuthorizedKeys
+                | Self::Processes
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -105,12 +267,219 @@ pub(super) struct ModuleSelection {
     pub(super) source: ModuleSource,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct RenderedItem {
     pub(super) label: String,
     pub(super) value: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CertificateStatus {
+    pub(super) label: String,
+    pub(super) days_left: Result<i64, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FdUsageStatus {
+    pub(super) label: String,
+    pub(super) usage: Result<FdUsage, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct FdUsage {
+    pub(super) open: usize,
+    pub(super) limit: Option<u64>,
+    /// Established TCP connections; `None` when the tables were unreadable.
+    pub(super) connections: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct RecentLogin {
+    pub(super) when_secs: u64,
+    pub(super) user: String,
+    pub(super) from: String,
+}
+
+/// Swap traffic between two `/proc/vmstat` samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SwapActivity {
+    pub(super) in_bytes_per_sec: u64,
+    pub(super) out_bytes_per_sec: u64,
+}
+
+/// Received and transmitted bytes summed over every non-loopback interface:
+/// totals since boot, or per second when `per_sec` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct NetThroughput {
+    pub(super) rx_bytes: u64,
+    pub(super) tx_bytes: u64,
+    pub(super) per_sec: bool,
+}
+
+/// Socket and NUMA layout reported by `lscpu`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct CpuTopology {
+    pub(super) sockets: usize,
+    pub(super) cores_per_socket: usize,
+    pub(super) threads_per_core: usize,
+    pub(super) numa_nodes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct OomSummary {
+    pub(super) count: usize,
+    pub(super) last_victim: Option<String>,
+}
+
+/// One mounted filesystem; the line is formatted at render time.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct DiskUsageItem {
+    pub(super) label: String,
+    pub(super) mount_path: String,
+    pub(super) fstype: String,
+    pub(super) used_bytes: u64,
+    pub(super) total_bytes: u64,
+    pub(super) ratio: f64,
+    pub(super) timed_out: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct AuthorizedKey {
+    pub(super) key_type: String,
+    pub(super) fingerprint: String,
+    pub(super) comment: String,
+}
+
+/// Keys from one `authorized_keys` file; `owner` is `None` for the current user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct AuthorizedKeysFile {
+    pub(super) owner: Option<String>,
+    pub(super) keys: Vec<AuthorizedKey>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TopProcess {
+    pub(super) name: String,
+    pub(super) cpu_percent: f64,
+}
+
+/// A process and its resident memory from `/proc/<pid>/status`, for `--verbose`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct MemoryProcess {
+    pub(super) pid: u32,
+    pub(super) name: String,
+    pub(super) rss_kb: u64,
+}
+
+/// Preemption model and tick rate of the running kernel, for `--verbose`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct KernelPreemption {
+    pub(super) realtime: bool,
+    pub(super) tick_hz: Option<u32>,
+}
+
+/// State of a physical network interface from `/sys/class/net`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct NetworkLink {
+    pub(super) iface: String,
+    pub(super) operstate: String,
+    pub(super) speed_mbps: Option<u32>,
+    pub(super) duplex: Option<String>,
+}
+
+/// One address from `ip -o addr`; `link_local` marks `fe80::/10` IPv6
+/// addresses, which are only listed with `--verbose`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct InterfaceAddress {
+    pub(super) iface: String,
+    pub(super) address: String,
+    pub(super) link_local: bool,
+}
+
+/// One `/sys/class/power_supply/BAT*` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct BatteryStatus {
+    pub(super) name: String,
+    pub(super) capacity: u8,
+    pub(super) status: String,
+}
+
+/// One NVIDIA device from `nvidia-smi`; memory is `None` when the driver
+/// reports `[N/A]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct GpuInfo {
+    pub(super) name: String,
+    pub(super) memory_used_mib: Option<u64>,
+    pub(super) memory_total_mib: Option<u64>,
+}
+
+/// The `some` averages of one `/proc/pressure/*` file: the share of time,
+/// in percent, that at least one task stalled on the resource.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct PressureAverages {
+    pub(super) avg10: f64,
+    pub(super) avg60: f64,
+    pub(super) avg300: f64,
+}
+
+/// Pressure stall information; `None` for resources whose module is off or
+/// whose file is missing, as on kernels built without PSI.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct PressureStall {
+    pub(super) cpu: Option<PressureAverages>,
+    pub(super) memory: Option<PressureAverages>,
+    pub(super) io: Option<PressureAverages>,
+}
+
+/// Space used by container images, containers, volumes and build cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ContainerStorage {
+    pub(super) total_bytes: u64,
+    pub(super) reclaimable_bytes: u64,
+}
+
+/// Running and total (including stopped) containers of one runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ContainerCounts {
+    pub(super) running: usize,
+    pub(super) total: usize,
+}
+
+/// Active mandatory access control LSM and its mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MacStatus {
+    SeLinux(SeLinuxMode),
+    /// Profile counts are `(enforce, complain)` and need root to read.
+    AppArmor {
+        enabled: bool,
+        profiles: Option<(usize, usize)>,
+    },
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SeLinuxMode {
+    Enforcing,
+    Permissive,
+    Disabled,
+}
+
+impl SeLinuxMode {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            Self::Enforcing => "Enforcing",
+            Self::Permissive => "Permissive",
+            Self::Disabled => "Disabled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct UserProcessCount {
+    pub(super) user: String,
+    pub(super) processes: usize,
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct SystemSnapshot {
     pub(super) host_name: String,
@@ -118,24 +487,66 @@ pub(super) struct SystemSnapshot {
     pub(super) main_ip: String,
     pub(super) current_user: String,
     pub(super) from_ip: String,
+    /// Reverse DNS name of `from_ip` when `resolve_hostname` is on.
+    pub(super) from_host: Option<String>,
     pub(super) login_user_count: usize,
     pub(super) now_str_with_tz: String,
     pub(super) uptime_str: String,
+    pub(super) uptime_secs: Option<u64>,
     pub(super) load_average: String,
     pub(super) os_name: String,
     pub(super) os_version: String,
     pub(super) kernel_version: String,
+    pub(super) kernel_preemption: Option<KernelPreemption>,
     pub(super) virt_info: Option<String>,
     pub(super) cpu_brand: String,
     pub(super) cpu_count: usize,
+    pub(super) cpu_physical_cores: Option<usize>,
+    pub(super) cpu_online: Option<usize>,
+    pub(super) cpu_quota: Option<f64>,
+    pub(super) cpu_topology: Option<CpuTopology>,
     pub(super) memory: UsageSummary,
+    pub(super) memory_cgroup: bool,
     pub(super) swap: UsageSummary,
+    pub(super) swap_activity: Option<SwapActivity>,
+    /// Busy share of all CPUs between two `/proc/stat` samples, in percent.
+    pub(super) cpu_usage: Option<f64>,
+    pub(super) gpus: Vec<GpuInfo>,
+    /// Hottest CPU sensor in degrees Celsius; `None` without thermal zones.
+    pub(super) cpu_temperature: Option<f64>,
+    pub(super) batteries: Vec<BatteryStatus>,
+    pub(super) net_throughput: Option<NetThroughput>,
+    pub(super) pressure: PressureStall,
+    /// ZFS pools and Btrfs filesystems as their own tools count them.
+    pub(super) storage_pools: Vec<DiskUsageItem>,
+    /// Largest resident processes; only filled in for `--verbose`.
+    pub(super) top_memory: Vec<MemoryProcess>,
     pub(super) root_disk: Option<UsageSummary>,
-    pub(super) disk_items: Vec<RenderedItem>,
+    pub(super) disk_items: Vec<DiskUsageItem>,
     pub(super) last_login: LastLoginInfo,
     pub(super) failed_login: FailedLoginInfo,
     pub(super) service_items: Vec<RenderedItem>,
     pub(super) update_summary: String,
+    pub(super) needrestart_count: Option<usize>,
+    pub(super) auto_updates_enabled: Option<bool>,
+    pub(super) reboot_required: Option<bool>,
+    pub(super) failed_units: Vec<String>,
+    pub(super) certificates: Vec<CertificateStatus>,
+    pub(super) fd_usages: Vec<FdUsageStatus>,
+    pub(super) recent_logins: Vec<RecentLogin>,
+    pub(super) oom: Option<OomSummary>,
+    pub(super) top_cpu: Option<TopProcess>,
+    pub(super) top_users: Vec<UserProcessCount>,
+    pub(super) process_count: Option<usize>,
+    /// Established TCP connections on the local SSH port.
+    pub(super) ssh_session_count: Option<usize>,
+    pub(super) mac_status: Option<MacStatus>,
+    pub(super) container_storage: Option<ContainerStorage>,
+    pub(super) containers: Option<ContainerCounts>,
+    pub(super) network_links: Vec<NetworkLink>,
+    pub(super) interface_addresses: Vec<InterfaceAddress>,
+    pub(super) newer_release: Option<String>,
+    pub(super) authorized_keys: Vec<AuthorizedKeysFile>,
     pub(super) diagnostics: SnapshotDiagnostics,
 }
 
@@ -300,6 +711,7 @@ pub(super) enum WelcomeIssue {
     LocalFileEmpty {
         path: PathBuf,
     },
+    AsciiArtFileIgnored(String),
     #[cfg(feature = "remote-welcome")]
     UnsupportedUrlScheme(String),
     #[cfg(not(feature = "remote-welcome"))]
@@ -363,6 +775,11 @@ impl fmt::Display for WelcomeIssue {
             Self::LocalFileEmpty { path } => {
                 write!(f, "local welcome file '{}' was empty", path.display())
             }
+            Self::AsciiArtFileIgnored(path) => write!(
+                f,
+                "ascii_art_file '{}' ignored because welcome is also set",
+                path
+            ),
             #[cfg(feature = "remote-welcome")]
             Self::UnsupportedUrlScheme(scheme) => write!(
                 f,
@@ -488,6 +905,28 @@ pub(super) enum ProbeIssue {
     FailedLoginProbeFailed(String),
     ServiceStatusProbeFailed(String),
     UpdateProbeFailed(String),
+    NeedRestartProbeFailed(String),
+    CertificateCheckFailed { label: String, message: String },
+    FdUsageProbeFailed { label: String, message: String },
+    RecentLoginsFailed(String),
+    OomProbeFailed(String),
+    TopCpuProbeFailed(String),
+    TopUsersProbeFailed(String),
+    ProcessCountFailed(String),
+    InterfaceAddressesFailed(String),
+    ContainerStorageFailed(String),
+    StoragePoolsFailed(String),
+    ContainerCountFailed(String),
+    FailedUnitsProbeFailed(String),
+    DiskUsageTimedOut(String),
+    CpuTopologyFailed(String),
+    SwapActivityFailed(String),
+    NetThroughputFailed(String),
+    CpuUsageFailed(String),
+    GpuProbeFailed(String),
+    TopMemoryFailed(String),
+    UpdateCheckFailed(String),
+    AuthorizedKeysFailed { path: String, message: String },
 }
 
 impl fmt::Display for ProbeIssue {
@@ -539,6 +978,72 @@ impl fmt::Display for ProbeIssue {
             Self::UpdateProbeFailed(message) => {
                 write!(f, "updates: {}", message)
             }
+            Self::NeedRestartProbeFailed(message) => {
+                write!(f, "needrestart: {}", message)
+            }
+            Self::CertificateCheckFailed { label, message } => {
+                write!(f, "certificates: {}: {}", label, message)
+            }
+            Self::FdUsageProbeFailed { label, message } => {
+                write!(f, "fd_usage: {}: {}", label, message)
+            }
+            Self::RecentLoginsFailed(message) => {
+                write!(f, "recent_logins: {}", message)
+            }
+            Self::OomProbeFailed(message) => {
+                write!(f, "oom: {}", message)
+            }
+            Self::TopCpuProbeFailed(message) => {
+                write!(f, "top_cpu: {}", message)
+            }
+            Self::TopUsersProbeFailed(message) => {
+                write!(f, "top_users: {}", message)
+            }
+            Self::ProcessCountFailed(message) => {
+                write!(f, "processes: {}", message)
+            }
+            Self::InterfaceAddressesFailed(message) => {
+                write!(f, "addresses: {}", message)
+            }
+            Self::ContainerStorageFailed(message) => {
+                write!(f, "container_storage: {}", message)
+            }
+            Self::StoragePoolsFailed(message) => {
+                write!(f, "pools: {}", message)
+            }
+            Self::ContainerCountFailed(message) => {
+                write!(f, "containers: {}", message)
+            }
+            Self::FailedUnitsProbeFailed(message) => {
+                write!(f, "failed_units: {}", message)
+            }
+            Self::DiskUsageTimedOut(mount_path) => {
+                write!(f, "disk: statvfs '{}' timed out", mount_path)
+            }
+            Self::CpuTopologyFailed(message) => {
+                write!(f, "cpu: lscpu: {}", message)
+            }
+            Self::SwapActivityFailed(message) => {
+                write!(f, "swap: {}", message)
+            }
+            Self::NetThroughputFailed(message) => {
+                write!(f, "network: {}", message)
+            }
+            Self::CpuUsageFailed(message) => {
+                write!(f, "cpu: {}", message)
+            }
+            Self::GpuProbeFailed(message) => {
+                write!(f, "cpu: nvidia-smi: {}", message)
+            }
+            Self::TopMemoryFailed(message) => {
+                write!(f, "top_memory: {}", message)
+            }
+            Self::UpdateCheckFailed(message) => {
+                write!(f, "update_check: {}", message)
+            }
+            Self::AuthorizedKeysFailed { path, message } => {
+                write!(f, "authorized_keys {}: {}", path, message)
+            }
         }
     }
 }
@@ -556,6 +1061,16 @@ pub(super) struct SnapshotDiagnostics {
     pub(super) failed_login_source: String,
     pub(super) service_status_source: String,
     pub(super) updates_source: String,
+    pub(super) needrestart_source: String,
+    pub(super) auto_updates_source: String,
+    pub(super) reboot_required_source: String,
+    pub(super) recent_logins_source: String,
+    pub(super) oom_source: String,
+    pub(super) cpu_topology_source: String,
+    pub(super) container_storage_source: String,
+    pub(super) storage_pools_source: String,
+    pub(super) containers_source: String,
+    pub(super) update_check_source: String,
 }
 
 impl SnapshotDiagnostics {
@@ -571,6 +1086,110 @@ impl SnapshotDiagnostics {
     }
 }
 
+/// Which mounts get a disk line; `RootAndNfs` is the classic view.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) enum DiskMountSelection {
+    #[default]
+    RootAndNfs,
+    AllLocal,
+    Only(Vec<String>),
+}
+
+/// A color accepted in the `[colors]` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ThemeColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl ThemeColor {
+    pub(super) fn parse(raw: &str) -> Option<Self> {
+        match raw
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['-', ' '], "_")
+            .as_str()
+        {
+            "black" => Some(Self::Black),
+            "red" => Some(Self::Red),
+            "green" => Some(Self::Green),
+            "yellow" => Some(Self::Yellow),
+            "blue" => Some(Self::Blue),
+            "magenta" | "purple" => Some(Self::Magenta),
+            "cyan" => Some(Self::Cyan),
+            "white" => Some(Self::White),
+            "bright_black" | "gray" | "grey" => Some(Self::BrightBlack),
+            "bright_red" => Some(Self::BrightRed),
+            "bright_green" => Some(Self::BrightGreen),
+            "bright_yellow" => Some(Self::BrightYellow),
+            "bright_blue" => Some(Self::BrightBlue),
+            "bright_magenta" | "bright_purple" => Some(Self::BrightMagenta),
+            "bright_cyan" => Some(Self::BrightCyan),
+            "bright_white" => Some(Self::BrightWhite),
+            _ => None,
+        }
+    }
+}
+
+/// Scale the `Temperature:` line is printed in; readings stay in Celsius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub(super) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_uppercase().as_str() {
+            "C" => Some(Self::Celsius),
+            "F" => Some(Self::Fahrenheit),
+            _ => None,
+        }
+    }
+
+    pub(super) fn format(self, celsius: f64) -> String {
+        match self {
+            Self::Celsius => format!("{:.1}°C", celsius),
+            Self::Fahrenheit => format!("{:.1}°F", celsius * 9.0 / 5.0 + 32.0),
+        }
+    }
+}
+
+/// How disk and memory lines frame usage; `UsedTotal` is the classic view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum UsageDisplay {
+    #[default]
+    UsedTotal,
+    UsedFree,
+    FreeTotal,
+}
+
+impl UsageDisplay {
+    pub(super) fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "used_total" => Some(Self::UsedTotal),
+            "used_free" => Some(Self::UsedFree),
+            "free_total" => Some(Self::FreeTotal),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(super) enum HiddenField {
     MainInterface,
@@ -591,12 +1210,105 @@ pub(super) struct OutputSettings {
     pub(super) section_headers: bool,
     pub(super) hidden_fields: HashSet<HiddenField>,
     pub(super) ignored_hidden_fields: Vec<String>,
+    pub(super) max_disk_lines: Option<usize>,
+    pub(super) disk_display: UsageDisplay,
+    pub(super) display_hostname: Option<String>,
+    pub(super) disk_no_alert: Vec<String>,
+    pub(super) disk_labels: BTreeMap<String, String>,
+    pub(super) colors: HashMap<ModuleKind, ThemeColor>,
+    pub(super) ignored_colors: Vec<String>,
+    pub(super) thresholds: UsageThresholds,
+    /// Width of the bar drawn before memory, swap and disk figures; `None`
+    /// when `progress_bars` is off.
+    pub(super) progress_bar_width: Option<usize>,
+    /// Columns a module line may use before its value is cut with `…`;
+    /// `None` leaves lines as long as they come.
+    pub(super) line_width: Option<usize>,
+    pub(super) temperature_unit: TemperatureUnit,
+    /// Line printed between the info block and the first storage line.
+    pub(super) separator: Option<String>,
+    /// Set while `environment` names a known or configured environment.
+    pub(super) environment: Option<EnvironmentStyle>,
+}
+
+/// How the active `environment` marks the banner: a background for the
+/// host name and an optional line above the welcome text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct EnvironmentStyle {
+    pub(super) name: String,
+    pub(super) color: Option<ThemeColor>,
+    pub(super) banner: Option<String>,
+}
+
+/// Percentages (degrees Celsius for temperature) at which a figure turns
+/// yellow (`warn`) and red (`crit`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Threshold {
+    pub(super) warn: f64,
+    pub(super) crit: f64,
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Self {
+            warn: 75.0,
+            crit: 90.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct UsageThresholds {
+    pub(super) memory: Threshold,
+    pub(super) swap: Threshold,
+    pub(super) disk: Threshold,
+    pub(super) temperature: Threshold,
+    /// 1-minute load per online core; only `--check` judges it.
+    pub(super) load: Threshold,
+    /// `avg10` of the `some` line in `/proc/pressure/*`.
+    pub(super) pressure: Threshold,
+    /// Battery charge, in percent, below which it is shown in red.
+    pub(super) battery_low: f64,
+}
+
+impl Default for UsageThresholds {
+    fn default() -> Self {
+        Self {
+            memory: Threshold::default(),
+            swap: Threshold::default(),
+            disk: Threshold::default(),
+            temperature: Threshold::default(),
+            load: DEFAULT_LOAD_THRESHOLD,
+            pressure: DEFAULT_PRESSURE_THRESHOLD,
+            battery_low: DEFAULT_BATTERY_LOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SpacingSettings {
+    pub(super) before_welcome: usize,
+    pub(super) after_welcome: usize,
+    pub(super) before_farewell: usize,
+    pub(super) gutter: usize,
 }
 
 impl OutputSettings {
     pub(super) fn hidden(&self, field: HiddenField) -> bool {
         self.hidden_fields.contains(&field)
     }
+
+    /// Mounts listed in `disk_no_alert` are shown but never flagged.
+    pub(super) fn disk_alerts_enabled(&self, mount_path: &str) -> bool {
+        !self.disk_no_alert.iter().any(|path| path == mount_path)
+    }
+
+    /// The `[disk_labels]` name for a mount, or the mount path itself.
+    pub(super) fn disk_label<'a>(&'a self, mount_path: &'a str) -> &'a str {
+        self.disk_labels
+            .get(mount_path)
+            .map_or(mount_path, String::as_str)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -620,7 +1332,7 @@ impl SectionKind {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum PaintKind {
     Label,
     Header,
@@ -630,4 +1342,5 @@ pub(super) enum PaintKind {
     Red,
     Green,
     Magenta,
+    Alert,
 }
@@ -40,10 +40,40 @@ struct RemoteFetchResult {
 pub(super) fn resolve_welcome_text(cfg: &MotdConfig) -> WelcomeResolution {
     let settings = resolve_remote_welcome_settings(cfg);
     let sources = configured_welcome_sources(cfg);
+    let art_file = cfg.ascii_art_file.as_deref();
     if sources.is_empty() {
-        return default_welcome(settings);
+        return match art_file {
+            Some(path) => resolve_ascii_art_file(path, settings),
+            None => default_welcome(settings),
+        };
+    }
+
+    let mut resolution = resolve_welcome_sources(sources, settings);
+    if let Some(path) = art_file {
+        resolution
+            .warnings
+            .push(WelcomeIssue::AsciiArtFileIgnored(path.to_string()));
     }
+    resolution
+}
+
+/// Reads `ascii_art_file` as a plain path, even when it does not look like
+/// one (`art.txt`); an unreadable or empty file falls back to the default.
+fn resolve_ascii_art_file(path: &str, settings: RemoteWelcomeSettings) -> WelcomeResolution {
+    match resolve_local_file_source(expand_tilde(path), path.to_string(), &settings) {
+        WelcomeAttempt::Resolved(resolution) => resolution,
+        WelcomeAttempt::Unusable(warnings) => {
+            let mut resolution = default_welcome(settings);
+            resolution.warnings = warnings;
+            resolution
+        }
+    }
+}
 
+fn resolve_welcome_sources(
+    sources: Vec<String>,
+    settings: RemoteWelcomeSettings,
+) -> WelcomeResolution {
     let mut accumulated_warnings = Vec::new();
     for source in sources {
         match resolve_welcome_source(&source, &settings) {